@@ -49,6 +49,11 @@ pub struct SchemaSection {
     pub label: String,
     pub fields: BTreeMap<String, String>,
     pub over: Vec<String>,
+    /// Superseded schema; references to it are reported as `DeprecatedSchemaUsed`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub deprecated_note: String,
 
     // Implementation metadata for diff-impl
     pub impl_bind: Option<String>,
@@ -56,6 +61,51 @@ pub struct SchemaSection {
     pub impl_path: Option<String>,
 }
 
+/// Annotations a `fields` entry may carry after its type.
+pub const FIELD_ANNOTATIONS: &[&str] = &["unique", "indexed"];
+
+/// A `fields` entry split into its type and `@annotations`, e.g.
+/// `email = "string @unique @indexed"`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FieldSpec {
+    pub name: String,
+    pub r#type: String,
+    /// Annotation names without the `@`, as written (duplicates kept)
+    pub annotations: Vec<String>,
+}
+
+impl FieldSpec {
+    pub fn parse(name: &str, value: &str) -> Self {
+        let mut type_words = Vec::new();
+        let mut annotations = Vec::new();
+        for word in value.split_whitespace() {
+            match word.strip_prefix('@') {
+                Some(annotation) => annotations.push(annotation.to_string()),
+                None => type_words.push(word),
+            }
+        }
+        FieldSpec {
+            name: name.to_string(),
+            r#type: type_words.join(" "),
+            annotations,
+        }
+    }
+
+    pub fn has(&self, annotation: &str) -> bool {
+        self.annotations.iter().any(|a| a == annotation)
+    }
+}
+
+impl SchemaSection {
+    /// `fields` parsed into types and annotations, ordered by field name.
+    pub fn field_specs(&self) -> Vec<FieldSpec> {
+        self.fields
+            .iter()
+            .map(|(name, value)| FieldSpec::parse(name, value))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct FuncSection {
     pub name: String,
@@ -74,9 +124,27 @@ pub struct FuncSection {
 pub struct ModSection {
     pub name: String,
     pub purpose: String,
+    /// Optional hint such as `"http"` (`export openapi` only accepts HTTP modules)
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub kind: String,
     pub schemas: Vec<String>,
     pub funcs: Vec<String>,
+    /// Every pipeline step in order, flattened from `pipeline_chains`
     pub pipeline: Vec<String>,
+    /// Pipeline chains as written (`pipeline = ["func.a -> func.b", "func.c"]`).
+    /// Each chain is a list of ordered stages; steps within a stage are
+    /// parallel branches, and one chain does not feed the next.
+    pub pipeline_chains: Vec<Vec<Vec<String>>>,
+    /// Teams or people responsible for the module (`owners = ["team-auth"]`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+}
+
+impl ModSection {
+    /// All stages of every chain, in order.
+    pub fn pipeline_stages(&self) -> Vec<Vec<String>> {
+        self.pipeline_chains.concat()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -91,4 +159,7 @@ pub struct ModuleStatus {
     pub state: String,
     pub coverage: f64,
     pub notes: String,
+    // Date of the last `status set` on this module; empty if never stamped
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub updated_at: String,
 }
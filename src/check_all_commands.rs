@@ -0,0 +1,164 @@
+use survibe_parser_rs::text_table::TextTable;
+use survibe_parser_rs::{
+    check_deploy_file, check_surv_file, parse_deploy_file_from_path,
+    parse_surv_file_with_unknown_keys, Diagnostic,
+};
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::markers;
+
+enum IrKind {
+    Surv,
+    Deploy,
+    // A project manifest (`[project]`); checked by project-check instead
+    Manifest,
+}
+
+struct FileResult {
+    path: PathBuf,
+    kind: IrKind,
+    errors: usize,
+    warnings: usize,
+}
+
+pub fn run_check_all(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        print_check_all_usage();
+        if args.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(other) = args.get(1) {
+        return Err(format!("Unknown option: {}", other).into());
+    }
+
+    let dir = Path::new(&args[0]);
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()).into());
+    }
+
+    let mut paths: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    if paths.is_empty() {
+        println!("No .toml files found in {}", dir.display());
+        return Ok(());
+    }
+
+    let mut results = Vec::new();
+    for path in paths {
+        results.push(check_one(path)?);
+    }
+
+    let mut table = TextTable::new(&["FILE", "KIND", "ERRORS", "WARNINGS", "RESULT"])
+        .align_right(2)
+        .align_right(3);
+    for result in &results {
+        let (kind, outcome) = match result.kind {
+            IrKind::Surv => ("surv", pass_fail(result)),
+            IrKind::Deploy => ("deploy", pass_fail(result)),
+            IrKind::Manifest => ("manifest", "skipped".to_string()),
+        };
+        table.add_row(vec![
+            result.path.display().to_string(),
+            kind.to_string(),
+            result.errors.to_string(),
+            result.warnings.to_string(),
+            outcome,
+        ]);
+    }
+    print!("{}", table.render());
+
+    let failed = results.iter().filter(|r| r.errors > 0).count();
+    let checked = results
+        .iter()
+        .filter(|r| !matches!(r.kind, IrKind::Manifest))
+        .count();
+    println!();
+    println!("{} file(s) checked, {} failed", checked, failed);
+
+    if failed > 0 {
+        Err(format!("{} file(s) with errors", failed).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn pass_fail(result: &FileResult) -> String {
+    if result.errors > 0 {
+        format!("{} fail", markers::err())
+    } else {
+        format!("{} pass", markers::ok())
+    }
+}
+
+/// Classify a file by its table headers and run the matching checker.
+/// A file that fails to parse counts as one error.
+fn check_one(path: PathBuf) -> Result<FileResult, Box<dyn Error>> {
+    let content = fs::read_to_string(&path)?;
+    let headers: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('['))
+        .collect();
+    let kind = if headers.iter().any(|h| h.starts_with("[deploy.") || *h == "[deploy]") {
+        IrKind::Deploy
+    } else if headers.contains(&"[project]") {
+        IrKind::Manifest
+    } else {
+        IrKind::Surv
+    };
+
+    let diags: Result<Vec<Diagnostic>, Box<dyn Error>> = match kind {
+        IrKind::Surv => parse_surv_file_with_unknown_keys(File::open(&path)?)
+            .map(|(file, mut diags)| {
+                diags.extend(check_surv_file(&file));
+                diags
+            })
+            .map_err(Into::into),
+        IrKind::Deploy => parse_deploy_file_from_path(&path)
+            .map(|deploy| check_deploy_file(&deploy))
+            .map_err(Into::into),
+        IrKind::Manifest => Ok(Vec::new()),
+    };
+
+    let (errors, warnings) = match diags {
+        Ok(diags) => (
+            diags.iter().filter(|d| d.severity == "error").count(),
+            diags.iter().filter(|d| d.severity == "warning").count(),
+        ),
+        Err(err) => {
+            eprintln!("{} {}: {}", markers::err(), path.display(), err);
+            (1, 0)
+        }
+    };
+
+    Ok(FileResult {
+        path,
+        kind,
+        errors,
+        warnings,
+    })
+}
+
+fn print_check_all_usage() {
+    eprintln!("Usage: surc check-all <dir>");
+    eprintln!();
+    eprintln!("Check every .toml file under a directory with the matching checker:");
+    eprintln!("files with [deploy.*] tables as Deploy IR, others as Surv IR.");
+    eprintln!("Project manifests ([project]) are skipped; use project-check for those.");
+    eprintln!("Exits non-zero if any file has errors; run check/deploy-check on a file for details.");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc check-all design/");
+}
@@ -1,5 +1,6 @@
-use crate::ast::{FuncSection, ModSection, SchemaSection, Section, SurvFile};
+use crate::ast::{FuncSection, ModSection, SchemaSection, Section, SurvFile, FIELD_ANNOTATIONS};
 use crate::diagnostic::Diagnostic;
+use crate::suggest::closest_match;
 use std::collections::{BTreeMap, BTreeSet};
 
 pub fn check_surv_file(file: &SurvFile) -> Vec<Diagnostic> {
@@ -8,8 +9,14 @@ pub fn check_surv_file(file: &SurvFile) -> Vec<Diagnostic> {
     check_func_schemas(&index, &mut diags);
     check_mod_references(&index, &mut diags);
     check_schema_links(&index, &mut diags);
+    check_deprecated_schemas(&index, &mut diags);
     check_pipeline_semantics(&index, &mut diags);
+    check_dead_pipeline_outputs(&index, &mut diags);
+    check_pipeline_data_flow(&index, &mut diags);
+    check_pipeline_order(&index, &mut diags);
     check_unused_definitions(&index, &mut diags);
+    check_status_entries(file, &index, &mut diags);
+    check_field_annotations(&index, &mut diags);
     diags
 }
 
@@ -17,14 +24,320 @@ pub fn check_surv_ast(file: &SurvFile) -> Vec<Diagnostic> {
     check_surv_file(file)
 }
 
-struct FileIndex<'a> {
-    schemas: BTreeMap<String, &'a SchemaSection>,
-    funcs: BTreeMap<String, &'a FuncSection>,
-    mods: BTreeMap<String, &'a ModSection>,
+/// Opt-in check (`check --require-impl`): warn for every schema and func
+/// without `impl.bind` or `impl.path`, since diff-impl cannot match it to code.
+pub fn check_impl_bindings(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    let mut diags = Vec::new();
+
+    let schemas = index
+        .schemas
+        .iter()
+        .map(|(id, schema)| (id, &schema.impl_bind, &schema.impl_path));
+    let funcs = index
+        .funcs
+        .iter()
+        .map(|(id, func)| (id, &func.impl_bind, &func.impl_path));
+
+    for (id, bind, path) in schemas.chain(funcs) {
+        if bind.is_none() && path.is_none() {
+            diags.push(Diagnostic::new(
+                "warning",
+                "MissingImplBinding",
+                format!(
+                    "{} has no impl.bind or impl.path; diff-impl cannot match it",
+                    id
+                ),
+                id.clone(),
+            ));
+        }
+    }
+
+    diags
+}
+
+/// Opt-in check (`check --require-owners`): warn for every mod without
+/// `owners`, so ownership reports (`deps --by-owner`) have no gaps.
+pub fn check_module_owners(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    index
+        .mods
+        .iter()
+        .filter(|(_, module)| module.owners.is_empty())
+        .map(|(mod_id, _)| {
+            Diagnostic::new(
+                "warning",
+                "ModuleWithoutOwner",
+                format!("{} has no owners", mod_id),
+                format!("{}.owners", mod_id),
+            )
+            .with_suggestion(format!("add owners = [\"<team>\"] to [{}]", mod_id))
+        })
+        .collect()
+}
+
+/// Opt-in check (`check --impl-lang`): warn when a schema's `impl.lang` differs
+/// from the `impl.lang` of every func that takes or returns it.
+pub fn check_impl_langs(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    let mut diags = Vec::new();
+
+    for (id, schema) in &index.schemas {
+        let Some(lang) = schema.impl_lang.as_deref().filter(|l| *l != "either") else {
+            continue;
+        };
+        let users: Vec<&FuncSection> = index
+            .funcs
+            .values()
+            .copied()
+            .filter(|func| func.input.contains(id) || func.output.contains(id))
+            .collect();
+        // A func without impl.lang (or with "either") can live on either side
+        let compatible = |func: &&FuncSection| match func.impl_lang.as_deref() {
+            None | Some("either") => true,
+            Some(other) => other == lang,
+        };
+        if users.is_empty() || users.iter().any(compatible) {
+            continue;
+        }
+
+        let func_langs: Vec<String> = users
+            .iter()
+            .map(|func| {
+                let func_lang = func.impl_lang.as_deref().unwrap_or_default();
+                format!("{} ({})", func_id(func), func_lang)
+            })
+            .collect();
+        diags.push(Diagnostic::new(
+            "warning",
+            "ImplLangMismatch",
+            format!(
+                "{} is bound to impl.lang = \"{}\" but every func using it is bound elsewhere: {}",
+                id,
+                lang,
+                func_langs.join(", ")
+            ),
+            format!("{}.impl.lang", id),
+        ));
+    }
+
+    diags
+}
+
+/// Intent words that usually belong near the start of a pipeline.
+const EARLY_INTENT_KEYWORDS: &[&str] = &["validate", "validation", "parse", "parsing"];
+/// Intent words that usually belong near the end of a pipeline.
+const LATE_INTENT_KEYWORDS: &[&str] = &["respond", "response", "send"];
+
+/// Opt-in lint (`check --lint-boundaries`): note schemas claimed by more than
+/// one boundary's `over`, so authors can confirm the overlap is deliberate.
+pub fn check_boundary_overlap(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    let mut owners: BTreeMap<&String, Vec<String>> = BTreeMap::new();
+    for schema in index.schemas.values().filter(|s| s.kind == "boundary") {
+        for over in schema.over.iter().collect::<BTreeSet<_>>() {
+            owners.entry(over).or_default().push(schema_id(schema));
+        }
+    }
+
+    owners
+        .into_iter()
+        .filter(|(_, boundaries)| boundaries.len() > 1)
+        .map(|(schema, boundaries)| {
+            Diagnostic::new(
+                "info",
+                "OverlappingBoundaries",
+                format!(
+                    "{} is inside more than one boundary: {}",
+                    schema,
+                    boundaries.join(", ")
+                ),
+                format!("{}.over({})", boundaries[1], schema),
+            )
+        })
+        .collect()
+}
+
+/// Opt-in lint (`check --lint-duplicates`): note funcs with the same input
+/// and output schema sets, which may be redundant copies of one operation.
+pub fn check_duplicate_funcs(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    duplicate_func_notes(index.funcs.iter().map(|(id, func)| (id.clone(), *func)))
+}
+
+/// One `PossibleDuplicateFunc` note per group of funcs sharing a signature.
+/// `funcs` pairs each func with the label reported for it; schemas are
+/// compared by local name, and funcs without inputs or outputs are skipped.
+pub(crate) fn duplicate_func_notes<'a>(
+    funcs: impl IntoIterator<Item = (String, &'a FuncSection)>,
+) -> Vec<Diagnostic> {
+    type Signature<'a> = (BTreeSet<&'a str>, BTreeSet<&'a str>);
+    let local = |schema: &'a String| schema.trim().rsplit('.').next().unwrap_or_default();
+
+    let mut groups: BTreeMap<Signature<'a>, Vec<(String, &'a FuncSection)>> = BTreeMap::new();
+    for (label, func) in funcs {
+        if func.input.is_empty() && func.output.is_empty() {
+            continue;
+        }
+        let signature = (
+            func.input.iter().map(local).collect(),
+            func.output.iter().map(local).collect(),
+        );
+        groups.entry(signature).or_default().push((label, func));
+    }
+
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|group| {
+            let labels: Vec<&str> = group.iter().map(|(label, _)| label.as_str()).collect();
+            let func = group[0].1;
+            Diagnostic::new(
+                "info",
+                "PossibleDuplicateFunc",
+                format!(
+                    "{} all take [{}] and return [{}]; one may duplicate another",
+                    labels.join(", "),
+                    func.input.join(", "),
+                    func.output.join(", ")
+                ),
+                labels[1].to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Opt-in check (`check --check-version`): a non-empty `meta.version` must
+/// be a semantic version such as `1.2.0` or `2.0.0-rc.1`.
+pub fn check_meta_version(file: &SurvFile) -> Vec<Diagnostic> {
+    file.sections
+        .iter()
+        .filter_map(|section| match section {
+            Section::Meta(meta) if !meta.version.is_empty() && !is_semver(&meta.version) => {
+                Some(Diagnostic::new(
+                    "warning",
+                    "InvalidVersion",
+                    format!(
+                        "meta.version '{}' is not a semantic version (MAJOR.MINOR.PATCH)",
+                        meta.version
+                    ),
+                    "meta.version",
+                ))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// `MAJOR.MINOR.PATCH`, optionally followed by `-prerelease` and `+build`
+/// (semver 2.0.0).
+fn is_semver(version: &str) -> bool {
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let numeric = |part: &str| {
+        !part.is_empty()
+            && part.bytes().all(|b| b.is_ascii_digit())
+            && (part == "0" || !part.starts_with('0'))
+    };
+    let identifier = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+
+    let core: Vec<&str> = core.split('.').collect();
+    core.len() == 3
+        && core.iter().all(|part| numeric(part))
+        && pre.is_none_or(|pre| {
+            pre.split('.').all(|part| {
+                identifier(part) && (!part.bytes().all(|b| b.is_ascii_digit()) || numeric(part))
+            })
+        })
+        && build.is_none_or(|build| build.split('.').all(identifier))
+}
+
+/// Opt-in lint (`check --lint-intent`): flag funcs whose intent suggests the
+/// other end of the pipeline, e.g. a "validate" step placed last.
+pub fn check_intent_order(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    let mut diags = Vec::new();
+
+    for (mod_id, module) in &index.mods {
+        let stages = module.pipeline_stages();
+        if stages.len() < 2 {
+            continue;
+        }
+        let ends = [
+            (&stages[0], LATE_INTENT_KEYWORDS, "start"),
+            (&stages[stages.len() - 1], EARLY_INTENT_KEYWORDS, "end"),
+        ];
+        for (stage, keywords, position) in ends {
+            for step in stage {
+                let Some(func) = index.funcs.get(step) else {
+                    continue;
+                };
+                if let Some(keyword) = intent_keyword(&func.intent, keywords) {
+                    diags.push(Diagnostic::new(
+                        "info",
+                        "SuspiciousPipelineOrder",
+                        format!(
+                            "{} intent mentions '{}' but it is at the {} of {}.pipeline",
+                            step, keyword, position, mod_id
+                        ),
+                        format!("{}.pipeline", mod_id),
+                    ));
+                }
+            }
+        }
+    }
+
+    diags
+}
+
+/// Info-level lint run by `check` (skip with `--no-lint-pipeline`): modules
+/// that list funcs but no pipeline leave the execution order unstated.
+/// Modules that are plain function collections can ignore it.
+pub fn check_module_pipelines(file: &SurvFile) -> Vec<Diagnostic> {
+    let index = FileIndex::new(file);
+    index
+        .mods
+        .iter()
+        .filter(|(_, module)| !module.funcs.is_empty() && module.pipeline.is_empty())
+        .map(|(mod_id, module)| {
+            Diagnostic::new(
+                "info",
+                "ModuleWithoutPipeline",
+                format!(
+                    "{} declares {} func(s) but no pipeline; add one if they run in sequence",
+                    mod_id,
+                    module.funcs.len()
+                ),
+                format!("{}.pipeline", mod_id),
+            )
+        })
+        .collect()
+}
+
+fn intent_keyword<'k>(intent: &str, keywords: &[&'k str]) -> Option<&'k str> {
+    let intent = intent.to_lowercase();
+    intent
+        .split(|c: char| !c.is_alphanumeric())
+        .find_map(|word| keywords.iter().find(|k| word.starts_with(**k)).copied())
+}
+
+pub(crate) struct FileIndex<'a> {
+    pub(crate) schemas: BTreeMap<String, &'a SchemaSection>,
+    pub(crate) funcs: BTreeMap<String, &'a FuncSection>,
+    pub(crate) mods: BTreeMap<String, &'a ModSection>,
 }
 
 impl<'a> FileIndex<'a> {
-    fn new(file: &'a SurvFile) -> Self {
+    pub(crate) fn new(file: &'a SurvFile) -> Self {
         let mut schemas = BTreeMap::new();
         let mut funcs = BTreeMap::new();
         let mut mods = BTreeMap::new();
@@ -69,31 +382,37 @@ fn check_func_schemas(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
     for func in index.funcs.values() {
         for schema in &func.input {
             if !index.schemas.contains_key(schema) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedSchema".into(),
-                    message: format!(
-                        "func {}: input schema {} is not defined",
-                        func_id(func),
-                        schema
-                    ),
-                    location: format!("{}.input({})", func_id(func), schema),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedSchema",
+                        format!(
+                            "func {}: input schema {} is not defined",
+                            func_id(func),
+                            schema
+                        ),
+                        format!("{}.input({})", func_id(func), schema),
+                    )
+                    .with_suggestion(undefined_fix(schema, &index.schemas)),
+                );
             }
         }
 
         for schema in &func.output {
             if !index.schemas.contains_key(schema) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedSchema".into(),
-                    message: format!(
-                        "func {}: output schema {} is not defined",
-                        func_id(func),
-                        schema
-                    ),
-                    location: format!("{}.output({})", func_id(func), schema),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedSchema",
+                        format!(
+                            "func {}: output schema {} is not defined",
+                            func_id(func),
+                            schema
+                        ),
+                        format!("{}.output({})", func_id(func), schema),
+                    )
+                    .with_suggestion(undefined_fix(schema, &index.schemas)),
+                );
             }
         }
     }
@@ -103,85 +422,188 @@ fn check_mod_references(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
     for module in index.mods.values() {
         for schema in &module.schemas {
             if !index.schemas.contains_key(schema) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedSchemaInMod".into(),
-                    message: format!("mod {}: schema {} is not defined", mod_id(module), schema),
-                    location: format!("{}.schemas({})", mod_id(module), schema),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedSchemaInMod",
+                        format!("mod {}: schema {} is not defined", mod_id(module), schema),
+                        format!("{}.schemas({})", mod_id(module), schema),
+                    )
+                    .with_suggestion(undefined_fix(schema, &index.schemas)),
+                );
             }
         }
 
         for func in &module.funcs {
             if !index.funcs.contains_key(func) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedFuncInMod".into(),
-                    message: format!("mod {}: func {} is not defined", mod_id(module), func),
-                    location: format!("{}.funcs({})", mod_id(module), func),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedFuncInMod",
+                        format!("mod {}: func {} is not defined", mod_id(module), func),
+                        format!("{}.funcs({})", mod_id(module), func),
+                    )
+                    .with_suggestion(undefined_fix(func, &index.funcs)),
+                );
             }
         }
 
         for step in &module.pipeline {
-            if !index.funcs.contains_key(step) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedFuncInPipeline".into(),
-                    message: format!(
-                        "mod {}: pipeline step {} is not defined",
-                        mod_id(module),
-                        step
-                    ),
-                    location: format!("{}.pipeline({})", mod_id(module), step),
-                });
+            if index.schemas.contains_key(step) || index.mods.contains_key(step) {
+                let kind = if index.schemas.contains_key(step) { "schema" } else { "mod" };
+                let suggested = suggest_func_for_step(index, module, step);
+                let hint = match &suggested {
+                    Some(func) => format!("; did you mean {}?", func),
+                    None => String::new(),
+                };
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "PipelineStepIsNotFunc",
+                        format!(
+                            "mod {}: pipeline step {} is a {}, not a func{}",
+                            mod_id(module),
+                            step,
+                            kind,
+                            hint
+                        ),
+                        format!("{}.pipeline({})", mod_id(module), step),
+                    )
+                    .with_suggestion(suggested.map(|func| format!("replace with {}", func))),
+                );
+            } else if !index.funcs.contains_key(step) {
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedFuncInPipeline",
+                        format!(
+                            "mod {}: pipeline step {} is not defined",
+                            mod_id(module),
+                            step
+                        ),
+                        format!("{}.pipeline({})", mod_id(module), step),
+                    )
+                    .with_suggestion(undefined_fix(step, &index.funcs)),
+                );
             }
         }
     }
 }
 
+/// Fix for a reference to an undefined symbol: the closest defined id when the
+/// reference looks like a typo, otherwise defining it.
+fn undefined_fix<V>(reference: &str, defined: &BTreeMap<String, V>) -> String {
+    match closest_match(reference, defined.keys().map(String::as_str)) {
+        Some(candidate) => format!("replace with {}", candidate),
+        None => format!("define [{}] or correct the reference", reference),
+    }
+}
+
+/// Guess the func a non-func pipeline step was meant to be: a func with the
+/// same name, else one that takes (or produces) the schema, preferring the
+/// module's own funcs.
+fn suggest_func_for_step(
+    index: &FileIndex<'_>,
+    module: &ModSection,
+    step: &str,
+) -> Option<String> {
+    let local_name = step.split_once('.').map(|(_, name)| name).unwrap_or(step);
+    let same_name = format!("func.{}", local_name);
+    if index.funcs.contains_key(&same_name) {
+        return Some(same_name);
+    }
+
+    let candidates = module
+        .funcs
+        .iter()
+        .filter_map(|id| index.funcs.get_key_value(id))
+        .chain(index.funcs.iter());
+    let mut fallback = None;
+    for (id, func) in candidates {
+        if func.input.iter().any(|s| s == step) {
+            return Some(id.clone());
+        }
+        if fallback.is_none() && func.output.iter().any(|s| s == step) {
+            fallback = Some(id.clone());
+        }
+    }
+    fallback
+}
+
 fn check_schema_links(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
     for schema in index.schemas.values() {
         match schema.kind.as_str() {
             "edge" => {
+                let missing: Vec<&str> = [("from", &schema.from), ("to", &schema.to)]
+                    .into_iter()
+                    .filter(|(_, endpoint)| endpoint.is_empty())
+                    .map(|(key, _)| key)
+                    .collect();
+                if !missing.is_empty() {
+                    diags.push(
+                        Diagnostic::new(
+                            "error",
+                            "IncompleteEdgeSchema",
+                            format!(
+                                "schema {}: edge has no {}",
+                                schema_id(schema),
+                                missing.join(" or ")
+                            ),
+                            format!("{}.{}", schema_id(schema), missing[0]),
+                        )
+                        .with_suggestion(format!(
+                            "set {} to the schema(s) this edge connects",
+                            missing.join(" and ")
+                        )),
+                    );
+                }
                 if !schema.from.is_empty() && !index.schemas.contains_key(&schema.from) {
-                    diags.push(Diagnostic {
-                        severity: "error".into(),
-                        kind: "UndefinedSchemaInEdgeFrom".into(),
-                        message: format!(
-                            "schema {}: edge.from {} is not defined",
-                            schema_id(schema),
-                            schema.from
-                        ),
-                        location: format!("{}.from({})", schema_id(schema), schema.from),
-                    });
+                    diags.push(
+                        Diagnostic::new(
+                            "error",
+                            "UndefinedSchemaInEdgeFrom",
+                            format!(
+                                "schema {}: edge.from {} is not defined",
+                                schema_id(schema),
+                                schema.from
+                            ),
+                            format!("{}.from({})", schema_id(schema), schema.from),
+                        )
+                        .with_suggestion(undefined_fix(&schema.from, &index.schemas)),
+                    );
                 }
                 if !schema.to.is_empty() && !index.schemas.contains_key(&schema.to) {
-                    diags.push(Diagnostic {
-                        severity: "error".into(),
-                        kind: "UndefinedSchemaInEdgeTo".into(),
-                        message: format!(
-                            "schema {}: edge.to {} is not defined",
-                            schema_id(schema),
-                            schema.to
-                        ),
-                        location: format!("{}.to({})", schema_id(schema), schema.to),
-                    });
+                    diags.push(
+                        Diagnostic::new(
+                            "error",
+                            "UndefinedSchemaInEdgeTo",
+                            format!(
+                                "schema {}: edge.to {} is not defined",
+                                schema_id(schema),
+                                schema.to
+                            ),
+                            format!("{}.to({})", schema_id(schema), schema.to),
+                        )
+                        .with_suggestion(undefined_fix(&schema.to, &index.schemas)),
+                    );
                 }
             }
             "boundary" => {
                 for over in &schema.over {
                     if !index.schemas.contains_key(over) {
-                        diags.push(Diagnostic {
-                            severity: "error".into(),
-                            kind: "UndefinedSchemaInBoundary".into(),
-                            message: format!(
-                                "schema {}: boundary.over {} is not defined",
-                                schema_id(schema),
-                                over
-                            ),
-                            location: format!("{}.over({})", schema_id(schema), over),
-                        });
+                        diags.push(
+                            Diagnostic::new(
+                                "error",
+                                "UndefinedSchemaInBoundary",
+                                format!(
+                                    "schema {}: boundary.over {} is not defined",
+                                    schema_id(schema),
+                                    over
+                                ),
+                                format!("{}.over({})", schema_id(schema), over),
+                            )
+                            .with_suggestion(undefined_fix(over, &index.schemas)),
+                        );
                     }
                 }
             }
@@ -190,6 +612,47 @@ fn check_schema_links(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
     }
 }
 
+fn check_deprecated_schemas(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
+    // (referencing location, referenced schema)
+    let mut uses: Vec<(String, &String)> = Vec::new();
+    for func in index.funcs.values() {
+        let id = func_id(func);
+        uses.extend(func.input.iter().map(|s| (format!("{}.input({})", id, s), s)));
+        uses.extend(func.output.iter().map(|s| (format!("{}.output({})", id, s), s)));
+    }
+    for module in index.mods.values() {
+        let id = mod_id(module);
+        uses.extend(module.schemas.iter().map(|s| (format!("{}.schemas({})", id, s), s)));
+    }
+    for schema in index.schemas.values() {
+        let id = schema_id(schema);
+        if schema.kind == "edge" {
+            uses.push((format!("{}.from({})", id, schema.from), &schema.from));
+            uses.push((format!("{}.to({})", id, schema.to), &schema.to));
+        }
+        if schema.kind == "boundary" {
+            uses.extend(schema.over.iter().map(|s| (format!("{}.over({})", id, s), s)));
+        }
+    }
+
+    for (location, target) in uses {
+        let Some(schema) = index.schemas.get(target).filter(|s| s.deprecated) else {
+            continue;
+        };
+        let note = if schema.deprecated_note.is_empty() {
+            String::new()
+        } else {
+            format!(": {}", schema.deprecated_note)
+        };
+        diags.push(Diagnostic::new(
+            "warning",
+            "DeprecatedSchemaUsed",
+            format!("{} uses deprecated schema {}{}", location, target, note),
+            location,
+        ));
+    }
+}
+
 fn check_pipeline_semantics(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
     for module in index.mods.values() {
         if module.pipeline.is_empty() {
@@ -199,23 +662,50 @@ fn check_pipeline_semantics(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>)
         let mut seen = BTreeSet::new();
         for step in &module.pipeline {
             if !seen.insert(step.clone()) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "PipelineCycle".into(),
-                    message: format!(
+                diags.push(Diagnostic::new(
+                    "error",
+                    "PipelineCycle",
+                    format!(
                         "mod {}: pipeline has a cycle involving {} (appears multiple times)",
                         mod_id(module),
                         step
                     ),
-                    location: format!("{}.pipeline", mod_id(module)),
-                });
+                    format!("{}.pipeline", mod_id(module)),
+                ));
             }
         }
 
-        for pair in module.pipeline.windows(2) {
-            let f1 = &pair[0];
-            let f2 = &pair[1];
+        let declared: BTreeSet<&String> = module.schemas.iter().collect();
+        for step in &seen {
+            let Some(func) = index.funcs.get(step) else {
+                continue;
+            };
+            let used: BTreeSet<&String> = func.input.iter().chain(&func.output).collect();
+            for schema in used {
+                if !declared.contains(schema) {
+                    diags.push(Diagnostic::new(
+                        "warning",
+                        "PipelineUsesUndeclaredSchema",
+                        format!(
+                            "mod {}: pipeline step {} uses {} which is not in the module's schemas",
+                            mod_id(module),
+                            step,
+                            schema
+                        ),
+                        format!("{}.schemas", mod_id(module)),
+                    ));
+                }
+            }
+        }
 
+        // Only stages within one chain are adjacent
+        let chains = &module.pipeline_chains;
+        let edges = chains.iter().flat_map(|chain| chain.windows(2)).flat_map(|pair| {
+            pair[0]
+                .iter()
+                .flat_map(move |f1| pair[1].iter().map(move |f2| (f1, f2)))
+        });
+        for (f1, f2) in edges {
             let Some(func1) = index.funcs.get(f1) else {
                 continue;
             };
@@ -224,20 +714,236 @@ fn check_pipeline_semantics(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>)
             };
 
             if !has_common_schema(&func1.output, &func2.input) {
-                diags.push(Diagnostic {
-                    severity: "warning".into(),
-                    kind: "PipelineTypeMismatch".into(),
-                    message: format!(
+                diags.push(Diagnostic::new(
+                    "warning",
+                    "PipelineTypeMismatch",
+                    format!(
                         "mod {}: pipeline step {} -> {} has no shared schema between output and input",
                         mod_id(module),
                         f1,
                         f2
                     ),
-                    location: format!("{}.pipeline({}->{})", mod_id(module), f1, f2),
-                });
+                    format!("{}.pipeline({}->{})", mod_id(module), f1, f2),
+                ));
+            }
+        }
+    }
+}
+
+/// Warn for pipeline step outputs that no later step of the chain, and no
+/// step of another chain, consumes. Schemas the chain's final stage outputs
+/// are the module's outputs and are never reported, wherever they are
+/// produced; neither are error outputs (`role = "error"`), which leave the
+/// pipeline by design.
+fn check_dead_pipeline_outputs(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
+    for module in index.mods.values() {
+        let chains = &module.pipeline_chains;
+        let mut reported = BTreeSet::new();
+        for (c, stages) in chains.iter().enumerate() {
+            let other_chains = chains.iter().enumerate().filter(|(other, _)| *other != c);
+            let other_steps: Vec<&String> =
+                other_chains.flat_map(|(_, chain)| chain.iter().flatten()).collect();
+            let module_outputs: BTreeSet<&String> = stages
+                .last()
+                .into_iter()
+                .flatten()
+                .filter_map(|step| index.funcs.get(step))
+                .flat_map(|func| &func.output)
+                .collect();
+            for (i, stage) in stages.iter().enumerate().take(stages.len().saturating_sub(1)) {
+                let later_inputs: BTreeSet<&String> = stages[i + 1..]
+                    .iter()
+                    .flatten()
+                    .chain(other_steps.iter().copied())
+                    .filter_map(|step| index.funcs.get(step))
+                    .flat_map(|func| &func.input)
+                    .collect();
+                for step in stage {
+                    let Some(func) = index.funcs.get(step) else {
+                        continue;
+                    };
+                    for schema in &func.output {
+                        let is_error = index.schemas.get(schema).is_some_and(|s| s.role == "error");
+                        if is_error
+                            || module_outputs.contains(schema)
+                            || later_inputs.contains(schema)
+                            || !reported.insert((step, schema))
+                        {
+                            continue;
+                        }
+                        diags.push(Diagnostic::new(
+                            "warning",
+                            "DeadPipelineOutput",
+                            format!(
+                                "mod {}: pipeline step {} outputs {} but no later step consumes it",
+                                mod_id(module),
+                                step,
+                                schema
+                            ),
+                            format!("{}.pipeline({})", mod_id(module), step),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Follow schemas through each pipeline chain: the first stage's inputs and
+/// outputs are available, and a later stage continues the flow when one of
+/// its steps consumes an available schema (its outputs then become
+/// available too). Unlike `PipelineTypeMismatch`, a schema may be carried
+/// past intermediate steps. Only the first break per chain is reported.
+fn check_pipeline_data_flow(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
+    for module in index.mods.values() {
+        for chain in &module.pipeline_chains {
+            let Some((first, rest)) = chain.split_first() else {
+                continue;
+            };
+            let mut available: BTreeSet<&String> = first
+                .iter()
+                .filter_map(|step| index.funcs.get(step))
+                .flat_map(|func| func.input.iter().chain(&func.output))
+                .collect();
+            if available.is_empty() {
+                continue;
+            }
+
+            for stage in rest {
+                // Undefined steps are reported elsewhere; let the flow pass them
+                let funcs: Vec<(&String, &FuncSection)> = stage
+                    .iter()
+                    .filter_map(|step| index.funcs.get(step).map(|func| (step, *func)))
+                    .collect();
+                if funcs.len() < stage.len() {
+                    continue;
+                }
+                let continued: Vec<&FuncSection> = funcs
+                    .iter()
+                    .filter(|(_, func)| func.input.iter().any(|schema| available.contains(schema)))
+                    .map(|(_, func)| *func)
+                    .collect();
+                if continued.is_empty() {
+                    let steps: Vec<&str> = stage.iter().map(String::as_str).collect();
+                    diags.push(Diagnostic::new(
+                        "warning",
+                        "PipelineDataFlowBroken",
+                        format!(
+                            "mod {}: pipeline data flow breaks at {}: no input comes from the pipeline's inputs or an earlier step's outputs",
+                            mod_id(module),
+                            steps.join(", ")
+                        ),
+                        format!("{}.pipeline({})", mod_id(module), steps[0]),
+                    ));
+                    break;
+                }
+                available.extend(continued.into_iter().flat_map(|func| &func.output));
+            }
+        }
+    }
+}
+
+/// Warn when a chain lists a step before the step that produces its input.
+///
+/// `a` feeds `b` when `a` outputs a schema `b` consumes that `a` does not
+/// consume itself; a step that updates a schema in place only orders the
+/// suggestion, never warns. Mutual dependencies are ambiguous and ignored.
+/// The suggestion is the chain's steps sorted by data flow, keeping the
+/// listed order otherwise.
+fn check_pipeline_order(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
+    for module in index.mods.values() {
+        for chain in &module.pipeline_chains {
+            let steps: Vec<(usize, &String, &FuncSection)> = chain
+                .iter()
+                .enumerate()
+                .flat_map(|(stage, steps)| steps.iter().map(move |step| (stage, step)))
+                .filter_map(|(stage, step)| index.funcs.get(step).map(|func| (stage, step, *func)))
+                .collect();
+            // (schema, whether `a` produces it rather than updating it)
+            let feeds = |a: &FuncSection, b: &FuncSection| -> Option<(String, bool)> {
+                let shared: Vec<&String> = a
+                    .output
+                    .iter()
+                    .filter(|schema| b.input.contains(schema) && !b.output.contains(schema))
+                    .collect();
+                let produced = shared.iter().find(|schema| !a.input.contains(schema));
+                produced
+                    .map(|schema| ((*schema).clone(), true))
+                    .or_else(|| shared.first().map(|schema| ((*schema).clone(), false)))
+            };
+
+            let mut edges: Vec<(usize, usize, String)> = Vec::new();
+            let mut violations: Vec<(usize, usize, String)> = Vec::new();
+            for (i, (_, _, a)) in steps.iter().enumerate() {
+                for (j, (_, _, b)) in steps.iter().enumerate() {
+                    if i == j || feeds(b, a).is_some() {
+                        continue;
+                    }
+                    let Some((schema, produced)) = feeds(a, b) else {
+                        continue;
+                    };
+                    if produced && steps[j].0 < steps[i].0 {
+                        violations.push((i, j, schema.clone()));
+                    }
+                    edges.push((i, j, schema));
+                }
+            }
+            if violations.is_empty() {
+                continue;
+            }
+            let order = data_flow_order(steps.len(), &edges).map(|order| {
+                let names: Vec<&str> = order.iter().map(|&i| steps[i].1.as_str()).collect();
+                format!("reorder the chain as {}", names.join(" -> "))
+            });
+
+            for (producer, consumer, schema) in violations {
+                let (producer, consumer) = (steps[producer].1, steps[consumer].1);
+                let message = format!(
+                    "mod {}: pipeline lists {} before {}, but {} outputs {} which {} consumes",
+                    mod_id(module),
+                    consumer,
+                    producer,
+                    producer,
+                    schema,
+                    consumer
+                );
+                diags.push(
+                    Diagnostic::new(
+                        "warning",
+                        "PipelineOrderViolatesDataFlow",
+                        message,
+                        format!("{}.pipeline({})", mod_id(module), consumer),
+                    )
+                    .with_suggestion(
+                        order
+                            .clone()
+                            .unwrap_or_else(|| format!("move {} before {}", producer, consumer)),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Topological order of `0..len` over `(from, to)` edges, taking the lowest
+/// ready index first so unrelated steps keep their order. `None` on a cycle.
+fn data_flow_order(len: usize, edges: &[(usize, usize, String)]) -> Option<Vec<usize>> {
+    let mut indegree = vec![0; len];
+    for (_, to, _) in edges {
+        indegree[*to] += 1;
+    }
+    let mut ready: BTreeSet<usize> = (0..len).filter(|&i| indegree[i] == 0).collect();
+    let mut order = Vec::with_capacity(len);
+    while let Some(next) = ready.pop_first() {
+        order.push(next);
+        for (_, to, _) in edges.iter().filter(|(from, _, _)| *from == next) {
+            indegree[*to] -= 1;
+            if indegree[*to] == 0 {
+                ready.insert(*to);
             }
         }
     }
+    (order.len() == len).then_some(order)
 }
 
 fn has_common_schema(a: &[String], b: &[String]) -> bool {
@@ -251,6 +957,75 @@ fn has_common_schema(a: &[String], b: &[String]) -> bool {
     b.iter().any(|schema| set.contains(schema))
 }
 
+/// `[status.mod.x]` entries left behind when a module is renamed or removed.
+fn check_status_entries(file: &SurvFile, index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
+    let module_names: Vec<&str> = index.mods.values().map(|m| m.name.as_str()).collect();
+    let statuses = file.sections.iter().filter_map(|section| match section {
+        Section::Status(status) => Some(status),
+        _ => None,
+    });
+
+    for name in statuses.flat_map(|status| status.modules.keys()) {
+        if module_names.contains(&name.as_str()) {
+            continue;
+        }
+        let suggestion = match closest_match(name, module_names.iter().copied()) {
+            Some(candidate) => format!("rename to [status.mod.{}]", candidate),
+            None => "remove it, or run `surc status sync --prune`".to_string(),
+        };
+        diags.push(
+            Diagnostic::new(
+                "warning",
+                "StaleStatusEntry",
+                format!(
+                    "status entry for mod.{} has no matching [mod.{}]",
+                    name, name
+                ),
+                format!("status.mod.{}", name),
+            )
+            .with_suggestion(suggestion),
+        );
+    }
+}
+
+/// `@annotations` on schema fields must be known and appear once per field.
+fn check_field_annotations(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
+    for (id, schema) in &index.schemas {
+        for field in schema.field_specs() {
+            let location = format!("{}.fields.{}", id, field.name);
+            let mut seen = BTreeSet::new();
+            for annotation in &field.annotations {
+                if !FIELD_ANNOTATIONS.contains(&annotation.as_str()) {
+                    let suggestion = closest_match(annotation, FIELD_ANNOTATIONS.iter().copied())
+                        .map(|candidate| format!("use @{}", candidate));
+                    diags.push(
+                        Diagnostic::new(
+                            "warning",
+                            "UnknownFieldAnnotation",
+                            format!(
+                                "Field '{}' of {} has unknown annotation @{}",
+                                field.name, id, annotation
+                            ),
+                            location.clone(),
+                        )
+                        .with_suggestion(suggestion),
+                    );
+                } else if !seen.insert(annotation.as_str()) {
+                    diags.push(
+                        Diagnostic::new(
+                            "warning",
+                            "DuplicateFieldAnnotation",
+                            format!("Field '{}' of {} repeats @{}", field.name, id, annotation),
+                            location.clone(),
+                        )
+                        .with_suggestion(format!("remove the extra @{}", annotation)),
+                    );
+                }
+            }
+        }
+    }
+}
+
 fn check_unused_definitions(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>) {
     let mut used_schemas = BTreeSet::new();
 
@@ -280,12 +1055,12 @@ fn check_unused_definitions(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>)
 
     for name in index.schemas.keys() {
         if !used_schemas.contains(name) {
-            diags.push(Diagnostic {
-                severity: "warning".into(),
-                kind: "UnusedSchema".into(),
-                message: format!("schema {} is defined but never referenced", name),
-                location: name.clone(),
-            });
+            diags.push(Diagnostic::new(
+                "warning",
+                "UnusedSchema",
+                format!("schema {} is defined but never referenced", name),
+                name.clone(),
+            ));
         }
     }
 
@@ -302,12 +1077,12 @@ fn check_unused_definitions(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>)
 
     for name in index.funcs.keys() {
         if !used_funcs.contains(name) {
-            diags.push(Diagnostic {
-                severity: "warning".into(),
-                kind: "UnusedFunc".into(),
-                message: format!("func {} is defined but never referenced in any mod", name),
-                location: name.clone(),
-            });
+            diags.push(Diagnostic::new(
+                "warning",
+                "UnusedFunc",
+                format!("func {} is defined but never referenced in any mod", name),
+                name.clone(),
+            ));
         }
     }
 }
@@ -315,11 +1090,10 @@ fn check_unused_definitions(index: &FileIndex<'_>, diags: &mut Vec<Diagnostic>)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
 
     fn parse(input: &str) -> SurvFile {
-        parse_surv_file(Cursor::new(input)).expect("parse")
+        parse_surv_str(input).expect("parse")
     }
 
     #[test]
@@ -384,6 +1158,38 @@ pipeline = ["func.create_user", "func.nonexistent"]
         );
     }
 
+    #[test]
+    fn detects_pipeline_step_that_is_not_a_func() {
+        let ir = r#"
+[meta]
+name = "test"
+
+[schema.user]
+kind = "node"
+type = "User"
+
+[func.create_user]
+intent = "test"
+input  = ["schema.user"]
+output = ["schema.user"]
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user"]
+funcs   = ["func.create_user"]
+pipeline = ["schema.user", "func.create_user"]
+"#;
+
+        let file = parse(ir);
+        let diags = check_surv_file(&file);
+        let diag = diags
+            .iter()
+            .find(|d| d.kind == "PipelineStepIsNotFunc")
+            .expect("expected PipelineStepIsNotFunc");
+        assert!(diag.message.contains("did you mean func.create_user"));
+        assert!(!diags.iter().any(|d| d.kind == "UndefinedFuncInPipeline"));
+    }
+
     #[test]
     fn detects_undefined_schema_in_boundary() {
         let ir = r#"
@@ -428,6 +1234,55 @@ to   = "schema.nonexistent"
         let file = parse(ir);
         let diags = check_surv_file(&file);
         assert!(diags.iter().any(|d| d.kind == "UndefinedSchemaInEdgeTo"));
+        assert!(!diags.iter().any(|d| d.kind == "IncompleteEdgeSchema"));
+    }
+
+    #[test]
+    fn detects_edge_without_to() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.follows]
+kind = "edge"
+from = "schema.user"
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let incomplete: Vec<_> = diags.iter().filter(|d| d.kind == "IncompleteEdgeSchema").collect();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].severity, "error");
+        assert_eq!(incomplete[0].location, "schema.follows.to");
+    }
+
+    #[test]
+    fn detects_edge_without_from() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.follows]
+kind = "edge"
+to = "schema.user"
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let incomplete: Vec<_> = diags.iter().filter(|d| d.kind == "IncompleteEdgeSchema").collect();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].location, "schema.follows.from");
+    }
+
+    #[test]
+    fn detects_edge_without_either_endpoint() {
+        let ir = r#"
+[schema.follows]
+kind = "edge"
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let incomplete: Vec<_> = diags.iter().filter(|d| d.kind == "IncompleteEdgeSchema").collect();
+        assert_eq!(incomplete.len(), 1);
+        assert!(incomplete[0].message.contains("no from or to"));
     }
 
     #[test]
@@ -515,28 +1370,135 @@ funcs   = ["func.create_user"]
     }
 
     #[test]
-    fn detects_unused_func() {
+    fn detects_dead_pipeline_output() {
         let ir = r#"
-[meta]
-name = "test"
+[schema.order]
+kind = "node"
 
-[schema.user]
+[schema.invoice]
 kind = "node"
-type = "User"
 
-[func.create_user]
-intent = "test"
-input  = ["schema.user"]
-output = ["schema.user"]
+[schema.audit_log]
+kind = "node"
 
-[func.unused_func]
-intent = "test"
-input  = ["schema.user"]
-output = ["schema.user"]
+[schema.receipt]
+kind = "node"
 
-[mod.api]
-purpose = "test"
-schemas = ["schema.user"]
+[schema.PaymentDeclined]
+kind = "node"
+role = "error"
+
+[func.place_order]
+intent = "Place an order"
+output = ["schema.order", "schema.audit_log"]
+
+[func.bill]
+intent = "Bill the order"
+input = ["schema.order"]
+output = ["schema.invoice", "schema.PaymentDeclined"]
+
+[func.send_receipt]
+intent = "Send the receipt"
+input = ["schema.invoice"]
+output = ["schema.receipt"]
+
+[mod.orders]
+purpose = "Orders"
+schemas = ["schema.order", "schema.invoice", "schema.audit_log", "schema.receipt"]
+funcs = ["func.place_order", "func.bill", "func.send_receipt"]
+pipeline = ["func.place_order", "func.bill", "func.send_receipt"]
+"#;
+
+        let file = parse(ir);
+        let diags = check_surv_file(&file);
+        let dead: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "DeadPipelineOutput")
+            .collect();
+        // The unconsumed error output of func.bill is not reported
+        assert_eq!(dead.len(), 1);
+        assert!(dead[0].message.contains("schema.audit_log"));
+        assert_eq!(dead[0].location, "mod.orders.pipeline(func.place_order)");
+    }
+
+    #[test]
+    fn module_outputs_are_not_dead_anywhere_in_the_chain() {
+        let ir = r#"
+[schema.Query]
+kind = "node"
+
+[schema.User]
+kind = "node"
+
+[func.createUser]
+intent = "Create"
+input = ["schema.User"]
+output = ["schema.User"]
+
+[func.findUser]
+intent = "Find"
+input = ["schema.Query"]
+output = ["schema.User"]
+
+[mod.users]
+purpose = "Users"
+pipeline = ["func.createUser", "func.findUser"]
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        assert!(!diags.iter().any(|d| d.kind == "DeadPipelineOutput"));
+    }
+
+    #[test]
+    fn notes_modules_with_funcs_but_no_pipeline() {
+        let ir = r#"
+[func.create]
+intent = "Create"
+
+[func.delete]
+intent = "Delete"
+
+[mod.ordered]
+purpose = "Ordered"
+funcs = ["func.create", "func.delete"]
+pipeline = ["func.create", "func.delete"]
+
+[mod.helpers]
+purpose = "Helpers"
+funcs = ["func.create", "func.delete"]
+"#;
+
+        let file = parse(ir);
+        let diags = check_module_pipelines(&file);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "ModuleWithoutPipeline");
+        assert_eq!(diags[0].severity, "info");
+        assert_eq!(diags[0].location, "mod.helpers.pipeline");
+    }
+
+    #[test]
+    fn detects_unused_func() {
+        let ir = r#"
+[meta]
+name = "test"
+
+[schema.user]
+kind = "node"
+type = "User"
+
+[func.create_user]
+intent = "test"
+input  = ["schema.user"]
+output = ["schema.user"]
+
+[func.unused_func]
+intent = "test"
+input  = ["schema.user"]
+output = ["schema.user"]
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user"]
 funcs   = ["func.create_user"]
 "#;
 
@@ -644,6 +1606,50 @@ pipeline = ["func.create_user", "func.create_product"]
         assert!(diags.iter().any(|d| d.kind == "PipelineTypeMismatch"));
     }
 
+    #[test]
+    fn checks_pipeline_chains_separately() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.user_row]
+kind = "node"
+
+[schema.product]
+kind = "node"
+
+[func.load_user]
+intent = "Load a user"
+input = ["schema.user_row"]
+output = ["schema.user"]
+
+[func.render_user]
+intent = "Render a user"
+input = ["schema.user"]
+output = ["schema.user"]
+
+[func.load_product]
+intent = "Load a product"
+input = ["schema.product"]
+output = ["schema.product"]
+
+[func.render_product]
+intent = "Render a product"
+input = ["schema.product"]
+output = ["schema.product"]
+
+[mod.catalog]
+purpose = "Two independent flows"
+schemas = ["schema.user", "schema.user_row", "schema.product"]
+pipeline = ["func.load_user -> func.render_user", "func.load_product -> func.render_product"]
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let pipeline_kinds = ["PipelineTypeMismatch", "PipelineDataFlowBroken", "DeadPipelineOutput"];
+        let seam: Vec<_> = diags.iter().filter(|d| pipeline_kinds.contains(&d.kind.as_str())).collect();
+        assert!(seam.is_empty(), "{:?}", seam);
+    }
+
     #[test]
     fn valid_pipeline_composition() {
         let ir = r#"
@@ -690,4 +1696,496 @@ pipeline = ["func.parse_request", "func.process_user", "func.send_response"]
             .iter()
             .any(|d| d.kind == "PipelineTypeMismatch" || d.severity == "error"));
     }
+
+    #[test]
+    fn checks_each_pipeline_branch() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+type = "User"
+
+[schema.product]
+kind = "node"
+type = "Product"
+
+[func.load_user]
+intent = "load user"
+input  = ["schema.user"]
+output = ["schema.user"]
+
+[func.audit_user]
+intent = "audit user"
+input  = ["schema.user"]
+output = ["schema.user"]
+
+[func.price_product]
+intent = "price product"
+input  = ["schema.product"]
+output = ["schema.product"]
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user", "schema.product"]
+funcs   = ["func.load_user", "func.audit_user", "func.price_product"]
+pipeline = "func.load_user -> { func.audit_user, func.price_product }"
+"#;
+
+        let file = parse(ir);
+        let diags = check_surv_file(&file);
+        let mismatches: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "PipelineTypeMismatch")
+            .collect();
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].location.contains("func.load_user->func.price_product"));
+    }
+
+    #[test]
+    fn detects_pipeline_uses_undeclared_schema() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+type = "User"
+
+[schema.audit]
+kind = "node"
+type = "Audit"
+
+[func.save_user]
+intent = "save user"
+input  = ["schema.user"]
+output = ["schema.audit"]
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user"]
+funcs   = ["func.save_user"]
+pipeline = ["func.save_user"]
+"#;
+
+        let file = parse(ir);
+        let diags = check_surv_file(&file);
+        let undeclared: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "PipelineUsesUndeclaredSchema")
+            .collect();
+        assert_eq!(undeclared.len(), 1);
+        assert!(undeclared[0].message.contains("schema.audit"));
+    }
+
+    #[test]
+    fn reports_missing_impl_bindings() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+impl.bind = "User"
+
+[schema.order]
+kind = "node"
+
+[func.create_user]
+intent = "test"
+impl.path = "src/users.ts"
+
+[func.cancel_order]
+intent = "test"
+"#;
+
+        let diags = check_impl_bindings(&parse(ir));
+        let missing: Vec<_> = diags.iter().map(|d| d.location.as_str()).collect();
+        assert_eq!(missing, vec!["schema.order", "func.cancel_order"]);
+        assert!(diags.iter().all(|d| d.kind == "MissingImplBinding"));
+    }
+
+    #[test]
+    fn reports_modules_without_owners() {
+        let ir = r#"
+[mod.auth]
+purpose = "Auth"
+owners = ["team-auth", "team-security"]
+
+[mod.billing]
+purpose = "Billing"
+"#;
+
+        let file = parse(ir);
+        let owners = file.sections.iter().find_map(|section| match section {
+            Section::Mod(m) if m.name == "auth" => Some(m.owners.clone()),
+            _ => None,
+        });
+        assert_eq!(owners, Some(vec!["team-auth".to_string(), "team-security".to_string()]));
+
+        let diags = check_module_owners(&file);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "ModuleWithoutOwner");
+        assert_eq!(diags[0].location, "mod.billing.owners");
+    }
+
+    #[test]
+    fn warns_on_status_entries_without_a_module() {
+        let ir = r#"
+[mod.user_api]
+purpose = "Users"
+
+[status]
+updated_at = "2024-01-01"
+
+[status.mod.user_api]
+state = "done"
+
+[status.mod.users_api]
+state = "todo"
+
+[status.mod.legacy_billing]
+state = "partial"
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let stale: Vec<_> = diags.iter().filter(|d| d.kind == "StaleStatusEntry").collect();
+        assert_eq!(stale.len(), 2);
+        assert_eq!(stale[0].location, "status.mod.legacy_billing");
+        assert_eq!(stale[1].location, "status.mod.users_api");
+        assert_eq!(stale[1].suggestion.as_deref(), Some("rename to [status.mod.user_api]"));
+    }
+
+    #[test]
+    fn parses_and_checks_field_annotations() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+fields = { id = "uuid @unique @unique", name = "string @indexd" }
+
+[schema.user.field.email]
+type = "string"
+unique = true
+indexed = true
+"#;
+
+        let file = parse(ir);
+        let index = FileIndex::new(&file);
+        let specs = index.schemas["schema.user"].field_specs();
+        let email = specs.iter().find(|f| f.name == "email").unwrap();
+        assert_eq!(email.r#type, "string");
+        assert!(email.has("unique") && email.has("indexed"));
+
+        let diags = check_surv_file(&file);
+        let duplicate: Vec<_> =
+            diags.iter().filter(|d| d.kind == "DuplicateFieldAnnotation").collect();
+        assert_eq!(duplicate.len(), 1);
+        assert_eq!(duplicate[0].location, "schema.user.fields.id");
+        let unknown: Vec<_> = diags.iter().filter(|d| d.kind == "UnknownFieldAnnotation").collect();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("use @indexed"));
+    }
+
+    #[test]
+    fn warns_when_deprecated_schemas_are_used() {
+        let ir = r#"
+[schema.user_v1]
+kind = "node"
+deprecated = true
+deprecated_note = "use schema.user"
+
+[schema.user]
+kind = "node"
+
+[schema.follows]
+kind = "edge"
+from = "schema.user_v1"
+to = "schema.user"
+
+[func.load_user]
+intent = "test"
+input = ["schema.user_v1"]
+output = ["schema.user"]
+
+[mod.users]
+purpose = "test"
+schemas = ["schema.user", "schema.follows"]
+funcs = ["func.load_user"]
+pipeline = ["func.load_user"]
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let deprecated: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "DeprecatedSchemaUsed")
+            .collect();
+        assert_eq!(deprecated.len(), 2);
+        assert!(deprecated.iter().all(|d| d.message.contains("use schema.user")));
+        assert_eq!(deprecated[0].location, "func.load_user.input(schema.user_v1)");
+    }
+
+    #[test]
+    fn warns_when_schema_lang_differs_from_every_user() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+impl.lang = "rust"
+
+[schema.session]
+kind = "node"
+impl.lang = "rust"
+
+[func.render_user]
+intent = "test"
+input = ["schema.user", "schema.session"]
+impl.lang = "ts"
+
+[func.load_session]
+intent = "test"
+output = ["schema.session"]
+"#;
+
+        let diags = check_impl_langs(&parse(ir));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "ImplLangMismatch");
+        assert_eq!(diags[0].location, "schema.user.impl.lang");
+        assert!(diags[0].message.contains("func.render_user (ts)"));
+    }
+
+    #[test]
+    fn flags_intents_at_the_wrong_end_of_a_pipeline() {
+        let ir = r#"
+[func.send_reply]
+intent = "Send the response to the client"
+
+[func.load]
+intent = "Load the user"
+
+[func.validate_input]
+intent = "Validates the request body"
+
+[mod.api]
+purpose = "test"
+funcs = ["func.send_reply", "func.load", "func.validate_input"]
+pipeline = ["func.send_reply", "func.load", "func.validate_input"]
+"#;
+
+        let diags = check_intent_order(&parse(ir));
+        assert_eq!(diags.len(), 2);
+        assert!(diags.iter().all(|d| d.kind == "SuspiciousPipelineOrder" && d.severity == "info"));
+        assert!(diags[0].message.contains("func.send_reply"));
+        assert!(diags[1].message.contains("func.validate_input"));
+    }
+
+    #[test]
+    fn suggests_the_closest_definition_for_undefined_references() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[func.load_user]
+intent = "test"
+input = ["schema.usr"]
+
+[mod.users]
+purpose = "test"
+funcs = ["func.load_user"]
+pipeline = ["func.lod_user", "func.render"]
+"#;
+
+        let diags = check_surv_file(&parse(ir));
+        let suggestion = |kind: &str, needle: &str| {
+            diags
+                .iter()
+                .find(|d| d.kind == kind && d.message.contains(needle))
+                .and_then(|d| d.suggestion.clone())
+        };
+
+        assert_eq!(
+            suggestion("UndefinedSchema", "schema.usr").as_deref(),
+            Some("replace with schema.user")
+        );
+        assert_eq!(
+            suggestion("UndefinedFuncInPipeline", "func.lod_user").as_deref(),
+            Some("replace with func.load_user")
+        );
+        assert_eq!(
+            suggestion("UndefinedFuncInPipeline", "func.render").as_deref(),
+            Some("define [func.render] or correct the reference")
+        );
+    }
+
+    #[test]
+    fn validates_meta_version_as_semver() {
+        for valid in ["1.2.0", "0.0.1", "2.0.0-rc.1", "1.0.0-alpha-2+build.7"] {
+            assert!(is_semver(valid), "{valid}");
+        }
+        for invalid in ["v1", "1.2", "01.2.3", "1.2.3-", "1.2.3-01", "1.2.3+"] {
+            assert!(!is_semver(invalid), "{invalid}");
+        }
+
+        let diags = check_meta_version(&parse("[meta]\nname = \"api\"\nversion = \"v1\"\n"));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "InvalidVersion");
+        assert_eq!(diags[0].location, "meta.version");
+        assert!(check_meta_version(&parse("[meta]\nname = \"api\"\n")).is_empty());
+    }
+
+    #[test]
+    fn detects_a_broken_data_flow_across_the_whole_pipeline() {
+        let ir = r#"
+[schema.request]
+kind = "node"
+
+[schema.user]
+kind = "node"
+
+[schema.report]
+kind = "node"
+
+[func.parse]
+intent = "test"
+input = ["schema.request"]
+output = ["schema.user"]
+
+[func.audit]
+intent = "test"
+input = ["schema.request"]
+output = ["schema.request"]
+
+[func.render]
+intent = "test"
+input = ["schema.user"]
+output = ["schema.report"]
+
+[func.reload]
+intent = "test"
+input = ["schema.report"]
+
+[mod.carried]
+purpose = "test"
+pipeline = ["func.parse", "func.audit", "func.render"]
+
+[mod.broken]
+purpose = "test"
+pipeline = ["func.audit", "func.render", "func.reload"]
+"#;
+
+        let diags: Vec<_> = check_surv_file(&parse(ir))
+            .into_iter()
+            .filter(|d| d.kind == "PipelineDataFlowBroken")
+            .collect();
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.starts_with("mod mod.broken"));
+        assert_eq!(diags[0].location, "mod.broken.pipeline(func.render)");
+    }
+
+    #[test]
+    fn detects_pipeline_order_against_data_flow() {
+        let ir = r#"
+[schema.request]
+kind = "node"
+
+[schema.user]
+kind = "node"
+
+[schema.snapshot]
+kind = "node"
+
+[schema.response]
+kind = "node"
+
+[func.build]
+intent = "test"
+input = ["schema.request"]
+output = ["schema.user"]
+
+[func.save]
+intent = "test"
+input = ["schema.user", "schema.snapshot"]
+output = ["schema.snapshot"]
+
+[func.respond]
+intent = "test"
+input = ["schema.snapshot"]
+output = ["schema.response"]
+
+[mod.swapped]
+purpose = "test"
+pipeline = ["func.save", "func.respond", "func.build"]
+
+[mod.ordered]
+purpose = "test"
+pipeline = ["func.build", "func.save", "func.respond"]
+"#;
+
+        let diags: Vec<_> = check_surv_file(&parse(ir))
+            .into_iter()
+            .filter(|d| d.kind == "PipelineOrderViolatesDataFlow")
+            .collect();
+        assert_eq!(diags.len(), 1, "{:?}", diags);
+        assert_eq!(diags[0].location, "mod.swapped.pipeline(func.save)");
+        assert!(diags[0].message.contains("lists func.save before func.build"));
+        assert_eq!(
+            diags[0].suggestion.as_deref(),
+            Some("reorder the chain as func.build -> func.save -> func.respond")
+        );
+    }
+
+    #[test]
+    fn notes_funcs_with_identical_signatures() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.order]
+kind = "node"
+
+[func.create_order]
+intent = "test"
+input = ["schema.user", "schema.order"]
+output = ["schema.order"]
+
+[func.place_order]
+intent = "test"
+input = ["schema.order", "schema.user"]
+output = ["schema.order"]
+
+[func.cancel_order]
+intent = "test"
+input = ["schema.order"]
+output = ["schema.order"]
+
+[func.noop_a]
+intent = "test"
+
+[func.noop_b]
+intent = "test"
+"#;
+
+        let diags = check_duplicate_funcs(&parse(ir));
+        assert_eq!(diags.len(), 1, "{:?}", diags);
+        assert_eq!(diags[0].kind, "PossibleDuplicateFunc");
+        assert_eq!(diags[0].severity, "info");
+        assert!(diags[0].message.starts_with("func.create_order, func.place_order all take"));
+        assert_eq!(diags[0].location, "func.place_order");
+    }
+
+    #[test]
+    fn notes_schemas_shared_by_boundaries() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.order]
+kind = "node"
+
+[schema.accounts]
+kind = "boundary"
+over = ["schema.user"]
+
+[schema.checkout]
+kind = "boundary"
+over = ["schema.user", "schema.order"]
+"#;
+
+        let diags = check_boundary_overlap(&parse(ir));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "OverlappingBoundaries");
+        assert_eq!(diags[0].severity, "info");
+        assert!(diags[0].message.contains("schema.accounts, schema.checkout"));
+        assert_eq!(diags[0].location, "schema.checkout.over(schema.user)");
+    }
 }
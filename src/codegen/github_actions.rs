@@ -1,4 +1,6 @@
-use crate::deploy::ast::DeployFile;
+use crate::codegen::{yaml_flow_list, DeployGenerator};
+use crate::deploy::ast::{DeployFile, JobCondition, Notify};
+use crate::deploy::environments::expand_environments;
 use std::collections::HashMap;
 
 pub struct GitHubActionsGenerator;
@@ -9,6 +11,10 @@ impl GitHubActionsGenerator {
     }
 
     pub fn generate(&self, deploy: &DeployFile) -> String {
+        // Instantiate per-environment jobs before rendering
+        let expanded = expand_environments(deploy);
+        let deploy = &expanded;
+
         let mut output = String::new();
 
         // Header
@@ -22,10 +28,7 @@ impl GitHubActionsGenerator {
         output.push_str("\n");
 
         output.push_str("name: Deploy Pipeline\n\n");
-        output.push_str("on:\n");
-        output.push_str("  push:\n");
-        output.push_str("    branches: [main]\n");
-        output.push_str("  workflow_dispatch:\n\n");
+        output.push_str(&Self::triggers(deploy));
 
         // Collect all secrets referenced
         let mut all_secrets: Vec<String> = Vec::new();
@@ -40,6 +43,8 @@ impl GitHubActionsGenerator {
 
         output.push_str("jobs:\n");
 
+        let stage_needs = self.stage_needs(deploy);
+
         // Generate jobs in topological order
         for (job_name, job) in &deploy.jobs {
             output.push_str(&format!("  {}:\n", Self::sanitize_job_name(job_name)));
@@ -61,6 +66,15 @@ impl GitHubActionsGenerator {
             };
             output.push_str(&format!("    runs-on: {}\n", runner));
 
+            // One run per matrix combination
+            if !job.matrix.is_empty() {
+                output.push_str("    strategy:\n");
+                output.push_str("      matrix:\n");
+                for (axis, values) in &job.matrix {
+                    output.push_str(&format!("        {}: {}\n", axis, yaml_flow_list(values)));
+                }
+            }
+
             // Add environment for production/staging
             if !job.uses_target.is_empty() {
                 let target_name = job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target);
@@ -69,20 +83,32 @@ impl GitHubActionsGenerator {
                 }
             }
 
-            // Add dependencies (needs)
-            if !job.requires.is_empty() {
+            // Add dependencies (needs), including every job of the previous declared stage
+            let mut needs: Vec<String> = job
+                .requires
+                .iter()
+                .map(|req| Self::sanitize_job_name(req.strip_prefix("job.").unwrap_or(req)))
+                .collect();
+            for dep in stage_needs.get(job_name).into_iter().flatten() {
+                let dep = Self::sanitize_job_name(dep);
+                if !needs.contains(&dep) {
+                    needs.push(dep);
+                }
+            }
+            if !needs.is_empty() {
                 output.push_str("    needs:");
-                if job.requires.len() == 1 {
-                    let dep = job.requires[0].strip_prefix("job.").unwrap_or(&job.requires[0]);
-                    output.push_str(&format!(" {}\n", Self::sanitize_job_name(dep)));
+                if needs.len() == 1 {
+                    output.push_str(&format!(" {}\n", needs[0]));
                 } else {
                     output.push_str("\n");
-                    for req in &job.requires {
-                        let dep = req.strip_prefix("job.").unwrap_or(req);
-                        output.push_str(&format!("      - {}\n", Self::sanitize_job_name(dep)));
+                    for dep in &needs {
+                        output.push_str(&format!("      - {}\n", dep));
                     }
                 }
             }
+            if let Some(condition) = JobCondition::parse(&job.when) {
+                output.push_str(&format!("    if: ${{{{ {} }}}}\n", Self::job_if(&condition)));
+            }
 
             // Steps
             output.push_str("    steps:\n");
@@ -123,9 +149,146 @@ impl GitHubActionsGenerator {
             output.push_str("\n");
         }
 
+        if let Some(notify) = &deploy.notify {
+            output.push_str(&self.notify_job(deploy, notify));
+        }
+
+        output
+    }
+
+    /// Pushes to main plus any branch or tag a job's `when` waits for, and
+    /// manual dispatch.
+    fn triggers(deploy: &DeployFile) -> String {
+        let mut branches = vec!["main".to_string()];
+        let mut tags = false;
+        for job in deploy.jobs.values() {
+            match JobCondition::parse(&job.when) {
+                Some(JobCondition::OnBranch(branch)) if !branches.contains(&branch) => {
+                    branches.push(branch)
+                }
+                Some(JobCondition::OnTag) => tags = true,
+                _ => {}
+            }
+        }
+
+        let mut output = String::from("on:\n  push:\n");
+        output.push_str(&format!("    branches: [{}]\n", branches.join(", ")));
+        if tags {
+            output.push_str("    tags: [\"*\"]\n");
+        }
+        output.push_str("  workflow_dispatch:\n\n");
+        output
+    }
+
+    /// The `if:` expression for a job condition
+    fn job_if(condition: &JobCondition) -> String {
+        match condition {
+            JobCondition::Always => "always()".to_string(),
+            JobCondition::Manual => "github.event_name == 'workflow_dispatch'".to_string(),
+            JobCondition::OnTag => "startsWith(github.ref, 'refs/tags/')".to_string(),
+            JobCondition::OnBranch(branch) => format!("github.ref == 'refs/heads/{}'", branch),
+        }
+    }
+
+    /// A final job that waits for every other job and sends one step per
+    /// channel when the pipeline outcome matches `on`.
+    fn notify_job(&self, deploy: &DeployFile, notify: &Notify) -> String {
+        let taken: Vec<String> = deploy.jobs.keys().map(|n| Self::sanitize_job_name(n)).collect();
+        let name = if taken.iter().any(|n| n == "notify") { "notify_pipeline" } else { "notify" };
+        let condition = match (
+            notify.on.iter().any(|e| e == "success"),
+            notify.on.iter().any(|e| e == "failure"),
+        ) {
+            (true, false) => "success()",
+            (false, true) => "failure()",
+            _ => "always()",
+        };
+
+        let mut output = format!("  {}:\n", name);
+        output.push_str("    runs-on: ubuntu-latest\n");
+        if !taken.is_empty() {
+            output.push_str("    needs:\n");
+            for dep in &taken {
+                output.push_str(&format!("      - {}\n", dep));
+            }
+        }
+        output.push_str(&format!("    if: ${{{{ {} }}}}\n", condition));
+        output.push_str("    env:\n");
+        output.push_str(
+            "      PIPELINE_STATUS: ${{ contains(needs.*.result, 'failure') && 'failure' || 'success' }}\n",
+        );
+        output.push_str("    steps:\n");
+        for channel in notify.channels.iter().filter(|c| !c.trim().is_empty()) {
+            output.push_str(&format!("      - name: Notify {}\n", channel));
+            let (kind, address) = channel.split_once(':').unwrap_or(("", channel));
+            match kind {
+                "slack" => {
+                    output.push_str("        run: |\n");
+                    output.push_str(&format!(
+                        "          curl -fsS -X POST -H 'Content-Type: application/json' -d \"{{\\\"channel\\\": \\\"{}\\\", \\\"text\\\": \\\"Deploy pipeline $PIPELINE_STATUS\\\"}}\" \"$SLACK_WEBHOOK_URL\"\n",
+                        address
+                    ));
+                    output.push_str("        env:\n");
+                    output.push_str("          SLACK_WEBHOOK_URL: ${{ secrets.SLACK_WEBHOOK_URL }}\n");
+                }
+                "webhook" => {
+                    output.push_str("        run: |\n");
+                    output.push_str(&format!(
+                        "          curl -fsS -X POST -H 'Content-Type: application/json' -d \"{{\\\"status\\\": \\\"$PIPELINE_STATUS\\\"}}\" \"{}\"\n",
+                        address
+                    ));
+                }
+                _ => {
+                    output.push_str(&format!(
+                        "        # No built-in delivery for '{}'; replace with your notifier\n",
+                        channel
+                    ));
+                    output.push_str("        run: |\n");
+                    output.push_str(&format!(
+                        "          echo \"Deploy pipeline $PIPELINE_STATUS: notify {}\"\n",
+                        channel
+                    ));
+                }
+            }
+            output.push('\n');
+        }
         output
     }
 
+    /// GitHub Actions has no stages, so a job with an explicit `stage` waits
+    /// for every job in the nearest earlier declared stage that has jobs.
+    fn stage_needs(&self, deploy: &DeployFile) -> HashMap<String, Vec<String>> {
+        let mut needs = HashMap::new();
+        let Some(pipeline) = &deploy.pipeline else {
+            return needs;
+        };
+
+        let jobs_in_stage = |stage: &str| -> Vec<String> {
+            deploy
+                .jobs
+                .iter()
+                .filter(|(_, job)| job.stage == stage)
+                .map(|(name, _)| name.clone())
+                .collect()
+        };
+
+        for (job_name, job) in &deploy.jobs {
+            let Some(index) = pipeline.stages.iter().position(|s| *s == job.stage) else {
+                continue;
+            };
+            let previous = pipeline.stages[..index]
+                .iter()
+                .rev()
+                .map(|stage| jobs_in_stage(stage))
+                .find(|jobs| !jobs.is_empty());
+            if let Some(previous) = previous {
+                needs.insert(job_name.clone(), previous);
+            }
+        }
+
+        needs
+    }
+
     fn build_job_graph(&self, deploy: &DeployFile) -> HashMap<String, Vec<String>> {
         let mut graph = HashMap::new();
         for (job_name, job) in &deploy.jobs {
@@ -167,6 +330,20 @@ impl Default for GitHubActionsGenerator {
     }
 }
 
+impl DeployGenerator for GitHubActionsGenerator {
+    fn name(&self) -> &str {
+        "github-actions"
+    }
+
+    fn default_path(&self) -> &str {
+        ".github/workflows/deploy.yml"
+    }
+
+    fn generate(&self, deploy: &DeployFile) -> String {
+        GitHubActionsGenerator::generate(self, deploy)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +356,7 @@ mod tests {
         deploy.pipeline = Some(Pipeline {
             name: "test-pipeline".to_string(),
             description: "Test deployment".to_string(),
+            stages: Vec::new(),
         });
 
         let mut jobs = BTreeMap::new();
@@ -193,6 +371,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         deploy.jobs = jobs;
@@ -222,6 +404,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         jobs.insert(
@@ -235,6 +421,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         deploy.jobs = jobs;
@@ -244,4 +434,154 @@ mod tests {
 
         assert!(yaml.contains("needs: build"));
     }
+
+    #[test]
+    fn expands_jobs_per_environment() {
+        let mut deploy = DeployFile::default();
+        for name in ["staging", "prod"] {
+            deploy.targets.insert(
+                name.to_string(),
+                Target {
+                    name: name.to_string(),
+                    kind: name.to_string(),
+                    domain: String::new(),
+                },
+            );
+        }
+        deploy.jobs.insert(
+            "deploy".to_string(),
+            Job {
+                name: "deploy".to_string(),
+                runs: vec!["kubectl apply -f deploy.yaml".to_string()],
+                environments: vec!["target.staging".to_string(), "target.prod".to_string()],
+                stage: String::new(),
+                ..Default::default()
+            },
+        );
+
+        let generator = GitHubActionsGenerator::new();
+        let yaml = generator.generate(&deploy);
+
+        assert!(yaml.contains("  deploy_staging:"));
+        assert!(yaml.contains("  deploy_prod:"));
+        assert!(yaml.contains("environment: prod"));
+    }
+
+    #[test]
+    fn orders_jobs_by_declared_stages() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.pipeline]
+name = "staged"
+stages = ["build", "test", "deploy"]
+
+[deploy.job.compile]
+stage = "build"
+runs = ["make"]
+
+[deploy.job.unit]
+stage = "test"
+runs = ["make test"]
+
+[deploy.job.lint]
+stage = "test"
+runs = ["make lint"]
+
+[deploy.job.release]
+stage = "deploy"
+requires = ["job.unit"]
+runs = ["make release"]
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitHubActionsGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains("  unit:\n    runs-on: ubuntu-latest\n    needs: compile\n"));
+        assert!(yaml.contains("    needs:\n      - unit\n      - lint\n"));
+    }
+
+    #[test]
+    fn renders_notify_job_after_all_jobs() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.job.build]
+runs = ["make"]
+
+[deploy.job.release]
+requires = ["job.build"]
+runs = ["make release"]
+
+[deploy.notify]
+channels = ["slack:#deploys", "webhook:https://hooks.example.com/deploy"]
+on = ["failure"]
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitHubActionsGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains(
+            "  notify:\n    runs-on: ubuntu-latest\n    needs:\n      - build\n      - release\n    if: ${{ failure() }}\n"
+        ));
+        assert!(yaml.contains("      - name: Notify slack:#deploys\n"));
+        assert!(yaml.contains("SLACK_WEBHOOK_URL: ${{ secrets.SLACK_WEBHOOK_URL }}"));
+        assert!(yaml.contains("\"https://hooks.example.com/deploy\""));
+    }
+
+    #[test]
+    fn translates_job_conditions_to_if() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.job.build]
+runs = ["make"]
+
+[deploy.job.publish]
+requires = ["job.build"]
+runs = ["./publish.sh"]
+when = "on_tag"
+
+[deploy.job.preview]
+requires = ["job.build"]
+runs = ["./preview.sh"]
+when = "on_branch(develop)"
+
+[deploy.job.cleanup]
+requires = ["job.build"]
+runs = ["./cleanup.sh"]
+when = "always"
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitHubActionsGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains("    branches: [main, develop]\n    tags: [\"*\"]\n"));
+        assert!(yaml
+            .contains("    needs: build\n    if: ${{ startsWith(github.ref, 'refs/tags/') }}\n"));
+        assert!(yaml.contains("    if: ${{ github.ref == 'refs/heads/develop' }}\n"));
+        assert!(yaml.contains("    if: ${{ always() }}\n"));
+        assert_eq!(yaml.matches("    if: ").count(), 3);
+    }
+
+    #[test]
+    fn renders_job_matrix() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.job.deploy]
+runs = ["./deploy.sh"]
+
+[deploy.job.deploy.matrix]
+region = ["us", "eu"]
+tier = ["web"]
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitHubActionsGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains(
+            "    strategy:\n      matrix:\n        region: [\"us\", \"eu\"]\n        tier: [\"web\"]\n"
+        ));
+    }
 }
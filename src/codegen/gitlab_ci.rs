@@ -1,4 +1,5 @@
-use crate::deploy::ast::DeployFile;
+use crate::codegen::{yaml_flow_list, DeployGenerator};
+use crate::deploy::ast::{DeployFile, JobCondition};
 use std::collections::HashMap;
 
 pub struct GitLabCIGenerator;
@@ -69,6 +70,21 @@ impl GitLabCIGenerator {
                 output.push_str("  image: ubuntu:latest\n");
             }
 
+            // One run per matrix combination
+            if !job.matrix.is_empty() {
+                output.push_str("  parallel:\n");
+                output.push_str("    matrix:\n");
+                for (i, (axis, values)) in job.matrix.iter().enumerate() {
+                    let bullet = if i == 0 { "-" } else { " " };
+                    output.push_str(&format!(
+                        "      {} {}: {}\n",
+                        bullet,
+                        axis,
+                        yaml_flow_list(values)
+                    ));
+                }
+            }
+
             // Add dependencies (needs)
             if !job.requires.is_empty() {
                 output.push_str("  needs:\n");
@@ -97,8 +113,15 @@ impl GitLabCIGenerator {
                 output.push_str(&format!("    - {}\n", cmd));
             }
 
+            // An explicit condition replaces the production defaults below
+            let condition = JobCondition::parse(&job.when);
+            if let Some(condition) = &condition {
+                output.push_str("  rules:\n");
+                output.push_str(&format!("    - {}\n", Self::rule(condition)));
+            }
+
             // Add manual approval for production
-            if !job.uses_target.is_empty() {
+            if condition.is_none() && !job.uses_target.is_empty() {
                 let target_name = job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target);
                 if let Some(target) = deploy.targets.get(target_name) {
                     if target.kind == "production" && deploy.gate.is_some() {
@@ -108,7 +131,7 @@ impl GitLabCIGenerator {
             }
 
             // Only run on main branch for production
-            if !job.uses_target.is_empty() {
+            if condition.is_none() && !job.uses_target.is_empty() {
                 let target_name = job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target);
                 if let Some(target) = deploy.targets.get(target_name) {
                     if target.kind == "production" {
@@ -134,7 +157,24 @@ impl GitLabCIGenerator {
         output
     }
 
-    fn determine_stages(&self, deploy: &DeployFile) -> Vec<String> {
+    /// The `rules:` entry for a job condition
+    fn rule(condition: &JobCondition) -> String {
+        match condition {
+            JobCondition::Always => "when: always".to_string(),
+            JobCondition::Manual => "when: manual".to_string(),
+            JobCondition::OnTag => "if: $CI_COMMIT_TAG".to_string(),
+            JobCondition::OnBranch(branch) => {
+                format!("if: $CI_COMMIT_BRANCH == \"{}\"", branch)
+            }
+        }
+    }
+
+    /// Stage order: the declared stages, else build/test/deploy as present.
+    pub(crate) fn determine_stages(&self, deploy: &DeployFile) -> Vec<String> {
+        if let Some(declared) = declared_stages(deploy) {
+            return declared.to_vec();
+        }
+
         let mut stages = Vec::new();
         let has_build = deploy.jobs.iter().any(|(name, _)| name.contains("build"));
         let has_test = deploy.jobs.iter().any(|(name, _)| name.contains("test"));
@@ -159,10 +199,19 @@ impl GitLabCIGenerator {
         stages
     }
 
-    fn assign_job_stages(&self, deploy: &DeployFile) -> HashMap<String, String> {
+    /// Each job's stage: its explicit `stage`, else one guessed from its name,
+    /// target and requirements.
+    pub(crate) fn assign_job_stages(&self, deploy: &DeployFile) -> HashMap<String, String> {
         let mut stages = HashMap::new();
 
+        let declared = declared_stages(deploy);
+
         for (job_name, job) in &deploy.jobs {
+            if !job.stage.is_empty() {
+                stages.insert(job_name.clone(), job.stage.clone());
+                continue;
+            }
+
             let stage = if job_name.contains("build") {
                 "build"
             } else if job_name.contains("test") {
@@ -174,6 +223,13 @@ impl GitLabCIGenerator {
             } else {
                 "deploy"
             };
+            // A guessed stage must still be one of the declared ones
+            let stage = match declared {
+                Some(declared) if !declared.iter().any(|s| s == stage) => {
+                    declared.last().map(String::as_str).unwrap_or(stage)
+                }
+                _ => stage,
+            };
             stages.insert(job_name.clone(), stage.to_string());
         }
 
@@ -185,12 +241,35 @@ impl GitLabCIGenerator {
     }
 }
 
+/// Stages declared in `[deploy.pipeline].stages`, if any
+fn declared_stages(deploy: &DeployFile) -> Option<&[String]> {
+    deploy
+        .pipeline
+        .as_ref()
+        .map(|p| p.stages.as_slice())
+        .filter(|stages| !stages.is_empty())
+}
+
 impl Default for GitLabCIGenerator {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl DeployGenerator for GitLabCIGenerator {
+    fn name(&self) -> &str {
+        "gitlab-ci"
+    }
+
+    fn default_path(&self) -> &str {
+        ".gitlab-ci.yml"
+    }
+
+    fn generate(&self, deploy: &DeployFile) -> String {
+        GitLabCIGenerator::generate(self, deploy)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,6 +282,7 @@ mod tests {
         deploy.pipeline = Some(Pipeline {
             name: "test-pipeline".to_string(),
             description: "Test deployment".to_string(),
+            stages: Vec::new(),
         });
 
         let mut jobs = BTreeMap::new();
@@ -217,6 +297,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         deploy.jobs = jobs;
@@ -246,6 +330,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         jobs.insert(
@@ -259,6 +347,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         deploy.jobs = jobs;
@@ -300,6 +392,10 @@ mod tests {
                 uses_perm: String::new(),
                 produces: Vec::new(),
                 side_effects: Vec::new(),
+                environments: Vec::new(),
+                stage: String::new(),
+                matrix: BTreeMap::new(),
+                when: String::new(),
             },
         );
         deploy.jobs = jobs;
@@ -311,4 +407,77 @@ mod tests {
         assert!(yaml.contains("only:"));
         assert!(yaml.contains("- main"));
     }
+
+    #[test]
+    fn explicit_condition_replaces_production_defaults() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.target.prod]
+kind = "production"
+
+[deploy.job.deploy_prod]
+runs = ["kubectl apply"]
+uses_target = "target.prod"
+when = "on_tag"
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitLabCIGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains("  rules:\n    - if: $CI_COMMIT_TAG\n"));
+        assert!(!yaml.contains("only:"));
+    }
+
+    #[test]
+    fn honors_declared_stages() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.pipeline]
+name = "staged"
+stages = ["compile", "verify", "ship"]
+
+[deploy.job.build]
+stage = "compile"
+runs = ["make"]
+
+[deploy.job.lint]
+stage = "verify"
+runs = ["make lint"]
+
+[deploy.job.publish]
+requires = ["job.lint"]
+runs = ["make publish"]
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitLabCIGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains("stages:\n  - compile\n  - verify\n  - ship\n"));
+        assert!(yaml.contains("lint:\n  stage: verify\n"));
+        // No explicit stage and the guessed "deploy" is not declared: use the last stage
+        assert!(yaml.contains("publish:\n  stage: ship\n"));
+    }
+
+    #[test]
+    fn renders_job_matrix() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.job.deploy]
+runs = ["./deploy.sh"]
+
+[deploy.job.deploy.matrix]
+region = ["us", "eu"]
+tier = ["web"]
+"#,
+        )
+        .unwrap();
+
+        let yaml = GitLabCIGenerator::new().generate(&deploy);
+
+        assert!(yaml.contains(
+            "  parallel:\n    matrix:\n      - region: [\"us\", \"eu\"]\n        tier: [\"web\"]\n"
+        ));
+    }
 }
@@ -3,3 +3,129 @@ pub mod gitlab_ci;
 
 pub use github_actions::GitHubActionsGenerator;
 pub use gitlab_ci::GitLabCIGenerator;
+
+use crate::deploy::ast::DeployFile;
+
+/// A CI/CD backend that renders a Deploy IR file to its native config.
+pub trait DeployGenerator {
+    /// Platform name used on the command line, e.g. `github-actions`.
+    fn name(&self) -> &str;
+    /// Conventional path of the generated file, relative to the repository root.
+    fn default_path(&self) -> &str;
+    fn generate(&self, deploy: &DeployFile) -> String;
+}
+
+/// Every available backend, in the order `codegen all` writes them.
+pub fn generators() -> Vec<Box<dyn DeployGenerator>> {
+    vec![
+        Box::new(GitHubActionsGenerator::new()),
+        Box::new(GitLabCIGenerator::new()),
+    ]
+}
+
+/// Look up a backend by its platform name.
+pub fn find_generator(name: &str) -> Option<Box<dyn DeployGenerator>> {
+    generators().into_iter().find(|g| g.name() == name)
+}
+
+const SOURCE_HASH_PREFIX: &str = "# Source-Hash: ";
+const GENERATED_AT_PREFIX: &str = "# Generated-At: ";
+
+/// Stable fingerprint of a Deploy IR file: 64-bit FNV-1a over its JSON form,
+/// which is deterministic because every map in the AST is ordered.
+pub fn source_hash(deploy: &DeployFile) -> String {
+    let json = serde_json::to_string(deploy).unwrap_or_default();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in json.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("fnv1a64:{:016x}", hash)
+}
+
+/// Insert `Source-Hash` and `Generated-At` comment lines after the first
+/// header line of a generator's output (`codegen --provenance`).
+pub fn with_provenance(generated: &str, deploy: &DeployFile, generated_at: &str) -> String {
+    let (first, rest) = generated.split_once('\n').unwrap_or((generated, ""));
+    format!(
+        "{}\n{}{}\n{}{}\n{}",
+        first,
+        SOURCE_HASH_PREFIX,
+        source_hash(deploy),
+        GENERATED_AT_PREFIX,
+        generated_at,
+        rest
+    )
+}
+
+/// The `Source-Hash` a generated file was stamped with, if any.
+pub fn embedded_source_hash(text: &str) -> Option<&str> {
+    text.lines()
+        .find_map(|line| line.strip_prefix(SOURCE_HASH_PREFIX))
+        .map(str::trim)
+}
+
+/// Generated text without its provenance lines or trailing blank lines, to
+/// compare file bodies.
+pub fn strip_provenance(text: &str) -> String {
+    let body: String = text
+        .lines()
+        .filter(|line| !line.starts_with(SOURCE_HASH_PREFIX))
+        .filter(|line| !line.starts_with(GENERATED_AT_PREFIX))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    body.trim_end().to_string()
+}
+
+/// Render values as a YAML flow sequence of quoted strings: `["us", "eu"]`.
+pub(crate) fn yaml_flow_list(values: &[String]) -> String {
+    let items: Vec<String> = values
+        .iter()
+        .map(|v| format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_generators_by_name() {
+        let names: Vec<String> = generators().iter().map(|g| g.name().to_string()).collect();
+        assert_eq!(names, vec!["github-actions", "gitlab-ci"]);
+        assert_eq!(
+            find_generator("gitlab-ci").map(|g| g.default_path().to_string()),
+            Some(".gitlab-ci.yml".to_string())
+        );
+        assert!(find_generator("jenkins").is_none());
+    }
+
+    #[test]
+    fn stamps_and_reads_back_the_source_hash() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.job.build]
+runs = ["npm run build"]
+"#,
+        )
+        .unwrap();
+        let generator = GitLabCIGenerator::new();
+        let plain = generator.generate(&deploy);
+        let stamped = with_provenance(&plain, &deploy, "2024-01-01T00:00:00Z");
+
+        assert!(stamped.starts_with("# Generated from Deploy IR by surc\n# Source-Hash: fnv1a64:"));
+        assert_eq!(embedded_source_hash(&stamped), Some(source_hash(&deploy).as_str()));
+        assert_eq!(embedded_source_hash(&plain), None);
+        assert_eq!(strip_provenance(&stamped), strip_provenance(&plain));
+
+        let changed = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.job.build]
+runs = ["cargo build"]
+"#,
+        )
+        .unwrap();
+        assert_ne!(source_hash(&changed), source_hash(&deploy));
+    }
+}
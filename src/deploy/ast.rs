@@ -13,12 +13,29 @@ pub struct DeployFile {
     pub release: Option<Release>,
     pub gate: Option<Gate>,
     pub rollback: Option<Rollback>,
+    pub notify: Option<Notify>,
+    /// Deploy fragments listed in `include = [...]`, relative to this file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
+    /// Jobs defined by more than one file while merging includes
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include_conflicts: Vec<IncludeConflict>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IncludeConflict {
+    pub job: String,
+    pub first_file: String,
+    pub second_file: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Pipeline {
     pub name: String,
     pub description: String,
+    /// Ordered stage names; jobs opt in with `stage = "..."`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -38,6 +55,52 @@ pub struct Job {
     pub uses_perm: String,
     pub produces: Vec<String>,
     pub side_effects: Vec<String>,
+    /// Targets to instantiate this job for; expanded into one job per target
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub environments: Vec<String>,
+    /// Explicit stage from `[deploy.pipeline].stages`; empty when not set
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub stage: String,
+    /// Matrix axes, e.g. `matrix = { region = ["us", "eu"] }`; generators
+    /// run the job once per combination
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub matrix: BTreeMap<String, Vec<String>>,
+    /// Run condition, parsed by `JobCondition::parse`; empty means the job
+    /// runs on every pipeline run
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub when: String,
+}
+
+/// A job's `when` condition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobCondition {
+    /// `always`: run even if an earlier job failed
+    Always,
+    /// `manual`: run only when started by hand
+    Manual,
+    /// `on_tag`: run for tag pushes
+    OnTag,
+    /// `on_branch(<name>)`: run for pushes to one branch
+    OnBranch(String),
+}
+
+impl JobCondition {
+    /// Spellings accepted by `parse`, for error messages and suggestions
+    pub const FORMS: &'static [&'static str] = &["always", "manual", "on_tag", "on_branch(main)"];
+
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim() {
+            "always" => Some(Self::Always),
+            "manual" => Some(Self::Manual),
+            "on_tag" => Some(Self::OnTag),
+            other => {
+                let branch = other.strip_prefix("on_branch(")?.strip_suffix(')')?.trim();
+                let valid = !branch.is_empty()
+                    && !branch.contains(|c: char| c.is_whitespace() || "'\"(),".contains(c));
+                valid.then(|| Self::OnBranch(branch.to_string()))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -59,6 +122,9 @@ pub struct Permission {
     pub name: String,
     pub role: String,
     pub allows: Vec<String>,
+    /// Targets the permission may be used for (e.g. "target.prod"); empty
+    /// means any target
+    pub scope: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -76,6 +142,22 @@ pub struct Gate {
 pub struct Rollback {
     pub on: Vec<String>,
     pub strategy: String,
+    /// Optional job that performs the rollback (e.g. "job.rollback")
+    pub job: String,
+    /// Production targets the rollback covers (e.g. "target.prod"); empty
+    /// means every target
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub covers: Vec<String>,
+}
+
+/// Post-pipeline notifications from `[deploy.notify]`
+#[derive(Debug, Clone, Serialize)]
+pub struct Notify {
+    /// Destinations as `<kind>:<address>`, e.g. "slack:#deploys",
+    /// "email:ops@example.com" or "webhook:https://hooks.example.com/deploy"
+    pub channels: Vec<String>,
+    /// Pipeline outcomes to notify on ("success", "failure"); empty means both
+    pub on: Vec<String>,
 }
 
 impl Default for Job {
@@ -89,6 +171,10 @@ impl Default for Job {
             uses_perm: String::new(),
             produces: Vec::new(),
             side_effects: Vec::new(),
+            environments: Vec::new(),
+            stage: String::new(),
+            matrix: BTreeMap::new(),
+            when: String::new(),
         }
     }
 }
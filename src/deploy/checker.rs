@@ -1,36 +1,248 @@
-use crate::deploy::ast::DeployFile;
+use crate::deploy::ast::{DeployFile, Job, JobCondition};
+use crate::deploy::environments::{
+    environment_instances, expand_environments, instance_name_clashes, EnvironmentInstance,
+};
 use crate::diagnostic::Diagnostic;
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::suggest::closest_match;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Names of the jobs a check should look at; `None` means every job.
+type JobFilter<'a> = Option<&'a HashSet<String>>;
+
+/// Rollback strategies recognised by `[deploy.rollback].strategy`
+const KNOWN_ROLLBACK_STRATEGIES: &[&str] = &[
+    "revert",
+    "revert_traffic",
+    "revert_commit",
+    "redeploy_previous",
+    "manual",
+];
+
+/// Pipeline outcomes `[deploy.notify].on` can name
+const NOTIFY_EVENTS: &[&str] = &["success", "failure"];
 
 pub fn check_deploy_file(deploy: &DeployFile) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
 
+    // Check per-environment job instances as they will actually run
+    let source = deploy;
+    let expanded = expand_environments(deploy);
+    let deploy = &expanded;
+
     // Phase 1: Structural checks
-    check_undefined_references(deploy, &mut diags);
+    check_include_conflicts(deploy, None, &mut diags);
+    check_job_name_collisions(deploy, &mut diags);
+    check_undefined_references(deploy, None, &mut diags);
     check_dag_structure(deploy, &mut diags);
     check_unreachable_jobs(deploy, &mut diags);
+    check_empty_job_runs(deploy, None, &mut diags);
+    check_job_matrix(deploy, None, &mut diags);
+    check_job_conditions(deploy, None, &mut diags);
+    check_job_stages(deploy, None, &mut diags);
 
     // Phase 2: Security checks
-    check_secret_scope(deploy, &mut diags);
+    check_secret_scope(deploy, None, &mut diags);
+    check_perm_scope(deploy, None, &mut diags);
     check_prod_safety(deploy, &mut diags);
-    check_side_effects_safety(deploy, &mut diags);
+    check_target_domain_kinds(deploy, &mut diags);
+    check_prod_job_approval(deploy, None, &mut diags);
+    check_prod_rollback_coverage(deploy, None, &mut diags);
+    check_side_effects_safety(deploy, None, &mut diags);
+    check_rollback(deploy, &mut diags);
+    check_notify(deploy, &mut diags);
+
+    map_instances_to_source(source, &mut diags);
+    check_instance_name_clashes(source, None, &mut diags);
+    diags
+}
 
+/// Run only the checks that concern one job: its references, stage, secret
+/// scope and side-effect/approval rules. File-wide checks (cycles,
+/// reachability, gate and rollback sections) are skipped.
+///
+/// A job with `environments` is checked through each of its instances.
+pub fn check_deploy_job(deploy: &DeployFile, job_name: &str) -> Vec<Diagnostic> {
+    let job_name = job_name.strip_prefix("job.").unwrap_or(job_name);
+    let mut selected: HashSet<String> = HashSet::new();
+    selected.insert(job_name.to_string());
+    if let Some(job) = deploy.jobs.get(job_name) {
+        for env in &job.environments {
+            let env = env.strip_prefix("target.").unwrap_or(env);
+            selected.insert(format!("{}_{}", job_name, env));
+        }
+    }
+    let only = Some(&selected);
+
+    let mut diags = Vec::new();
+    let source = deploy;
+    let expanded = expand_environments(deploy);
+    let deploy = &expanded;
+
+    check_include_conflicts(deploy, only, &mut diags);
+    check_undefined_references(deploy, only, &mut diags);
+    check_empty_job_runs(deploy, only, &mut diags);
+    check_job_matrix(deploy, only, &mut diags);
+    check_job_conditions(deploy, only, &mut diags);
+    check_job_stages(deploy, only, &mut diags);
+    check_secret_scope(deploy, only, &mut diags);
+    check_perm_scope(deploy, only, &mut diags);
+    check_prod_job_approval(deploy, only, &mut diags);
+    check_prod_rollback_coverage(deploy, only, &mut diags);
+    check_side_effects_safety(deploy, only, &mut diags);
+
+    map_instances_to_source(source, &mut diags);
+    check_instance_name_clashes(source, Some(job_name), &mut diags);
     diags
 }
 
+/// Point diagnostics about a generated instance (`deploy_prod`) back at the
+/// job that declares `environments`, and name the environment.
+fn map_instances_to_source(source: &DeployFile, diags: &mut [Diagnostic]) {
+    let dropped: Vec<EnvironmentInstance> = instance_name_clashes(source)
+        .into_iter()
+        .map(|clash| clash.instance)
+        .collect();
+    let instances: Vec<EnvironmentInstance> = environment_instances(source)
+        .into_iter()
+        .filter(|instance| !dropped.contains(instance))
+        .collect();
+
+    for diag in diags.iter_mut() {
+        for instance in &instances {
+            let prefix = format!("deploy.job.{}", instance.name);
+            let rest = diag.location.strip_prefix(&prefix);
+            if let Some(rest) = rest.filter(|rest| rest.is_empty() || rest.starts_with('.')) {
+                diag.location = format!("deploy.job.{}{}", instance.job, rest);
+            } else if !diag.message.contains(&format!("'{}'", instance.name)) {
+                continue;
+            }
+            diag.message.push_str(&format!(
+                " (environment '{}' of job '{}')",
+                instance.environment, instance.job
+            ));
+            break;
+        }
+    }
+}
+
+/// Check that no generated `<job>_<env>` instance takes an existing job name
+fn check_instance_name_clashes(
+    source: &DeployFile,
+    only_job: Option<&str>,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for clash in instance_name_clashes(source) {
+        let instance = &clash.instance;
+        if only_job.is_some_and(|job| job != instance.job) {
+            continue;
+        }
+        let taken_by = match &clash.other_instance {
+            Some(other) => format!(
+                "the instance of job '{}' for environment '{}'",
+                other.job, other.environment
+            ),
+            None => format!("the job defined as [deploy.job.{}]", instance.name),
+        };
+        diags.push(
+            Diagnostic::new(
+                "error",
+                "EnvironmentInstanceNameClash",
+                format!(
+                    "Job '{}' for environment '{}' would generate '{}', already used by {}",
+                    instance.job, instance.environment, instance.name, taken_by
+                ),
+                format!("deploy.job.{}.environments", instance.job),
+            )
+            .with_suggestion(format!(
+                "rename job '{}' or '{}'",
+                instance.job, instance.name
+            )),
+        );
+    }
+}
+
+fn selected_jobs<'a>(
+    deploy: &'a DeployFile,
+    only: JobFilter<'a>,
+) -> impl Iterator<Item = (&'a String, &'a Job)> {
+    deploy
+        .jobs
+        .iter()
+        .filter(move |(name, _)| only.is_none_or(|only| only.contains(*name)))
+}
+
+/// Check for jobs defined in more than one merged include
+fn check_include_conflicts(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for conflict in &deploy.include_conflicts {
+        if only.is_some_and(|only| !only.contains(&conflict.job)) {
+            continue;
+        }
+        diags.push(Diagnostic::new(
+            "error",
+            "DuplicateJobAcrossIncludes",
+            format!(
+                "Job '{}' is defined in both '{}' and '{}'",
+                conflict.job, conflict.first_file, conflict.second_file
+            ),
+            format!("deploy.job.{}", conflict.job),
+        ));
+    }
+}
+
+/// Check for jobs that become the same CI job identifier once sanitized.
+///
+/// The generators replace `-` and `.` with `_`; CI providers also treat job
+/// ids case-insensitively, so `deploy_prod` and `Deploy-Prod` collide.
+fn check_job_name_collisions(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for job_name in deploy.jobs.keys() {
+        let ci_name = job_name.replace(['-', '.'], "_");
+        match seen.get(&ci_name.to_lowercase()) {
+            Some(first) => diags.push(Diagnostic::new(
+                "warning",
+                "JobNameCollisionAfterSanitization",
+                format!(
+                    "Jobs '{}' and '{}' both become CI job '{}'",
+                    first, job_name, ci_name
+                ),
+                format!("deploy.job.{}", job_name),
+            )),
+            None => {
+                seen.insert(ci_name.to_lowercase(), job_name);
+            }
+        }
+    }
+}
+
+/// Fix for a reference to an undefined `kind.name`: the closest defined name
+/// when it looks like a typo, otherwise defining it.
+fn undefined_fix<V>(kind: &str, name: &str, defined: &BTreeMap<String, V>) -> String {
+    match closest_match(name, defined.keys().map(String::as_str)) {
+        Some(candidate) => format!("replace with {}.{}", kind, candidate),
+        None => format!("define [deploy.{}.{}] or correct the reference", kind, name),
+    }
+}
+
 /// Check for undefined references in jobs
-fn check_undefined_references(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
-    for (job_name, job) in &deploy.jobs {
+fn check_undefined_references(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for (job_name, job) in selected_jobs(deploy, only) {
         // Check job.requires references
         for req in &job.requires {
-            if !req.is_empty() && !deploy.jobs.contains_key(req.strip_prefix("job.").unwrap_or(req))
-            {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedJobReference".into(),
-                    message: format!("Job '{}' requires undefined job '{}'", job_name, req),
-                    location: format!("deploy.job.{}.requires", job_name),
-                });
+            let req_name = req.strip_prefix("job.").unwrap_or(req);
+            if !req.is_empty() && !deploy.jobs.contains_key(req_name) {
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedJobReference",
+                        format!("Job '{}' requires undefined job '{}'", job_name, req),
+                        format!("deploy.job.{}.requires", job_name),
+                    )
+                    .with_suggestion(undefined_fix(
+                        "job",
+                        req_name,
+                        &deploy.jobs,
+                    )),
+                );
             }
         }
 
@@ -41,15 +253,22 @@ fn check_undefined_references(deploy: &DeployFile, diags: &mut Vec<Diagnostic>)
                 .strip_prefix("target.")
                 .unwrap_or(&job.uses_target);
             if !deploy.targets.contains_key(target_name) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedTargetReference".into(),
-                    message: format!(
-                        "Job '{}' references undefined target '{}'",
-                        job_name, job.uses_target
-                    ),
-                    location: format!("deploy.job.{}.uses_target", job_name),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedTargetReference",
+                        format!(
+                            "Job '{}' references undefined target '{}'",
+                            job_name, job.uses_target
+                        ),
+                        format!("deploy.job.{}.uses_target", job_name),
+                    )
+                    .with_suggestion(undefined_fix(
+                        "target",
+                        target_name,
+                        &deploy.targets,
+                    )),
+                );
             }
         }
 
@@ -57,31 +276,48 @@ fn check_undefined_references(deploy: &DeployFile, diags: &mut Vec<Diagnostic>)
         for secret in &job.needs_secrets {
             let secret_name = secret.strip_prefix("secret.").unwrap_or(secret);
             if !deploy.secrets.contains_key(secret_name) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedSecretReference".into(),
-                    message: format!(
-                        "Job '{}' references undefined secret '{}'",
-                        job_name, secret
-                    ),
-                    location: format!("deploy.job.{}.needs_secrets", job_name),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedSecretReference",
+                        format!(
+                            "Job '{}' references undefined secret '{}'",
+                            job_name, secret
+                        ),
+                        format!("deploy.job.{}.needs_secrets", job_name),
+                    )
+                    .with_suggestion(undefined_fix(
+                        "secret",
+                        secret_name,
+                        &deploy.secrets,
+                    )),
+                );
             }
         }
 
         // Check permission references
         if !job.uses_perm.is_empty() {
-            let perm_name = job.uses_perm.strip_prefix("perm.").unwrap_or(&job.uses_perm);
+            let perm_name = job
+                .uses_perm
+                .strip_prefix("perm.")
+                .unwrap_or(&job.uses_perm);
             if !deploy.perms.contains_key(perm_name) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UndefinedPermReference".into(),
-                    message: format!(
-                        "Job '{}' references undefined permission '{}'",
-                        job_name, job.uses_perm
-                    ),
-                    location: format!("deploy.job.{}.uses_perm", job_name),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "error",
+                        "UndefinedPermReference",
+                        format!(
+                            "Job '{}' references undefined permission '{}'",
+                            job_name, job.uses_perm
+                        ),
+                        format!("deploy.job.{}.uses_perm", job_name),
+                    )
+                    .with_suggestion(undefined_fix(
+                        "perm",
+                        perm_name,
+                        &deploy.perms,
+                    )),
+                );
             }
         }
 
@@ -89,15 +325,22 @@ fn check_undefined_references(deploy: &DeployFile, diags: &mut Vec<Diagnostic>)
         for artifact in &job.produces {
             let artifact_name = artifact.strip_prefix("artifact.").unwrap_or(artifact);
             if !deploy.artifacts.contains_key(artifact_name) {
-                diags.push(Diagnostic {
-                    severity: "warning".into(),
-                    kind: "UndefinedArtifactReference".into(),
-                    message: format!(
-                        "Job '{}' produces undefined artifact '{}'",
-                        job_name, artifact
-                    ),
-                    location: format!("deploy.job.{}.produces", job_name),
-                });
+                diags.push(
+                    Diagnostic::new(
+                        "warning",
+                        "UndefinedArtifactReference",
+                        format!(
+                            "Job '{}' produces undefined artifact '{}'",
+                            job_name, artifact
+                        ),
+                        format!("deploy.job.{}.produces", job_name),
+                    )
+                    .with_suggestion(undefined_fix(
+                        "artifact",
+                        artifact_name,
+                        &deploy.artifacts,
+                    )),
+                );
             }
         }
     }
@@ -115,12 +358,15 @@ fn check_dag_structure(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
         if color.get(node) == Some(&Color::White) {
             if let Some(cycle) = detect_cycle(node, &graph, &mut color, &mut path) {
                 let cycle_path = cycle.join(" -> ");
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "DeployCycle".into(),
-                    message: format!("Deploy DAG contains a cycle: {} -> {}", cycle_path, cycle[0]),
-                    location: "deploy.job".into(),
-                });
+                diags.push(Diagnostic::new(
+                    "error",
+                    "DeployCycle",
+                    format!(
+                        "Deploy DAG contains a cycle: {} -> {}",
+                        cycle_path, cycle[0]
+                    ),
+                    "deploy.job",
+                ));
             }
         }
     }
@@ -185,6 +431,122 @@ fn detect_cycle(
     None
 }
 
+/// Check that job stages are declared in `[deploy.pipeline].stages` and that
+/// no job requires a job from a later stage.
+fn check_job_stages(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    let stages: &[String] = deploy
+        .pipeline
+        .as_ref()
+        .map(|p| p.stages.as_slice())
+        .unwrap_or(&[]);
+    let stage_index = |stage: &str| stages.iter().position(|s| s == stage);
+
+    for (job_name, job) in selected_jobs(deploy, only) {
+        if job.stage.is_empty() {
+            continue;
+        }
+        let Some(index) = stage_index(&job.stage) else {
+            let declared = if stages.is_empty() {
+                "no stages are declared in [deploy.pipeline]".to_string()
+            } else {
+                format!("declared stages: {}", stages.join(", "))
+            };
+            diags.push(Diagnostic::new(
+                "error",
+                "UndeclaredJobStage",
+                format!(
+                    "Job '{}' uses stage '{}' which is not declared ({})",
+                    job_name, job.stage, declared
+                ),
+                format!("deploy.job.{}.stage", job_name),
+            ));
+            continue;
+        };
+
+        for req in &job.requires {
+            let req_name = req.strip_prefix("job.").unwrap_or(req);
+            let Some(required) = deploy.jobs.get(req_name) else {
+                continue;
+            };
+            if stage_index(&required.stage).is_some_and(|req_index| req_index > index) {
+                diags.push(Diagnostic::new(
+                    "error",
+                    "RequiresLaterStage",
+                    format!(
+                        "Job '{}' (stage '{}') requires '{}' from later stage '{}'",
+                        job_name, job.stage, req_name, required.stage
+                    ),
+                    format!("deploy.job.{}.requires", job_name),
+                ));
+            }
+        }
+    }
+}
+
+/// Check for jobs without any `runs` commands.
+/// Approval steps (`when = "manual"`) are exempt: starting them is the point.
+fn check_empty_job_runs(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for (job_name, job) in selected_jobs(deploy, only) {
+        if !job.runs.iter().all(|cmd| cmd.trim().is_empty()) {
+            continue;
+        }
+        if JobCondition::parse(&job.when) == Some(JobCondition::Manual) {
+            continue;
+        }
+        diags.push(Diagnostic::new(
+            "warning",
+            "EmptyJobRuns",
+            format!("Job '{}' has no runs commands and does nothing", job_name),
+            format!("deploy.job.{}.runs", job_name),
+        ));
+    }
+}
+
+/// Check that every matrix axis lists at least one value
+fn check_job_matrix(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for (job_name, job) in selected_jobs(deploy, only) {
+        for (axis, values) in &job.matrix {
+            if values.is_empty() {
+                diags.push(Diagnostic::new(
+                    "error",
+                    "EmptyMatrixAxis",
+                    format!(
+                        "Job '{}' has matrix axis '{}' with no values",
+                        job_name, axis
+                    ),
+                    format!("deploy.job.{}.matrix.{}", job_name, axis),
+                ));
+            }
+        }
+    }
+}
+
+/// Check that `when` is one of the conditions the generators can translate
+fn check_job_conditions(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for (job_name, job) in selected_jobs(deploy, only) {
+        if job.when.is_empty() || JobCondition::parse(&job.when).is_some() {
+            continue;
+        }
+        diags.push(
+            Diagnostic::new(
+                "error",
+                "InvalidJobCondition",
+                format!(
+                    "Job '{}' has unknown condition '{}' (expected one of: {})",
+                    job_name,
+                    job.when,
+                    JobCondition::FORMS.join(", ")
+                ),
+                format!("deploy.job.{}.when", job_name),
+            )
+            .with_suggestion(
+                closest_match(&job.when, JobCondition::FORMS.iter().copied())
+                    .map(|m| format!("replace with {}", m)),
+            ),
+        );
+    }
+}
+
 /// Check for unreachable jobs (jobs with no path from entry points)
 fn check_unreachable_jobs(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
     if deploy.jobs.is_empty() {
@@ -202,12 +564,12 @@ fn check_unreachable_jobs(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
         .collect();
 
     if entry_points.is_empty() && !deploy.jobs.is_empty() {
-        diags.push(Diagnostic {
-            severity: "error".into(),
-            kind: "NoEntryPoint".into(),
-            message: "No entry point jobs found (all jobs have dependencies)".into(),
-            location: "deploy.job".into(),
-        });
+        diags.push(Diagnostic::new(
+            "error",
+            "NoEntryPoint",
+            "No entry point jobs found (all jobs have dependencies)",
+            "deploy.job",
+        ));
         return;
     }
 
@@ -232,49 +594,125 @@ fn check_unreachable_jobs(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
     // Report unreachable jobs
     for job_name in deploy.jobs.keys() {
         if !reachable.contains(job_name) {
-            diags.push(Diagnostic {
-                severity: "warning".into(),
-                kind: "UnreachableJob".into(),
-                message: format!(
+            diags.push(Diagnostic::new(
+                "warning",
+                "UnreachableJob",
+                format!(
                     "Job '{}' is unreachable (no path from any entry point)",
                     job_name
                 ),
-                location: format!("deploy.job.{}", job_name),
-            });
+                format!("deploy.job.{}", job_name),
+            ));
         }
     }
 }
 
 /// Check secret scope violations
-fn check_secret_scope(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
-    for (job_name, job) in &deploy.jobs {
+fn check_secret_scope(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for (job_name, job) in selected_jobs(deploy, only) {
         if job.uses_target.is_empty() {
             continue;
         }
 
-        let target_ref = format!("target.{}", job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target));
+        let target_name = job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target);
+        let target_ref = format!("target.{}", target_name);
+        let is_prod = deploy
+            .targets
+            .get(target_name)
+            .is_some_and(|target| target.kind == "production" || target.kind == "prod");
 
         for secret_ref in &job.needs_secrets {
             let secret_name = secret_ref.strip_prefix("secret.").unwrap_or(secret_ref);
 
             if let Some(secret) = deploy.secrets.get(secret_name) {
-                if !secret.scope.is_empty() && !secret.scope.contains(&target_ref) {
-                    diags.push(Diagnostic {
-                        severity: "error".into(),
-                        kind: "SecretScopeViolation".into(),
-                        message: format!(
+                if secret.scope.is_empty() && is_prod {
+                    diags.push(Diagnostic::new(
+                        "warning",
+                        "UnscopedSecretInProduction",
+                        format!(
+                            "Production job '{}' uses secret '{}' which has no scope; scope it to '{}'",
+                            job_name, secret_ref, target_ref
+                        ),
+                        format!("deploy.secret.{}.scope", secret_name),
+                    ));
+                } else if !secret.scope.is_empty() && !secret.scope.contains(&target_ref) {
+                    diags.push(Diagnostic::new(
+                        "error",
+                        "SecretScopeViolation",
+                        format!(
                             "Job '{}' uses secret '{}' which is not scoped for target '{}'",
                             job_name, secret_ref, job.uses_target
                         ),
-                        location: format!("deploy.job.{}.needs_secrets", job_name),
-                    });
+                        format!("deploy.job.{}.needs_secrets", job_name),
+                    ));
                 }
             }
         }
     }
 }
 
-/// Check production safety requirements
+/// Check that each job's permission is scoped for the job's target. A
+/// permission without `scope` may be used for any target.
+fn check_perm_scope(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
+    for (job_name, job) in selected_jobs(deploy, only) {
+        if job.uses_target.is_empty() || job.uses_perm.is_empty() {
+            continue;
+        }
+
+        let target_name = job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target);
+        let target_ref = format!("target.{}", target_name);
+        let perm_name = job.uses_perm.strip_prefix("perm.").unwrap_or(&job.uses_perm);
+
+        if let Some(perm) = deploy.perms.get(perm_name) {
+            if !perm.scope.is_empty() && !perm.scope.contains(&target_ref) {
+                diags.push(Diagnostic::new(
+                    "error",
+                    "PermScopeViolation",
+                    format!(
+                        "Job '{}' uses permission '{}' which is not scoped for target '{}'",
+                        job_name, job.uses_perm, job.uses_target
+                    ),
+                    format!("deploy.job.{}.uses_perm", job_name),
+                ));
+            }
+        }
+    }
+}
+
+/// Targets sharing a domain but differing in kind (e.g. staging and
+/// production on `example.com`) make an accidental prod deploy likely.
+fn check_target_domain_kinds(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
+    let mut by_domain: BTreeMap<String, Vec<(&String, &str)>> = BTreeMap::new();
+    for (name, target) in &deploy.targets {
+        let domain = target.domain.trim().trim_end_matches('.').to_lowercase();
+        if !domain.is_empty() {
+            by_domain.entry(domain).or_default().push((name, &target.kind));
+        }
+    }
+
+    for (domain, targets) in by_domain {
+        let kinds: BTreeSet<&str> = targets.iter().map(|(_, kind)| *kind).collect();
+        if kinds.len() < 2 {
+            continue;
+        }
+        let listed: Vec<String> = targets
+            .iter()
+            .map(|(name, kind)| format!("target.{} ({})", name, kind))
+            .collect();
+        diags.push(Diagnostic::new(
+            "error",
+            "ConflictingTargetKindsForDomain",
+            format!(
+                "Targets with different kinds share domain '{}': {}",
+                domain,
+                listed.join(", ")
+            ),
+            format!("deploy.target.{}.domain", targets[targets.len() - 1].0),
+        ));
+    }
+}
+
+/// Check production safety requirements: gate, rollback and health check sections
 fn check_prod_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
     // Check if gate and rollback are defined when needed
     let has_prod_jobs = deploy.jobs.iter().any(|(_, job)| {
@@ -288,22 +726,22 @@ fn check_prod_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
     if has_prod_jobs {
         // Check gate exists
         if deploy.gate.is_none() {
-            diags.push(Diagnostic {
-                severity: "error".into(),
-                kind: "MissingProdGate".into(),
-                message: "Production jobs require [deploy.gate] section".into(),
-                location: "deploy".into(),
-            });
+            diags.push(Diagnostic::new(
+                "error",
+                "MissingProdGate",
+                "Production jobs require [deploy.gate] section",
+                "deploy",
+            ));
         }
 
         // Check rollback exists
         if deploy.rollback.is_none() {
-            diags.push(Diagnostic {
-                severity: "error".into(),
-                kind: "MissingProdRollback".into(),
-                message: "Production jobs require [deploy.rollback] section".into(),
-                location: "deploy".into(),
-            });
+            diags.push(Diagnostic::new(
+                "error",
+                "MissingProdRollback",
+                "Production jobs require [deploy.rollback] section",
+                "deploy",
+            ));
         }
 
         // Check release strategy for prod
@@ -311,36 +749,38 @@ fn check_prod_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
             if (release.strategy == "canary" || release.strategy == "blue_green")
                 && release.health_check.is_empty()
             {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "MissingHealthCheck".into(),
-                    message: format!(
+                diags.push(Diagnostic::new(
+                    "error",
+                    "MissingHealthCheck",
+                    format!(
                         "Release strategy '{}' requires health_check",
                         release.strategy
                     ),
-                    location: "deploy.release".into(),
-                });
+                    "deploy.release",
+                ));
             }
         }
     }
+}
 
-    // Check each prod job has approval requirement
+/// Check each prod job's target is in the gate approval list
+fn check_prod_job_approval(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
     if let Some(gate) = &deploy.gate {
-        for (job_name, job) in &deploy.jobs {
+        for (job_name, job) in selected_jobs(deploy, only) {
             if let Some(target_name) = job.uses_target.strip_prefix("target.") {
                 if let Some(target) = deploy.targets.get(target_name) {
                     if target.kind == "production" || target.kind == "prod" {
                         let target_ref = format!("target.{}", target_name);
                         if !gate.require_manual_approval_for.contains(&target_ref) {
-                            diags.push(Diagnostic {
-                                severity: "error".into(),
-                                kind: "ProdJobWithoutApproval".into(),
-                                message: format!(
+                            diags.push(Diagnostic::new(
+                                "error",
+                                "ProdJobWithoutApproval",
+                                format!(
                                     "Production job '{}' target '{}' not in gate approval list",
                                     job_name, target_ref
                                 ),
-                                location: "deploy.gate.require_manual_approval_for".into(),
-                            });
+                                "deploy.gate.require_manual_approval_for",
+                            ));
                         }
                     }
                 }
@@ -349,26 +789,160 @@ fn check_prod_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
     }
 }
 
+/// Check each prod job's target is covered by the rollback section. A
+/// rollback without `covers` covers every target.
+fn check_prod_rollback_coverage(
+    deploy: &DeployFile,
+    only: JobFilter,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let Some(rollback) = deploy.rollback.as_ref().filter(|r| !r.covers.is_empty()) else {
+        return;
+    };
+
+    for (job_name, job) in selected_jobs(deploy, only) {
+        let Some(target_name) = job.uses_target.strip_prefix("target.") else {
+            continue;
+        };
+        let is_prod = deploy
+            .targets
+            .get(target_name)
+            .is_some_and(|target| target.kind == "production" || target.kind == "prod");
+        let target_ref = format!("target.{}", target_name);
+        if is_prod && !rollback.covers.contains(&target_ref) {
+            diags.push(
+                Diagnostic::new(
+                    "error",
+                    "ProdJobWithoutRollbackCoverage",
+                    format!(
+                        "Production job '{}' target '{}' not covered by rollback",
+                        job_name, target_ref
+                    ),
+                    "deploy.rollback.covers",
+                )
+                .with_suggestion(format!("add \"{}\" to deploy.rollback.covers", target_ref)),
+            );
+        }
+    }
+}
+
+/// Check that the rollback section uses a known strategy and a defined job
+fn check_rollback(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
+    let rollback = match &deploy.rollback {
+        Some(r) => r,
+        None => return,
+    };
+
+    if !rollback.strategy.is_empty()
+        && !KNOWN_ROLLBACK_STRATEGIES.contains(&rollback.strategy.as_str())
+    {
+        diags.push(Diagnostic::new(
+            "warning",
+            "UnknownRollbackStrategy",
+            format!(
+                "Unknown rollback strategy '{}' (expected one of: {})",
+                rollback.strategy,
+                KNOWN_ROLLBACK_STRATEGIES.join(", ")
+            ),
+            "deploy.rollback.strategy",
+        ));
+    }
+
+    if !rollback.job.is_empty() {
+        let job_name = rollback.job.strip_prefix("job.").unwrap_or(&rollback.job);
+        if !deploy.jobs.contains_key(job_name) {
+            diags.push(Diagnostic::new(
+                "error",
+                "RollbackReferencesUndefinedJob",
+                format!("Rollback references undefined job '{}'", rollback.job),
+                "deploy.rollback.job",
+            ));
+        }
+    }
+
+    for target in &rollback.covers {
+        let target_name = target.strip_prefix("target.").unwrap_or(target);
+        if !deploy.targets.contains_key(target_name) {
+            diags.push(
+                Diagnostic::new(
+                    "error",
+                    "UndefinedTargetReference",
+                    format!("Rollback covers undefined target '{}'", target),
+                    "deploy.rollback.covers",
+                )
+                .with_suggestion(undefined_fix(
+                    "target",
+                    target_name,
+                    &deploy.targets,
+                )),
+            );
+        }
+    }
+}
+
+fn check_notify(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
+    let Some(notify) = &deploy.notify else {
+        return;
+    };
+
+    if notify
+        .channels
+        .iter()
+        .all(|channel| channel.trim().is_empty())
+    {
+        diags.push(
+            Diagnostic::new(
+                "error",
+                "EmptyNotifyChannels",
+                "[deploy.notify] has no channels to notify",
+                "deploy.notify.channels",
+            )
+            .with_suggestion("add a channel, e.g. channels = [\"slack:#deploys\"]".to_string()),
+        );
+    }
+
+    for event in &notify.on {
+        if !NOTIFY_EVENTS.contains(&event.as_str()) {
+            diags.push(
+                Diagnostic::new(
+                    "warning",
+                    "UnknownNotifyEvent",
+                    format!(
+                        "Unknown notify event '{}' (expected one of: {})",
+                        event,
+                        NOTIFY_EVENTS.join(", ")
+                    ),
+                    "deploy.notify.on",
+                )
+                .with_suggestion(
+                    closest_match(event, NOTIFY_EVENTS.iter().copied())
+                        .map(|m| format!("replace with {}", m)),
+                ),
+            );
+        }
+    }
+}
+
 /// Check side effects safety
-fn check_side_effects_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
+fn check_side_effects_safety(deploy: &DeployFile, only: JobFilter, diags: &mut Vec<Diagnostic>) {
     let gate = match &deploy.gate {
         Some(g) => g,
         None => return,
     };
 
-    for (job_name, job) in &deploy.jobs {
+    for (job_name, job) in selected_jobs(deploy, only) {
         // Jobs with db_migration side effect require approval
         if job.side_effects.contains(&"db_migration".to_string()) {
             if job.uses_target.is_empty() {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "DbMigrationWithoutTarget".into(),
-                    message: format!(
+                diags.push(Diagnostic::new(
+                    "error",
+                    "DbMigrationWithoutTarget",
+                    format!(
                         "Job '{}' with db_migration side effect must specify uses_target",
                         job_name
                     ),
-                    location: format!("deploy.job.{}", job_name),
-                });
+                    format!("deploy.job.{}", job_name),
+                ));
                 continue;
             }
 
@@ -378,29 +952,29 @@ fn check_side_effects_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
             );
 
             if !gate.require_manual_approval_for.contains(&target_ref) {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "DbMigrationWithoutApproval".into(),
-                    message: format!(
+                diags.push(Diagnostic::new(
+                    "error",
+                    "DbMigrationWithoutApproval",
+                    format!(
                         "Job '{}' has db_migration side effect but target '{}' not in approval list",
                         job_name, target_ref
                     ),
-                    location: format!("deploy.job.{}.side_effects", job_name),
-                });
+                    format!("deploy.job.{}.side_effects", job_name),
+                ));
             }
         }
 
         // Jobs with release side effect should have release strategy
         if job.side_effects.contains(&"release".to_string()) && deploy.release.is_none() {
-            diags.push(Diagnostic {
-                severity: "warning".into(),
-                kind: "ReleaseWithoutStrategy".into(),
-                message: format!(
+            diags.push(Diagnostic::new(
+                "warning",
+                "ReleaseWithoutStrategy",
+                format!(
                     "Job '{}' has release side effect but no [deploy.release] defined",
                     job_name
                 ),
-                location: format!("deploy.job.{}.side_effects", job_name),
-            });
+                format!("deploy.job.{}.side_effects", job_name),
+            ));
         }
     }
 }
@@ -408,8 +982,7 @@ fn check_side_effects_safety(deploy: &DeployFile, diags: &mut Vec<Diagnostic>) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::deploy::parser::parse_deploy_file;
-    use std::io::Cursor;
+    use crate::deploy::parser::parse_deploy_str;
 
     #[test]
     fn detects_undefined_job_reference() {
@@ -422,7 +995,7 @@ runs = ["npm build"]
 requires = ["job.nonexistent"]
 runs = ["kubectl apply"]
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         assert!(diags
@@ -445,12 +1018,71 @@ runs = ["step b"]
 requires = ["job.a"]
 runs = ["step c"]
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         assert!(diags.iter().any(|d| d.kind == "DeployCycle"));
     }
 
+    #[test]
+    fn detects_job_names_that_collide_after_sanitization() {
+        let deploy_ir = r#"
+[deploy.job.deploy_prod]
+runs = ["kubectl apply"]
+
+[deploy.job.Deploy-Prod]
+requires = ["job.deploy_prod"]
+runs = ["kubectl rollout status"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let collisions: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "JobNameCollisionAfterSanitization")
+            .collect();
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].location, "deploy.job.deploy_prod");
+        assert!(collisions[0].message.contains("Deploy-Prod"));
+    }
+
+    #[test]
+    fn detects_empty_matrix_axis() {
+        let deploy_ir = r#"
+[deploy.job.deploy]
+runs = ["./deploy.sh"]
+matrix = { region = ["us", "eu"], tier = [] }
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        assert_eq!(deploy.jobs["deploy"].matrix["region"], vec!["us", "eu"]);
+
+        let diags = check_deploy_file(&deploy);
+        let empty: Vec<_> = diags.iter().filter(|d| d.kind == "EmptyMatrixAxis").collect();
+        assert_eq!(empty.len(), 1);
+        assert_eq!(empty[0].location, "deploy.job.deploy.matrix.tier");
+    }
+
+    #[test]
+    fn detects_invalid_job_condition() {
+        let deploy_ir = r#"
+[deploy.job.build]
+runs = ["make"]
+when = "on_branch(main)"
+
+[deploy.job.publish]
+requires = ["job.build"]
+runs = ["./publish.sh"]
+when = "on_tags"
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+        let invalid: Vec<_> = diags.iter().filter(|d| d.kind == "InvalidJobCondition").collect();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].location, "deploy.job.publish.when");
+        assert_eq!(invalid[0].suggestion.as_deref(), Some("replace with on_tag"));
+        assert_eq!(JobCondition::parse("on_branch()"), None);
+    }
+
     #[test]
     fn detects_unreachable_job() {
         let deploy_ir = r#"
@@ -462,7 +1094,7 @@ runs = ["npm build"]
 requires = ["job.nonexistent"]
 runs = ["orphaned"]
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         // Should have both undefined reference and unreachable warnings
@@ -487,7 +1119,7 @@ runs = ["deploy"]
 uses_target = "target.staging"
 needs_secrets = ["secret.DB_URL"]
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         assert!(diags
@@ -495,6 +1127,104 @@ needs_secrets = ["secret.DB_URL"]
             .any(|d| d.kind == "SecretScopeViolation"));
     }
 
+    #[test]
+    fn warns_about_unscoped_secrets_in_production() {
+        let deploy_ir = r#"
+[deploy.target.prod]
+kind = "production"
+
+[deploy.target.staging]
+kind = "staging"
+
+[deploy.secret.API_KEY]
+
+[deploy.job.deploy_prod]
+runs = ["deploy"]
+uses_target = "target.prod"
+needs_secrets = ["secret.API_KEY"]
+
+[deploy.job.deploy_staging]
+runs = ["deploy"]
+uses_target = "target.staging"
+needs_secrets = ["secret.API_KEY"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let unscoped: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "UnscopedSecretInProduction")
+            .collect();
+        assert_eq!(unscoped.len(), 1);
+        assert_eq!(unscoped[0].severity, "warning");
+        assert!(unscoped[0].message.contains("deploy_prod"));
+    }
+
+    #[test]
+    fn checks_a_single_job_in_isolation() {
+        let deploy_ir = r#"
+[deploy.target.staging]
+kind = "staging"
+
+[deploy.target.prod]
+kind = "production"
+
+[deploy.job.build]
+runs = []
+
+[deploy.job.deploy]
+requires = ["job.build"]
+runs = ["kubectl apply"]
+needs_secrets = ["secret.MISSING"]
+environments = ["target.staging", "target.prod"]
+
+[deploy.job.orphan]
+requires = ["job.nowhere"]
+runs = ["echo"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_job(&deploy, "deploy");
+
+        let kinds: Vec<_> = diags.iter().map(|d| d.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["UndefinedSecretReference", "UndefinedSecretReference"]);
+        assert!(diags.iter().all(|d| d.location == "deploy.job.deploy.needs_secrets"));
+        assert!(diags[0].message.ends_with("(environment 'prod' of job 'deploy')"));
+        assert!(diags[1].message.ends_with("(environment 'staging' of job 'deploy')"));
+
+        let build = check_deploy_job(&deploy, "job.build");
+        assert_eq!(build.len(), 1);
+        assert_eq!(build[0].kind, "EmptyJobRuns");
+    }
+
+    #[test]
+    fn detects_environment_instance_name_clash() {
+        let deploy_ir = r#"
+[deploy.target.prod]
+kind = "production"
+
+[deploy.job.deploy]
+runs = ["kubectl apply"]
+environments = ["target.prod"]
+
+[deploy.job.deploy_prod]
+runs = []
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let clashes: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "EnvironmentInstanceNameClash")
+            .collect();
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(clashes[0].severity, "error");
+        assert_eq!(clashes[0].location, "deploy.job.deploy.environments");
+        // The defined job keeps its name and its own diagnostics
+        let empty: Vec<_> = diags.iter().filter(|d| d.kind == "EmptyJobRuns").collect();
+        assert_eq!(empty.len(), 1);
+        assert_eq!(empty[0].location, "deploy.job.deploy_prod.runs");
+    }
+
     #[test]
     fn detects_missing_prod_gate() {
         let deploy_ir = r#"
@@ -506,7 +1236,7 @@ requires = []
 runs = ["deploy"]
 uses_target = "target.prod"
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         assert!(diags.iter().any(|d| d.kind == "MissingProdGate"));
@@ -532,7 +1262,7 @@ runs = ["migrate"]
 uses_target = "target.prod"
 side_effects = ["db_migration"]
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         assert!(diags
@@ -540,6 +1270,267 @@ side_effects = ["db_migration"]
             .any(|d| d.kind == "DbMigrationWithoutApproval"));
     }
 
+    #[test]
+    fn detects_duplicate_job_across_includes() {
+        let deploy_ir = r#"
+[deploy.job.build]
+runs = ["npm build"]
+"#;
+        let mut deploy = parse_deploy_str(deploy_ir).unwrap();
+        deploy.include_conflicts.push(crate::deploy::ast::IncludeConflict {
+            job: "build".to_string(),
+            first_file: "deploy.toml".to_string(),
+            second_file: "base.deploy.toml".to_string(),
+        });
+        let diags = check_deploy_file(&deploy);
+
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "DuplicateJobAcrossIncludes"));
+    }
+
+    #[test]
+    fn detects_rollback_issues() {
+        let deploy_ir = r#"
+[deploy.job.build]
+runs = ["npm build"]
+
+[deploy.rollback]
+on = ["deploy_fail"]
+strategy = "pray"
+job = "job.undo"
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "UnknownRollbackStrategy" && d.severity == "warning"));
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "RollbackReferencesUndefinedJob"));
+    }
+
+    #[test]
+    fn validates_notify_section() {
+        let deploy_ir = r#"
+[deploy.job.build]
+runs = ["npm build"]
+
+[deploy.notify]
+channels = []
+on = ["failure", "sucess"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "EmptyNotifyChannels" && d.severity == "error"));
+        let unknown: Vec<_> = diags.iter().filter(|d| d.kind == "UnknownNotifyEvent").collect();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("replace with success"));
+    }
+
+    #[test]
+    fn suggests_fixes_for_undefined_references() {
+        let deploy_ir = r#"
+[deploy.secret.DB_URL]
+scope = []
+
+[deploy.job.build]
+runs = ["npm build"]
+
+[deploy.job.ship]
+requires = ["job.buil", "job.publish"]
+needs_secrets = ["secret.DB_ULR"]
+runs = ["make ship"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+        let suggestion = |needle: &str| {
+            diags
+                .iter()
+                .find(|d| d.message.contains(needle))
+                .and_then(|d| d.suggestion.clone())
+        };
+
+        assert_eq!(suggestion("job.buil").as_deref(), Some("replace with job.build"));
+        assert_eq!(
+            suggestion("job.publish").as_deref(),
+            Some("define [deploy.job.publish] or correct the reference")
+        );
+        assert_eq!(suggestion("secret.DB_ULR").as_deref(), Some("replace with secret.DB_URL"));
+    }
+
+    #[test]
+    fn checks_rollback_covers_prod_targets() {
+        let deploy_ir = r#"
+[deploy.target.prod_us]
+kind = "production"
+
+[deploy.target.prod_eu]
+kind = "production"
+
+[deploy.job.ship_us]
+runs = ["make ship"]
+uses_target = "target.prod_us"
+
+[deploy.job.ship_eu]
+runs = ["make ship"]
+uses_target = "target.prod_eu"
+
+[deploy.gate]
+require_manual_approval_for = ["target.prod_us", "target.prod_eu"]
+
+[deploy.rollback]
+strategy = "revert"
+covers = ["target.prod_us", "target.staging"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let uncovered: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "ProdJobWithoutRollbackCoverage")
+            .collect();
+        assert_eq!(uncovered.len(), 1);
+        assert!(uncovered[0].message.contains("'ship_eu'"));
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "UndefinedTargetReference" && d.message.contains("target.staging")));
+
+        // Without covers, the rollback covers every target
+        let all = deploy_ir.replace("covers = [\"target.prod_us\", \"target.staging\"]", "");
+        let diags = check_deploy_file(&parse_deploy_str(&all).unwrap());
+        assert!(!diags.iter().any(|d| d.kind == "ProdJobWithoutRollbackCoverage"));
+    }
+
+    #[test]
+    fn detects_perm_scope_violation() {
+        let deploy_ir = r#"
+[deploy.target.staging]
+kind = "staging"
+
+[deploy.target.prod]
+kind = "production"
+
+[deploy.perm.deployer]
+role = "deploy-operator"
+scope = ["target.prod"]
+
+[deploy.perm.reader]
+role = "read-only"
+
+[deploy.job.deploy_staging]
+runs = ["make deploy"]
+uses_target = "target.staging"
+uses_perm = "perm.deployer"
+
+[deploy.job.smoke_staging]
+runs = ["make smoke"]
+uses_target = "target.staging"
+uses_perm = "perm.reader"
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let violations: Vec<_> = diags.iter().filter(|d| d.kind == "PermScopeViolation").collect();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location, "deploy.job.deploy_staging.uses_perm");
+    }
+
+    #[test]
+    fn detects_targets_with_different_kinds_on_one_domain() {
+        let deploy_ir = r#"
+[deploy.target.staging]
+kind = "staging"
+domain = "example.com"
+
+[deploy.target.prod]
+kind = "production"
+domain = "Example.com"
+
+[deploy.target.prod_eu]
+kind = "production"
+domain = "eu.example.com"
+
+[deploy.target.prod_eu_backup]
+kind = "production"
+domain = "eu.example.com"
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let conflicts: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "ConflictingTargetKindsForDomain")
+            .collect();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].severity, "error");
+        assert!(conflicts[0].message.contains("'example.com'"));
+        assert!(conflicts[0].message.contains("target.prod (production)"));
+        assert!(conflicts[0].message.contains("target.staging (staging)"));
+    }
+
+    #[test]
+    fn detects_empty_job_runs() {
+        let deploy_ir = r#"
+[deploy.job.build]
+runs = []
+
+[deploy.job.aggregate]
+requires = ["job.build"]
+runs = []
+
+[deploy.job.signoff]
+requires = ["job.aggregate"]
+runs = []
+when = "manual"
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        let mut empty: Vec<_> = diags
+            .iter()
+            .filter(|d| d.kind == "EmptyJobRuns")
+            .map(|d| d.location.as_str())
+            .collect();
+        empty.sort();
+        assert_eq!(empty, ["deploy.job.aggregate.runs", "deploy.job.build.runs"]);
+    }
+
+    #[test]
+    fn validates_job_stages() {
+        let deploy_ir = r#"
+[deploy.pipeline]
+name = "webapp"
+stages = ["build", "deploy"]
+
+[deploy.job.compile]
+stage = "build"
+requires = ["job.ship"]
+runs = ["make"]
+
+[deploy.job.ship]
+stage = "deploy"
+runs = ["make ship"]
+
+[deploy.job.audit]
+stage = "verify"
+runs = ["make audit"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let diags = check_deploy_file(&deploy);
+
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "UndeclaredJobStage" && d.message.contains("'audit'")));
+        assert!(diags
+            .iter()
+            .any(|d| d.kind == "RequiresLaterStage" && d.message.contains("'compile'")));
+    }
+
     #[test]
     fn valid_deploy_passes() {
         let deploy_ir = r#"
@@ -574,7 +1565,7 @@ health_check = "https://example.com/health"
 on = ["deploy_fail"]
 strategy = "revert"
 "#;
-        let deploy = parse_deploy_file(Cursor::new(deploy_ir)).unwrap();
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
         let diags = check_deploy_file(&deploy);
 
         let errors: Vec<_> = diags.iter().filter(|d| d.severity == "error").collect();
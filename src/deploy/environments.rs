@@ -0,0 +1,225 @@
+use crate::deploy::ast::DeployFile;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// One job generated from a job's `environments`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentInstance {
+    /// Generated job name, `<job>_<env>`
+    pub name: String,
+    /// The job that declares `environments`
+    pub job: String,
+    /// Environment without the `target.` prefix
+    pub environment: String,
+}
+
+/// A generated instance whose name is already taken, by a job defined in the
+/// file (`other_instance` is `None`) or by an earlier instance.
+#[derive(Debug, Clone)]
+pub struct InstanceNameClash {
+    pub instance: EnvironmentInstance,
+    pub other_instance: Option<EnvironmentInstance>,
+}
+
+/// Every instance `expand_environments` would generate, in job order.
+pub fn environment_instances(deploy: &DeployFile) -> Vec<EnvironmentInstance> {
+    deploy
+        .jobs
+        .iter()
+        .flat_map(|(name, job)| {
+            job.environments.iter().map(move |env| {
+                let env = env.strip_prefix("target.").unwrap_or(env);
+                EnvironmentInstance {
+                    name: format!("{}_{}", name, env),
+                    job: name.clone(),
+                    environment: env.to_string(),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Instances that `expand_environments` drops because their name is taken.
+/// A job defined in the file always keeps its name; between two instances
+/// the first in job order wins.
+pub fn instance_name_clashes(deploy: &DeployFile) -> Vec<InstanceNameClash> {
+    let defined: BTreeSet<&String> = deploy
+        .jobs
+        .iter()
+        .filter(|(_, job)| job.environments.is_empty())
+        .map(|(name, _)| name)
+        .collect();
+    let mut generated: BTreeMap<String, EnvironmentInstance> = BTreeMap::new();
+    let mut clashes = Vec::new();
+    for instance in environment_instances(deploy) {
+        if defined.contains(&instance.name) {
+            clashes.push(InstanceNameClash {
+                instance,
+                other_instance: None,
+            });
+        } else if let Some(first) = generated.get(&instance.name) {
+            clashes.push(InstanceNameClash {
+                other_instance: Some(first.clone()),
+                instance,
+            });
+        } else {
+            generated.insert(instance.name.clone(), instance);
+        }
+    }
+    clashes
+}
+
+/// Expand jobs that declare `environments` into one job per target.
+///
+/// A job `deploy` with `environments = ["target.staging", "target.prod"]`
+/// becomes `deploy_staging` and `deploy_prod`, each using its own target.
+/// Requirements on an expanded job are rewritten to the instance for the same
+/// environment when there is one, or to every instance otherwise.
+///
+/// An instance whose name is already taken is left out; see
+/// `instance_name_clashes`.
+pub fn expand_environments(deploy: &DeployFile) -> DeployFile {
+    let all = environment_instances(deploy);
+    // job name -> [(environment, instance name)]
+    let mut instances: BTreeMap<&str, Vec<(&str, String)>> = BTreeMap::new();
+    for instance in &all {
+        instances
+            .entry(instance.job.as_str())
+            .or_default()
+            .push((instance.environment.as_str(), instance.name.clone()));
+    }
+
+    if instances.is_empty() {
+        return deploy.clone();
+    }
+
+    let dropped: Vec<EnvironmentInstance> = instance_name_clashes(deploy)
+        .into_iter()
+        .map(|clash| clash.instance)
+        .collect();
+
+    let mut expanded = deploy.clone();
+    expanded.jobs = BTreeMap::new();
+
+    for (name, job) in &deploy.jobs {
+        match instances.get(name.as_str()) {
+            Some(envs) => {
+                for (env, instance_name) in envs {
+                    let is_dropped = dropped
+                        .iter()
+                        .any(|d| d.job == *name && d.environment == *env);
+                    if is_dropped {
+                        continue;
+                    }
+                    let mut instance = job.clone();
+                    instance.name = instance_name.clone();
+                    instance.uses_target = format!("target.{}", env);
+                    instance.environments = Vec::new();
+                    instance.requires = rewrite_requires(&job.requires, &instances, Some(env));
+                    expanded.jobs.insert(instance_name.clone(), instance);
+                }
+            }
+            None => {
+                let mut plain = job.clone();
+                plain.requires = rewrite_requires(&job.requires, &instances, None);
+                expanded.jobs.insert(name.clone(), plain);
+            }
+        }
+    }
+
+    expanded
+}
+
+fn rewrite_requires(
+    requires: &[String],
+    instances: &BTreeMap<&str, Vec<(&str, String)>>,
+    env: Option<&str>,
+) -> Vec<String> {
+    let mut result = Vec::new();
+    for req in requires {
+        let req_name = req.strip_prefix("job.").unwrap_or(req);
+        let Some(envs) = instances.get(req_name) else {
+            result.push(req.clone());
+            continue;
+        };
+        let same_env: Vec<_> = envs
+            .iter()
+            .filter(|(e, _)| Some(*e) == env)
+            .collect();
+        let targets = if same_env.is_empty() {
+            envs.iter().collect()
+        } else {
+            same_env
+        };
+        result.extend(targets.into_iter().map(|(_, inst)| format!("job.{}", inst)));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deploy::parser::parse_deploy_str;
+
+    #[test]
+    fn expands_jobs_per_environment() {
+        let deploy_ir = r#"
+[deploy.target.staging]
+kind = "staging"
+
+[deploy.target.prod]
+kind = "production"
+
+[deploy.job.build]
+runs = ["npm run build"]
+
+[deploy.job.deploy]
+requires = ["job.build"]
+runs = ["kubectl apply"]
+environments = ["target.staging", "target.prod"]
+
+[deploy.job.smoke]
+requires = ["job.deploy"]
+runs = ["curl health"]
+environments = ["target.staging", "target.prod"]
+
+[deploy.job.notify]
+requires = ["job.deploy"]
+runs = ["notify"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+        let expanded = expand_environments(&deploy);
+
+        assert!(!expanded.jobs.contains_key("deploy"));
+        assert_eq!(expanded.jobs["deploy_prod"].uses_target, "target.prod");
+        assert_eq!(expanded.jobs["deploy_staging"].requires, vec!["job.build"]);
+        assert_eq!(expanded.jobs["smoke_prod"].requires, vec!["job.deploy_prod"]);
+        assert_eq!(
+            expanded.jobs["notify"].requires,
+            vec!["job.deploy_staging", "job.deploy_prod"]
+        );
+        assert!(expanded.jobs.values().all(|job| job.environments.is_empty()));
+    }
+
+    #[test]
+    fn keeps_defined_jobs_when_an_instance_name_clashes() {
+        let deploy_ir = r#"
+[deploy.job.deploy]
+runs = ["kubectl apply"]
+environments = ["target.staging", "target.prod"]
+
+[deploy.job.deploy_prod]
+runs = ["./legacy-prod-deploy.sh"]
+"#;
+        let deploy = parse_deploy_str(deploy_ir).unwrap();
+
+        let clashes = instance_name_clashes(&deploy);
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(clashes[0].instance.job, "deploy");
+        assert_eq!(clashes[0].instance.environment, "prod");
+        assert!(clashes[0].other_instance.is_none());
+
+        let expanded = expand_environments(&deploy);
+        assert_eq!(expanded.jobs["deploy_prod"].runs, vec!["./legacy-prod-deploy.sh"]);
+        assert_eq!(expanded.jobs["deploy_staging"].uses_target, "target.staging");
+    }
+}
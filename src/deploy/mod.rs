@@ -1,7 +1,9 @@
 pub mod ast;
 pub mod checker;
+pub mod environments;
 pub mod parser;
 
 pub use ast::*;
-pub use checker::check_deploy_file;
-pub use parser::parse_deploy_file;
+pub use checker::{check_deploy_file, check_deploy_job};
+pub use environments::expand_environments;
+pub use parser::{parse_deploy_file, parse_deploy_file_from_path, parse_deploy_str};
@@ -1,8 +1,9 @@
 use crate::deploy::ast::*;
 use crate::simple_toml::{parse_toml, TomlTable, TomlValue};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{self, BufReader, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum ParseError {
@@ -30,7 +31,11 @@ impl std::error::Error for ParseError {}
 pub fn parse_deploy_file<R: Read>(reader: R) -> Result<DeployFile, ParseError> {
     let raw = parse_toml(BufReader::new(reader))?;
 
-    let mut deploy = DeployFile::default();
+    // Parse top-level include = [...]
+    let mut deploy = DeployFile {
+        includes: get_string_array(&raw, "include"),
+        ..Default::default()
+    };
 
     // Parse [deploy.pipeline]
     if let Some(pipeline_table) = get_nested_table(&raw, "deploy", "pipeline") {
@@ -111,12 +116,136 @@ pub fn parse_deploy_file<R: Read>(reader: R) -> Result<DeployFile, ParseError> {
         deploy.rollback = Some(parse_rollback(rollback_table));
     }
 
+    // Parse [deploy.notify]
+    if let Some(notify_table) = get_nested_table(&raw, "deploy", "notify") {
+        deploy.notify = Some(parse_notify(notify_table));
+    }
+
     Ok(deploy)
 }
 
+/// Parse a deploy IR document held in memory.
+pub fn parse_deploy_str(input: &str) -> Result<DeployFile, ParseError> {
+    parse_deploy_file(input.as_bytes())
+}
+
+/// Parse a deploy file and merge any `include`d fragments into it.
+///
+/// Includes are resolved relative to the including file's directory. Targets,
+/// secrets, artifacts and permissions defined locally take precedence over
+/// included ones; jobs defined twice are recorded in `include_conflicts`.
 pub fn parse_deploy_file_from_path(path: &Path) -> Result<DeployFile, ParseError> {
+    let mut stack = Vec::new();
+    parse_with_includes(path, &mut stack).map(|(deploy, _)| deploy)
+}
+
+/// Where a merged job was defined: canonical path for identity, and the path
+/// as written for messages.
+#[derive(Clone)]
+struct JobOrigin {
+    canonical: PathBuf,
+    display: String,
+}
+
+type JobOrigins = BTreeMap<String, JobOrigin>;
+
+fn parse_with_includes(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(DeployFile, JobOrigins), ParseError> {
     let file = File::open(path)?;
-    parse_deploy_file(file)
+    let mut deploy = parse_deploy_file(file)?;
+
+    let canonical = path.canonicalize()?;
+    let origin = JobOrigin {
+        canonical: canonical.clone(),
+        display: path.display().to_string(),
+    };
+    let mut job_origins: JobOrigins =
+        deploy.jobs.keys().map(|name| (name.clone(), origin.clone())).collect();
+    if deploy.includes.is_empty() {
+        return Ok((deploy, job_origins));
+    }
+
+    if stack.contains(&canonical) {
+        return Err(ParseError::InvalidFormat(format!(
+            "Include cycle detected at '{}'",
+            path.display()
+        )));
+    }
+    stack.push(canonical);
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for include in deploy.includes.clone() {
+        let include_path = base_dir.join(&include);
+        let (fragment, fragment_origins) = parse_with_includes(&include_path, stack)?;
+        merge_fragment(&mut deploy, fragment, fragment_origins, &mut job_origins);
+    }
+
+    stack.pop();
+    Ok((deploy, job_origins))
+}
+
+fn merge_fragment(
+    deploy: &mut DeployFile,
+    fragment: DeployFile,
+    fragment_origins: JobOrigins,
+    job_origins: &mut JobOrigins,
+) {
+    // A file reached through two include paths reports its conflicts once
+    for conflict in fragment.include_conflicts {
+        if !deploy.include_conflicts.contains(&conflict) {
+            deploy.include_conflicts.push(conflict);
+        }
+    }
+
+    for (name, job) in fragment.jobs {
+        let Some(origin) = fragment_origins.get(&name) else {
+            continue;
+        };
+        match job_origins.get(&name) {
+            // The same file included twice (a diamond) is not a conflict
+            Some(first) if first.canonical == origin.canonical => {}
+            Some(first) => deploy.include_conflicts.push(IncludeConflict {
+                job: name,
+                first_file: first.display.clone(),
+                second_file: origin.display.clone(),
+            }),
+            None => {
+                job_origins.insert(name.clone(), origin.clone());
+                deploy.jobs.insert(name, job);
+            }
+        }
+    }
+
+    for (name, target) in fragment.targets {
+        deploy.targets.entry(name).or_insert(target);
+    }
+    for (name, artifact) in fragment.artifacts {
+        deploy.artifacts.entry(name).or_insert(artifact);
+    }
+    for (name, secret) in fragment.secrets {
+        deploy.secrets.entry(name).or_insert(secret);
+    }
+    for (name, perm) in fragment.perms {
+        deploy.perms.entry(name).or_insert(perm);
+    }
+
+    if deploy.pipeline.is_none() {
+        deploy.pipeline = fragment.pipeline;
+    }
+    if deploy.release.is_none() {
+        deploy.release = fragment.release;
+    }
+    if deploy.gate.is_none() {
+        deploy.gate = fragment.gate;
+    }
+    if deploy.rollback.is_none() {
+        deploy.rollback = fragment.rollback;
+    }
+    if deploy.notify.is_none() {
+        deploy.notify = fragment.notify;
+    }
 }
 
 fn get_nested_table<'a>(root: &'a TomlTable, key1: &str, key2: &str) -> Option<&'a TomlTable> {
@@ -149,6 +278,7 @@ fn parse_pipeline(table: &TomlTable) -> Pipeline {
     Pipeline {
         name: get_string(table, "name"),
         description: get_string(table, "description"),
+        stages: get_string_array(table, "stages"),
     }
 }
 
@@ -170,9 +300,25 @@ fn parse_job(name: &str, table: &TomlTable) -> Job {
         uses_perm: get_string(table, "uses_perm"),
         produces: get_string_array(table, "produces"),
         side_effects: get_string_array(table, "side_effects"),
+        environments: get_string_array(table, "environments"),
+        stage: get_string(table, "stage"),
+        matrix: parse_matrix(table),
+        when: get_string(table, "when"),
     }
 }
 
+/// `matrix` as an inline table or `[deploy.job.x.matrix]` sub-table of string
+/// arrays. Empty axes are kept so the checker can report them.
+fn parse_matrix(table: &TomlTable) -> BTreeMap<String, Vec<String>> {
+    let Some(matrix) = table.get("matrix").and_then(|v| v.as_table()) else {
+        return BTreeMap::new();
+    };
+    matrix
+        .keys()
+        .map(|axis| (axis.clone(), get_string_array(matrix, axis)))
+        .collect()
+}
+
 fn parse_artifact(name: &str, table: &TomlTable) -> Artifact {
     Artifact {
         name: name.to_string(),
@@ -194,6 +340,7 @@ fn parse_permission(name: &str, table: &TomlTable) -> Permission {
         name: name.to_string(),
         role: get_string(table, "role"),
         allows: get_string_array(table, "allows"),
+        scope: get_string_array(table, "scope"),
     }
 }
 
@@ -214,13 +361,21 @@ fn parse_rollback(table: &TomlTable) -> Rollback {
     Rollback {
         on: get_string_array(table, "on"),
         strategy: get_string(table, "strategy"),
+        job: get_string(table, "job"),
+        covers: get_string_array(table, "covers"),
+    }
+}
+
+fn parse_notify(table: &TomlTable) -> Notify {
+    Notify {
+        channels: get_string_array(table, "channels"),
+        on: get_string_array(table, "on"),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
 
     const SAMPLE_DEPLOY: &str = r#"
 [deploy.pipeline]
@@ -261,11 +416,15 @@ require_manual_approval_for = ["target.prod"]
 [deploy.rollback]
 on = ["health_fail", "deploy_fail"]
 strategy = "revert_traffic"
+
+[deploy.notify]
+channels = ["slack:#deploys"]
+on = ["failure"]
 "#;
 
     #[test]
     fn parses_sample_deploy() {
-        let deploy = parse_deploy_file(Cursor::new(SAMPLE_DEPLOY)).expect("parse");
+        let deploy = parse_deploy_str(SAMPLE_DEPLOY).expect("parse");
 
         assert!(deploy.pipeline.is_some());
         assert_eq!(deploy.pipeline.unwrap().name, "webapp");
@@ -285,5 +444,82 @@ strategy = "revert_traffic"
         assert!(deploy.release.is_some());
         assert!(deploy.gate.is_some());
         assert!(deploy.rollback.is_some());
+
+        let notify = deploy.notify.expect("notify");
+        assert_eq!(notify.channels, vec!["slack:#deploys"]);
+        assert_eq!(notify.on, vec!["failure"]);
+    }
+
+    #[test]
+    fn merges_included_fragments() {
+        let dir = std::env::temp_dir().join(format!("surv-deploy-include-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("shared")).unwrap();
+        std::fs::write(
+            dir.join("shared/base.deploy.toml"),
+            r#"
+[deploy.target.prod]
+kind = "production"
+
+[deploy.secret.DB_URL]
+scope = ["target.prod"]
+
+[deploy.job.build]
+runs = ["npm run build"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("app.deploy.toml"),
+            r#"
+include = ["shared/base.deploy.toml"]
+
+[deploy.job.build]
+runs = ["cargo build"]
+
+[deploy.job.deploy]
+requires = ["job.build"]
+uses_target = "target.prod"
+needs_secrets = ["secret.DB_URL"]
+"#,
+        )
+        .unwrap();
+
+        let deploy = parse_deploy_file_from_path(&dir.join("app.deploy.toml")).expect("parse");
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(deploy.includes, vec!["shared/base.deploy.toml"]);
+        assert!(deploy.targets.contains_key("prod"));
+        assert!(deploy.secrets.contains_key("DB_URL"));
+        assert_eq!(deploy.jobs["build"].runs, vec!["cargo build"]);
+        assert_eq!(deploy.include_conflicts.len(), 1);
+        assert_eq!(deploy.include_conflicts[0].job, "build");
+    }
+
+    #[test]
+    fn diamond_include_is_not_a_conflict() {
+        let dir = std::env::temp_dir().join(format!("surv-deploy-diamond-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [
+            ("base.deploy.toml", "[deploy.job.build]\nruns = [\"make\"]\n"),
+            (
+                "web.deploy.toml",
+                "include = [\"base.deploy.toml\"]\n\n[deploy.job.web]\nruns = [\"./web.sh\"]\n",
+            ),
+            (
+                "api.deploy.toml",
+                "include = [\"base.deploy.toml\"]\n\n[deploy.job.api]\nruns = [\"./api.sh\"]\n",
+            ),
+            ("app.deploy.toml", "include = [\"web.deploy.toml\", \"api.deploy.toml\"]\n"),
+        ];
+        for (name, content) in files {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+
+        let deploy = parse_deploy_file_from_path(&dir.join("app.deploy.toml")).expect("parse");
+        std::fs::remove_dir_all(&dir).ok();
+
+        let jobs: Vec<&String> = deploy.jobs.keys().collect();
+        assert_eq!(jobs, ["api", "build", "web"]);
+        assert!(deploy.include_conflicts.is_empty());
     }
 }
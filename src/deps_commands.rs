@@ -1,5 +1,5 @@
-use survibe_parser_rs::{load_project, Manifest, ProjectAST, Section};
-use std::collections::HashMap;
+use survibe_parser_rs::{load_project, Manifest, NormalizedRequire, ProjectAST, Section};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::fs;
 use std::path::Path;
@@ -9,16 +9,28 @@ enum Scope {
     Package(String),
     Module(String),
     CrossPackage,
+    Unused,
+    ByOwner,
 }
 
 struct DepsOptions {
     scope: Scope,
     format: OutputFormat,
+    // Max package dependency depth to expand; None shows direct deps only
+    depth: Option<usize>,
+    group_by: GroupBy,
+}
+
+/// How `--cross-package` text output aggregates module edges.
+enum GroupBy {
+    None,
+    Package,
 }
 
 enum OutputFormat {
     Text,
     Mermaid,
+    Json,
 }
 
 pub fn run_deps(args: &[String]) -> Result<(), Box<dyn Error>> {
@@ -40,10 +52,14 @@ pub fn run_deps(args: &[String]) -> Result<(), Box<dyn Error>> {
     // Execute based on scope
     match options.format {
         OutputFormat::Text => match options.scope {
-            Scope::Packages => show_package_deps(&manifest, &project),
+            Scope::Packages => show_package_deps(&manifest, &project, options.depth),
             Scope::Package(ref name) => show_package_modules(&manifest, &project, name)?,
             Scope::Module(ref name) => show_module_deps(&manifest, &project, name)?,
-            Scope::CrossPackage => show_cross_package_deps(&manifest, &project)?,
+            Scope::CrossPackage => {
+                show_cross_package_deps(&manifest, &project, &options.group_by)?
+            }
+            Scope::Unused => show_unused_packages(&manifest, &project)?,
+            Scope::ByOwner => show_modules_by_owner(&manifest, &project)?,
         },
         OutputFormat::Mermaid => match options.scope {
             Scope::Packages => export_package_deps_mermaid(&manifest),
@@ -53,6 +69,14 @@ pub fn run_deps(args: &[String]) -> Result<(), Box<dyn Error>> {
                 std::process::exit(1);
             }
         },
+        OutputFormat::Json => {
+            let json = match options.scope {
+                Scope::Unused => build_unused_json(&manifest, &project)?,
+                Scope::ByOwner => build_owner_json(&manifest, &project)?,
+                _ => build_deps_json(&manifest, &project, &options.scope, options.depth)?,
+            };
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
     }
 
     Ok(())
@@ -61,6 +85,8 @@ pub fn run_deps(args: &[String]) -> Result<(), Box<dyn Error>> {
 fn parse_options(args: &[String]) -> Result<DepsOptions, Box<dyn Error>> {
     let mut scope = Scope::Packages;
     let mut format = OutputFormat::Text;
+    let mut depth = None;
+    let mut group_by = GroupBy::None;
 
     let mut i = 0;
     while i < args.len() {
@@ -86,16 +112,56 @@ fn parse_options(args: &[String]) -> Result<DepsOptions, Box<dyn Error>> {
                 scope = Scope::CrossPackage;
                 i += 1;
             }
+            "--packages" => {
+                scope = Scope::Packages;
+                i += 1;
+            }
+            "--unused" => {
+                scope = Scope::Unused;
+                i += 1;
+            }
+            "--by-owner" => {
+                scope = Scope::ByOwner;
+                i += 1;
+            }
+            "--transitive" => {
+                depth = Some(usize::MAX);
+                i += 1;
+            }
+            "--depth" => {
+                if i + 1 < args.len() {
+                    let n: usize = args[i + 1]
+                        .parse()
+                        .map_err(|_| format!("Invalid depth: {}", args[i + 1]))?;
+                    depth = Some(n);
+                    i += 2;
+                } else {
+                    return Err("--depth requires a number".into());
+                }
+            }
+            "--group-by" => {
+                if i + 1 < args.len() {
+                    group_by = match args[i + 1].as_str() {
+                        "none" => GroupBy::None,
+                        "package" => GroupBy::Package,
+                        other => return Err(format!("Unknown grouping: {}", other).into()),
+                    };
+                    i += 2;
+                } else {
+                    return Err("--group-by requires a value (package, none)".into());
+                }
+            }
             "--format" => {
                 if i + 1 < args.len() {
                     format = match args[i + 1].as_str() {
                         "text" => OutputFormat::Text,
                         "mermaid" => OutputFormat::Mermaid,
+                        "json" => OutputFormat::Json,
                         other => return Err(format!("Unknown format: {}", other).into()),
                     };
                     i += 2;
                 } else {
-                    return Err("--format requires a value (text, mermaid)".into());
+                    return Err("--format requires a value (text, mermaid, json)".into());
                 }
             }
             other => {
@@ -104,10 +170,15 @@ fn parse_options(args: &[String]) -> Result<DepsOptions, Box<dyn Error>> {
         }
     }
 
-    Ok(DepsOptions { scope, format })
+    Ok(DepsOptions {
+        scope,
+        format,
+        depth,
+        group_by,
+    })
 }
 
-fn show_package_deps(manifest: &Manifest, _project: &ProjectAST) {
+fn show_package_deps(manifest: &Manifest, _project: &ProjectAST, depth: Option<usize>) {
     println!("Packages:");
     println!();
 
@@ -127,10 +198,54 @@ fn show_package_deps(manifest: &Manifest, _project: &ProjectAST) {
                 println!("      └─> {}", dep);
             }
         }
+
+        if let Some(max_depth) = depth {
+            let indirect: Vec<_> = transitive_package_deps(manifest, pkg_name, max_depth)
+                .into_iter()
+                .filter(|(_, d)| *d > 1)
+                .collect();
+            if !indirect.is_empty() {
+                println!("    indirect:");
+                for (dep, d) in indirect {
+                    println!("      └┈> {} (depth {})", dep, d);
+                }
+            }
+        }
         println!();
     }
 }
 
+/// Breadth-first walk of manifest `depends`, returning each reachable package
+/// with the depth at which it is first reached (direct deps are depth 1)
+fn transitive_package_deps(
+    manifest: &Manifest,
+    root: &str,
+    max_depth: usize,
+) -> Vec<(String, usize)> {
+    let mut result = Vec::new();
+    let mut visited = HashSet::new();
+    visited.insert(root.to_string());
+    let mut queue = VecDeque::new();
+    queue.push_back((root.to_string(), 0));
+
+    while let Some((name, d)) = queue.pop_front() {
+        if d >= max_depth {
+            continue;
+        }
+        let Some(pkg) = manifest.packages.get(&name) else {
+            continue;
+        };
+        for dep in &pkg.depends {
+            if visited.insert(dep.clone()) {
+                result.push((dep.clone(), d + 1));
+                queue.push_back((dep.clone(), d + 1));
+            }
+        }
+    }
+
+    result
+}
+
 fn show_package_modules(
     manifest: &Manifest,
     project: &ProjectAST,
@@ -288,6 +403,7 @@ fn show_module_deps(
 fn show_cross_package_deps(
     manifest: &Manifest,
     project: &ProjectAST,
+    group_by: &GroupBy,
 ) -> Result<(), Box<dyn Error>> {
     println!("Cross-package dependencies:");
     println!();
@@ -298,36 +414,266 @@ fn show_cross_package_deps(
     // Get all dependencies
     let normalized_reqs = project.collect_normalized_requires();
 
-    let mut cross_package_edges = Vec::new();
+    let cross_package_edges = collect_cross_package_edges(&module_to_package, &normalized_reqs);
 
-    for req in &normalized_reqs {
-        let from_pkg = module_to_package.get(&req.from_mod);
-        let to_pkg = module_to_package.get(&req.to_mod);
+    if cross_package_edges.is_empty() {
+        println!("  No cross-package dependencies found");
+        return Ok(());
+    }
 
-        if from_pkg != to_pkg && from_pkg.is_some() && to_pkg.is_some() {
-            cross_package_edges.push((
-                from_pkg.unwrap(),
-                &req.from_mod,
-                to_pkg.unwrap(),
-                &req.to_mod,
-            ));
+    match group_by {
+        GroupBy::None => {
+            for (from_pkg, from_mod, to_pkg, to_mod) in cross_package_edges {
+                println!("  {}.{} → {}.{}", from_pkg, from_mod, to_pkg, to_mod);
+            }
+        }
+        GroupBy::Package => {
+            for ((from_pkg, to_pkg), count) in group_edges_by_package(&cross_package_edges) {
+                let noun = if count == 1 { "edge" } else { "edges" };
+                println!("  {} → {} ({} module {})", from_pkg, to_pkg, count, noun);
+            }
         }
     }
 
-    if cross_package_edges.is_empty() {
-        println!("  No cross-package dependencies found");
+    Ok(())
+}
+
+/// Aggregate module edges into package → package edges with the number of
+/// module edges behind each.
+fn group_edges_by_package<'a>(
+    edges: &[(&'a String, &'a String, &'a String, &'a String)],
+) -> BTreeMap<(&'a String, &'a String), usize> {
+    let mut grouped = BTreeMap::new();
+    for (from_pkg, _, to_pkg, _) in edges {
+        *grouped.entry((*from_pkg, *to_pkg)).or_insert(0) += 1;
+    }
+    grouped
+}
+
+/// A package no other package uses, either through manifest `depends` or
+/// through a module `requires`.
+struct UnusedPackage<'a> {
+    name: &'a String,
+    // Modules with a pipeline that no module requires; a package that still
+    // has some is a top-level leaf rather than an orphan
+    entry_modules: Vec<String>,
+}
+
+fn find_unused_packages<'a>(
+    manifest: &'a Manifest,
+    project: &ProjectAST,
+) -> Result<Vec<UnusedPackage<'a>>, Box<dyn Error>> {
+    let module_to_package = build_module_to_package_map(manifest, project)?;
+    let normalized_reqs = project.collect_normalized_requires();
+
+    let mut used: HashSet<&String> = manifest
+        .packages
+        .iter()
+        .flat_map(|(name, pkg)| pkg.depends.iter().filter(move |dep| *dep != name))
+        .collect();
+    for (_, _, to_pkg, _) in collect_cross_package_edges(&module_to_package, &normalized_reqs) {
+        used.insert(to_pkg);
+    }
+    let required: HashSet<&String> = normalized_reqs.iter().map(|req| &req.to_mod).collect();
+
+    let mut unused: Vec<UnusedPackage> = manifest
+        .packages
+        .keys()
+        .filter(|name| !used.contains(name))
+        .map(|name| {
+            let mut entry_modules: Vec<String> = project
+                .files
+                .iter()
+                .filter(|(_, file)| file.package.as_ref() == Some(name))
+                .flat_map(|(_, file)| &file.sections)
+                .filter_map(|section| match section {
+                    Section::Mod(m) if !m.pipeline.is_empty() => Some(format!("mod.{}", m.name)),
+                    _ => None,
+                })
+                .filter(|mod_id| !required.contains(mod_id))
+                .collect();
+            entry_modules.sort();
+            UnusedPackage {
+                name,
+                entry_modules,
+            }
+        })
+        .collect();
+    unused.sort_by_key(|pkg| pkg.name);
+
+    Ok(unused)
+}
+
+fn show_unused_packages(manifest: &Manifest, project: &ProjectAST) -> Result<(), Box<dyn Error>> {
+    println!("Packages without dependents:");
+    println!();
+
+    let unused = find_unused_packages(manifest, project)?;
+    if unused.is_empty() {
+        println!("  Every package is used by another package");
         return Ok(());
     }
 
-    cross_package_edges.sort();
+    let (leaves, orphans): (Vec<_>, Vec<_>) =
+        unused.iter().partition(|pkg| !pkg.entry_modules.is_empty());
+    if !orphans.is_empty() {
+        println!("  Orphans (no dependents, no entry modules) — likely dead:");
+        for pkg in &orphans {
+            println!("    {}", pkg.name);
+        }
+    }
+    if !leaves.is_empty() {
+        if !orphans.is_empty() {
+            println!();
+        }
+        println!("  Leaves (no dependents, used directly through entry modules):");
+        for pkg in &leaves {
+            println!("    {} ({})", pkg.name, pkg.entry_modules.join(", "));
+        }
+    }
 
-    for (from_pkg, from_mod, to_pkg, to_mod) in cross_package_edges {
-        println!("  {}.{} → {}.{}", from_pkg, from_mod, to_pkg, to_mod);
+    Ok(())
+}
+
+fn build_unused_json(
+    manifest: &Manifest,
+    project: &ProjectAST,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    let packages: Vec<serde_json::Value> = find_unused_packages(manifest, project)?
+        .into_iter()
+        .map(|pkg| {
+            let status = if pkg.entry_modules.is_empty() { "orphan" } else { "leaf" };
+            serde_json::json!({
+                "name": pkg.name,
+                "status": status,
+                "entry_modules": pkg.entry_modules,
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({ "unused_packages": packages }))
+}
+
+/// A module as listed in the `--by-owner` report.
+struct OwnedModule {
+    id: String,
+    package: Option<String>,
+    // Modules in other packages this one requires, as `package.mod.x`
+    cross_package_deps: Vec<String>,
+}
+
+struct OwnerReport {
+    owners: BTreeMap<String, Vec<OwnedModule>>,
+    unowned: Vec<OwnedModule>,
+}
+
+/// Group modules by their `owners`; a module with several owners is listed
+/// under each of them.
+fn group_modules_by_owner(
+    manifest: &Manifest,
+    project: &ProjectAST,
+) -> Result<OwnerReport, Box<dyn Error>> {
+    let module_to_package = build_module_to_package_map(manifest, project)?;
+    let normalized_reqs = project.collect_normalized_requires();
+    let edges = collect_cross_package_edges(&module_to_package, &normalized_reqs);
+
+    let mut report = OwnerReport {
+        owners: BTreeMap::new(),
+        unowned: Vec::new(),
+    };
+    for section in project.files.iter().flat_map(|(_, file)| &file.sections) {
+        let Section::Mod(module) = section else {
+            continue;
+        };
+        let id = format!("mod.{}", module.name);
+        let owned = || OwnedModule {
+            package: module_to_package.get(&id).cloned(),
+            cross_package_deps: edges
+                .iter()
+                .filter(|(_, from_mod, _, _)| **from_mod == id)
+                .map(|(_, _, to_pkg, to_mod)| format!("{}.{}", to_pkg, to_mod))
+                .collect(),
+            id: id.clone(),
+        };
+        if module.owners.is_empty() {
+            report.unowned.push(owned());
+        }
+        for owner in &module.owners {
+            report.owners.entry(owner.clone()).or_default().push(owned());
+        }
+    }
+
+    for modules in report.owners.values_mut().chain([&mut report.unowned]) {
+        modules.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+    Ok(report)
+}
+
+fn show_modules_by_owner(manifest: &Manifest, project: &ProjectAST) -> Result<(), Box<dyn Error>> {
+    println!("Modules by owner:");
+
+    let report = group_modules_by_owner(manifest, project)?;
+    if report.owners.is_empty() && report.unowned.is_empty() {
+        println!();
+        println!("  No modules found");
+        return Ok(());
+    }
+
+    let groups = report
+        .owners
+        .iter()
+        .map(|(owner, modules)| (owner.as_str(), modules))
+        .chain(Some(("Unowned", &report.unowned)).filter(|(_, m)| !m.is_empty()));
+    for (owner, modules) in groups {
+        let noun = if modules.len() == 1 { "module" } else { "modules" };
+        println!();
+        println!("  {} ({} {})", owner, modules.len(), noun);
+        for module in modules {
+            match &module.package {
+                Some(package) => println!("    {} ({})", module.id, package),
+                None => println!("    {}", module.id),
+            }
+            for dep in &module.cross_package_deps {
+                println!("      → {}", dep);
+            }
+        }
     }
 
     Ok(())
 }
 
+fn build_owner_json(
+    manifest: &Manifest,
+    project: &ProjectAST,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    let report = group_modules_by_owner(manifest, project)?;
+    let module_json = |modules: &[OwnedModule]| -> Vec<serde_json::Value> {
+        modules
+            .iter()
+            .map(|module| {
+                serde_json::json!({
+                    "id": module.id,
+                    "package": module.package,
+                    "cross_package_deps": module.cross_package_deps,
+                })
+            })
+            .collect()
+    };
+
+    let owners: Vec<serde_json::Value> = report
+        .owners
+        .iter()
+        .map(|(owner, modules)| {
+            serde_json::json!({ "owner": owner, "modules": module_json(modules) })
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "owners": owners,
+        "unowned": module_json(&report.unowned),
+    }))
+}
+
 fn export_package_deps_mermaid(manifest: &Manifest) {
     println!("graph TD");
 
@@ -353,23 +699,8 @@ fn export_cross_package_mermaid(
     // Get all dependencies
     let normalized_reqs = project.collect_normalized_requires();
 
-    let mut cross_package_edges = Vec::new();
-
-    for req in &normalized_reqs {
-        let from_pkg = module_to_package.get(&req.from_mod);
-        let to_pkg = module_to_package.get(&req.to_mod);
-
-        if from_pkg != to_pkg && from_pkg.is_some() && to_pkg.is_some() {
-            cross_package_edges.push((
-                from_pkg.unwrap(),
-                &req.from_mod,
-                to_pkg.unwrap(),
-                &req.to_mod,
-            ));
-        }
-    }
-
-    cross_package_edges.sort();
+    let mut cross_package_edges =
+        collect_cross_package_edges(&module_to_package, &normalized_reqs);
     cross_package_edges.dedup();
 
     for (from_pkg, from_mod, to_pkg, to_mod) in cross_package_edges {
@@ -385,6 +716,136 @@ fn export_cross_package_mermaid(
     Ok(())
 }
 
+/// Requires edges whose endpoints live in different known packages, sorted as
+/// (from package, from module, to package, to module)
+fn collect_cross_package_edges<'a>(
+    module_to_package: &'a HashMap<String, String>,
+    normalized_reqs: &'a [NormalizedRequire],
+) -> Vec<(&'a String, &'a String, &'a String, &'a String)> {
+    let mut edges = Vec::new();
+
+    for req in normalized_reqs {
+        let from_pkg = module_to_package.get(&req.from_mod);
+        let to_pkg = module_to_package.get(&req.to_mod);
+
+        if let (Some(from_pkg), Some(to_pkg)) = (from_pkg, to_pkg) {
+            if from_pkg != to_pkg {
+                edges.push((from_pkg, &req.from_mod, to_pkg, &req.to_mod));
+            }
+        }
+    }
+
+    edges.sort();
+    edges
+}
+
+/// Structured dependency graph for `--format json`: packages with their
+/// depends, modules with their package and edges, and cross-package edges.
+/// `--package` and `--module` narrow the packages and modules listed.
+fn build_deps_json(
+    manifest: &Manifest,
+    project: &ProjectAST,
+    scope: &Scope,
+    depth: Option<usize>,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    let module_to_package = build_module_to_package_map(manifest, project)?;
+    let normalized_reqs = project.collect_normalized_requires();
+
+    match scope {
+        Scope::Package(name) if !manifest.packages.contains_key(name) => {
+            return Err(format!("Package '{}' not found", name).into());
+        }
+        Scope::Module(name) if !project.mods.contains_key(name) => {
+            return Err(format!("Module '{}' not found", name).into());
+        }
+        _ => {}
+    }
+
+    let mut package_names: Vec<&String> = manifest
+        .packages
+        .keys()
+        .filter(|name| match scope {
+            Scope::Package(pkg) => *name == pkg,
+            _ => true,
+        })
+        .collect();
+    package_names.sort();
+
+    let packages: Vec<serde_json::Value> = package_names
+        .into_iter()
+        .map(|name| {
+            let pkg = &manifest.packages[name];
+            let mut value = serde_json::json!({
+                "name": name,
+                "namespace": pkg.namespace,
+                "root": pkg.root,
+                "depends": pkg.depends,
+            });
+            if let Some(max_depth) = depth {
+                let transitive: Vec<serde_json::Value> =
+                    transitive_package_deps(manifest, name, max_depth)
+                        .into_iter()
+                        .map(|(dep, d)| serde_json::json!({ "package": dep, "depth": d }))
+                        .collect();
+                value["transitive"] = serde_json::Value::Array(transitive);
+            }
+            value
+        })
+        .collect();
+
+    let mut module_ids: Vec<&String> = project
+        .mods
+        .keys()
+        .filter(|mod_id| match scope {
+            Scope::Package(pkg) => module_to_package.get(*mod_id) == Some(pkg),
+            Scope::Module(name) => *mod_id == name,
+            _ => true,
+        })
+        .collect();
+    module_ids.sort();
+
+    let modules: Vec<serde_json::Value> = module_ids
+        .into_iter()
+        .map(|mod_id| {
+            let depends: Vec<&String> = normalized_reqs
+                .iter()
+                .filter(|req| &req.from_mod == mod_id)
+                .map(|req| &req.to_mod)
+                .collect();
+            let dependents: Vec<&String> = normalized_reqs
+                .iter()
+                .filter(|req| &req.to_mod == mod_id)
+                .map(|req| &req.from_mod)
+                .collect();
+            serde_json::json!({
+                "id": mod_id,
+                "package": module_to_package.get(mod_id),
+                "depends": depends,
+                "dependents": dependents,
+            })
+        })
+        .collect();
+
+    let cross_package_edges: Vec<serde_json::Value> =
+        collect_cross_package_edges(&module_to_package, &normalized_reqs)
+            .into_iter()
+            .map(|(from_pkg, from_mod, to_pkg, to_mod)| {
+                serde_json::json!({
+                    "from_package": from_pkg,
+                    "from_module": from_mod,
+                    "to_package": to_pkg,
+                    "to_module": to_mod,
+                })
+            })
+            .collect();
+
+    Ok(serde_json::json!({
+        "packages": packages,
+        "modules": modules,
+        "cross_package_edges": cross_package_edges,
+    }))
+}
+
 fn build_module_to_package_map(
     manifest: &Manifest,
     project: &ProjectAST,
@@ -418,12 +879,190 @@ fn print_deps_usage() {
     eprintln!("  --package <name>     Show modules in a specific package");
     eprintln!("  --module <name>      Show dependencies for a specific module");
     eprintln!("  --cross-package      Show only cross-package dependencies");
-    eprintln!("  --format <format>    Output format (text, mermaid) [default: text]");
+    eprintln!("  --group-by <mode>    Group --cross-package edges (package, none) [default: none]");
+    eprintln!("  --packages           Show package dependencies (default)");
+    eprintln!("  --unused             Show packages nothing depends on (orphans vs. entry leaves)");
+    eprintln!("  --by-owner           Group modules and their cross-package deps by mod owners");
+    eprintln!("  --transitive         Also show indirect package dependencies");
+    eprintln!("  --depth <n>          Like --transitive, limited to n levels");
+    eprintln!("  --format <format>    Output format (text, mermaid, json) [default: text]");
     eprintln!();
     eprintln!("Examples:");
     eprintln!("  surc deps surv.toml");
     eprintln!("  surc deps surv.toml --package backend");
     eprintln!("  surc deps surv.toml --module mod.user_api");
     eprintln!("  surc deps surv.toml --cross-package");
+    eprintln!("  surc deps surv.toml --cross-package --group-by package");
+    eprintln!("  surc deps surv.toml --packages --transitive");
+    eprintln!("  surc deps surv.toml --unused");
+    eprintln!("  surc deps surv.toml --by-owner");
     eprintln!("  surc deps surv.toml --format mermaid > deps.md");
+    eprintln!("  surc deps surv.toml --format json > deps.json");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(packages: &str) -> Manifest {
+        let text = format!("[project]\nname = \"demo\"\n\n[paths]\nir_root = \".\"\n\n{}", packages);
+        toml::from_str(&text).unwrap()
+    }
+
+    /// app's web module requires api's users module; tools has no pipeline
+    /// and cli has an entry module.
+    fn project_fixture() -> (Manifest, ProjectAST) {
+        let manifest = manifest(
+            r#"
+[packages.app]
+root = "app"
+depends = ["api"]
+
+[packages.api]
+root = "api"
+
+[packages.tools]
+root = "tools"
+
+[packages.cli]
+root = "cli"
+"#,
+        );
+        let file = |path: &str, text: &str| {
+            (std::path::PathBuf::from(path), survibe_parser_rs::parse_surv_str(text).unwrap())
+        };
+        let project = ProjectAST::from_files(vec![
+            file(
+                "app/web.toml",
+                "package = \"app\"\nrequire = [\"mod.users\"]\n\n\
+                 [mod.web]\npurpose = \"x\"\n",
+            ),
+            file("api/users.toml", "package = \"api\"\n\n[mod.users]\npurpose = \"x\"\n"),
+            file("tools/lint.toml", "package = \"tools\"\n\n[mod.lint]\npurpose = \"x\"\n"),
+            file(
+                "cli/main.toml",
+                "package = \"cli\"\n\n[mod.cli]\npurpose = \"x\"\npipeline = [\"func.run\"]\n",
+            ),
+        ]);
+        (manifest, project)
+    }
+
+    #[test]
+    fn walks_package_depends_breadth_first() {
+        let manifest = manifest(
+            r#"
+[packages.app]
+root = "app"
+depends = ["api", "core"]
+
+[packages.api]
+root = "api"
+depends = ["core", "util"]
+
+[packages.core]
+root = "core"
+depends = ["app"]
+
+[packages.util]
+root = "util"
+"#,
+        );
+
+        // core is reached directly, so it stays at depth 1; the cycle back
+        // to app does not list the root
+        let mut deps = transitive_package_deps(&manifest, "app", 5);
+        deps.sort();
+        assert_eq!(
+            deps,
+            [("api".to_string(), 1), ("core".to_string(), 1), ("util".to_string(), 2)]
+        );
+
+        let mut direct = transitive_package_deps(&manifest, "app", 1);
+        direct.sort();
+        assert_eq!(direct, [("api".to_string(), 1), ("core".to_string(), 1)]);
+
+        assert!(transitive_package_deps(&manifest, "missing", 5).is_empty());
+    }
+
+    #[test]
+    fn builds_the_dependency_graph_as_json() {
+        let (manifest, project) = project_fixture();
+
+        let graph = build_deps_json(&manifest, &project, &Scope::Packages, None).unwrap();
+        let names: Vec<&str> = graph["packages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["api", "app", "cli", "tools"]);
+        let web = graph["modules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|m| m["id"] == "mod.web")
+            .unwrap();
+        assert_eq!(web["package"], "app");
+        assert_eq!(web["depends"], serde_json::json!(["mod.users"]));
+        assert_eq!(
+            graph["cross_package_edges"],
+            serde_json::json!([{
+                "from_package": "app",
+                "from_module": "mod.web",
+                "to_package": "api",
+                "to_module": "mod.users",
+            }])
+        );
+        assert!(graph["packages"][0].get("transitive").is_none());
+
+        let scope = Scope::Package("api".into());
+        let api = build_deps_json(&manifest, &project, &scope, Some(2)).unwrap();
+        assert_eq!(api["packages"].as_array().unwrap().len(), 1);
+        assert_eq!(api["packages"][0]["transitive"], serde_json::json!([]));
+        assert_eq!(api["modules"][0]["dependents"], serde_json::json!(["mod.web"]));
+        assert_eq!(api["modules"].as_array().unwrap().len(), 1);
+
+        let missing = Scope::Package("nope".into());
+        assert!(build_deps_json(&manifest, &project, &missing, None).is_err());
+    }
+
+    #[test]
+    fn groups_module_edges_by_package_pair() {
+        let [app, api, core, web, admin, users, store] =
+            ["app", "api", "core", "mod.web", "mod.admin", "mod.users", "mod.store"]
+                .map(String::from);
+        let edges = [
+            (&app, &web, &api, &users),
+            (&app, &admin, &api, &users),
+            (&app, &web, &core, &store),
+            (&api, &users, &core, &store),
+        ];
+
+        let grouped: Vec<_> = group_edges_by_package(&edges).into_iter().collect();
+
+        assert_eq!(
+            grouped,
+            [((&api, &core), 1), ((&app, &api), 2), ((&app, &core), 1)]
+        );
+    }
+
+    #[test]
+    fn finds_packages_nothing_depends_on() {
+        let (manifest, project) = project_fixture();
+
+        let unused = find_unused_packages(&manifest, &project).unwrap();
+        let names: Vec<&str> = unused.iter().map(|pkg| pkg.name.as_str()).collect();
+        assert_eq!(names, ["app", "cli", "tools"]);
+        assert_eq!(unused[1].entry_modules, ["mod.cli"]);
+
+        // A package with an entry module is a leaf, not an orphan
+        let json = build_unused_json(&manifest, &project).unwrap();
+        let statuses: Vec<(&str, &str)> = json["unused_packages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|pkg| (pkg["name"].as_str().unwrap(), pkg["status"].as_str().unwrap()))
+            .collect();
+        assert_eq!(statuses, [("app", "orphan"), ("cli", "leaf"), ("tools", "orphan")]);
+    }
 }
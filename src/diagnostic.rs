@@ -6,4 +6,143 @@ pub struct Diagnostic {
     pub kind: String,
     pub message: String,
     pub location: String,
+    /// Other package involved when the diagnostic crosses a package boundary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// Concrete fix an editor could offer, e.g. the name a typo was meant to be
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(
+        severity: impl Into<String>,
+        kind: impl Into<String>,
+        message: impl Into<String>,
+        location: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: severity.into(),
+            kind: kind.into(),
+            message: message.into(),
+            location: location.into(),
+            package: None,
+            suggestion: None,
+        }
+    }
+
+    /// Mark the other package involved; `None` leaves it unset.
+    pub fn with_package(mut self, package: impl Into<Option<String>>) -> Self {
+        self.package = package.into();
+        self
+    }
+
+    /// Attach a concrete fix; `None` leaves it unset.
+    pub fn with_suggestion(mut self, suggestion: impl Into<Option<String>>) -> Self {
+        self.suggestion = suggestion.into();
+        self
+    }
+}
+
+/// Render diagnostics as JUnit XML for CI test-report views.
+///
+/// Each `(name, diagnostics)` pair becomes a `<testsuite>`, each diagnostic a
+/// `<testcase>`; errors carry a `<failure>`, other severities pass with the
+/// message in `<system-out>`. A suite without diagnostics gets one passing case.
+pub fn render_junit(suites: &[(String, Vec<Diagnostic>)]) -> String {
+    let total: usize = suites.iter().map(|(_, diags)| diags.len().max(1)).sum();
+    let failures = suites
+        .iter()
+        .flat_map(|(_, diags)| diags)
+        .filter(|diag| diag.severity == "error")
+        .count();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"surc\" tests=\"{}\" failures=\"{}\">\n",
+        total, failures
+    ));
+    for (name, diags) in suites {
+        let suite_failures = diags.iter().filter(|d| d.severity == "error").count();
+        out.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            xml_escape(name),
+            diags.len().max(1),
+            suite_failures
+        ));
+        if diags.is_empty() {
+            out.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"no issues\"/>\n",
+                xml_escape(name)
+            ));
+        }
+        for diag in diags {
+            out.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\">\n",
+                xml_escape(name),
+                xml_escape(&format!("[{}] {}", diag.kind, diag.location))
+            ));
+            let mut detail = diag.message.clone();
+            if let Some(suggestion) = &diag.suggestion {
+                detail.push_str(&format!("\nhelp: {}", suggestion));
+            }
+            if diag.severity == "error" {
+                out.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                    xml_escape(&diag.kind),
+                    xml_escape(&diag.message),
+                    xml_escape(&detail)
+                ));
+            } else {
+                out.push_str(&format!(
+                    "      <system-out>{}: {}</system-out>\n",
+                    xml_escape(&diag.severity),
+                    xml_escape(&detail)
+                ));
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diag(severity: &str, kind: &str, message: &str) -> Diagnostic {
+        Diagnostic::new(severity, kind, message, "mod.api")
+    }
+
+    #[test]
+    fn renders_junit_suites_per_file() {
+        let xml = render_junit(&[
+            (
+                "api.toml".to_string(),
+                vec![
+                    diag("error", "UndefinedFuncInMod", "func <x> is not defined"),
+                    diag("warning", "UnusedSchema", "schema.a is unused"),
+                ],
+            ),
+            ("clean.toml".to_string(), Vec::new()),
+        ]);
+
+        assert!(xml.contains("<testsuites name=\"surc\" tests=\"3\" failures=\"1\">"));
+        assert!(xml.contains("<testsuite name=\"api.toml\" tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains(
+            "<failure type=\"UndefinedFuncInMod\" message=\"func &lt;x&gt; is not defined\">"
+        ));
+        assert!(xml.contains("<system-out>warning: schema.a is unused</system-out>"));
+        assert!(xml.contains("<testcase classname=\"clean.toml\" name=\"no issues\"/>"));
+    }
 }
@@ -0,0 +1,140 @@
+use survibe_parser_rs::ir_diff::{diff_surv_files, FieldChange, IrDiff};
+use survibe_parser_rs::parse_surv_file;
+use serde_json::Value;
+use std::error::Error;
+use std::fs::File;
+
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub fn run_diff(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.first().is_some_and(|a| a == "--help" || a == "-h") {
+        print_diff_usage();
+        return Ok(());
+    }
+    if args.len() < 2 {
+        print_diff_usage();
+        std::process::exit(1);
+    }
+
+    let (old_path, new_path) = (&args[0], &args[1]);
+    let mut format = OutputFormat::Text;
+
+    let mut i = 2;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                if i + 1 < args.len() {
+                    format = match args[i + 1].as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        other => return Err(format!("Unknown format: {}", other).into()),
+                    };
+                    i += 2;
+                } else {
+                    return Err("--format requires a value (text, json)".into());
+                }
+            }
+            other => {
+                return Err(format!("Unknown option: {}", other).into());
+            }
+        }
+    }
+
+    let old = parse_surv_file(File::open(old_path)?)?;
+    let new = parse_surv_file(File::open(new_path)?)?;
+    let diff = diff_surv_files(&old, &new);
+
+    match format {
+        OutputFormat::Text => print_diff(&diff),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+    }
+
+    Ok(())
+}
+
+fn print_diff(diff: &IrDiff) {
+    if diff.is_empty() {
+        println!("No structural differences");
+        return;
+    }
+
+    if !diff.added.is_empty() {
+        println!("Added:");
+        for id in &diff.added {
+            println!("  + {}", id);
+        }
+        println!();
+    }
+    if !diff.removed.is_empty() {
+        println!("Removed:");
+        for id in &diff.removed {
+            println!("  - {}", id);
+        }
+        println!();
+    }
+    if !diff.modified.is_empty() {
+        println!("Modified:");
+        for symbol in &diff.modified {
+            println!("  ~ {}", symbol.id);
+            for change in &symbol.fields {
+                println!("      {}: {}", change.field, describe_change(change));
+            }
+        }
+        println!();
+    }
+
+    println!(
+        "{} added, {} removed, {} modified",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.modified.len()
+    );
+}
+
+/// Lists show the entries added and removed; anything else `old → new`.
+fn describe_change(change: &FieldChange) -> String {
+    if let (Value::Array(old), Value::Array(new)) = (&change.old, &change.new) {
+        let added = new.iter().filter(|v| !old.contains(v)).map(|v| format!("+{}", entry(v)));
+        let removed = old.iter().filter(|v| !new.contains(v)).map(|v| format!("-{}", entry(v)));
+        let entries: Vec<String> = added.chain(removed).collect();
+        if !entries.is_empty() {
+            return entries.join(" ");
+        }
+        // Same entries, different order
+    }
+    format!("{} → {}", compact(&change.old), compact(&change.new))
+}
+
+fn entry(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn compact(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{}\"", s),
+        Value::Null => "(unset)".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn print_diff_usage() {
+    eprintln!("Usage: surc diff <old.toml> <new.toml> [--format text|json]");
+    eprintln!();
+    eprintln!("Compare two versions of a Surv IR file and list added, removed and");
+    eprintln!("modified schemas, funcs and mods, with the fields that changed.");
+    eprintln!("(For IR vs. implementation drift, see diff-impl.)");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --format <format>    Output format (text, json) [default: text]");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  git show main:api.toml > /tmp/api.old.toml");
+    eprintln!("  surc diff /tmp/api.old.toml api.toml");
+    eprintln!("  surc diff old.toml new.toml --format json");
+}
@@ -1,4 +1,5 @@
 use super::types::{DiffResult, SymbolKind};
+use crate::text_table::TextTable;
 use serde_json::json;
 
 /// Format diff result as plain text
@@ -208,6 +209,56 @@ pub fn report_markdown(result: &DiffResult) -> String {
     output
 }
 
+/// Format diff result as aligned columns, one row per missing/ambiguous/extra symbol
+pub fn report_table(result: &DiffResult) -> String {
+    let mut table = TextTable::new(&["STATUS", "KIND", "SURV_NAME", "IMPL.PATH", "FOUND"]);
+
+    for exp in &result.missing {
+        table.add_row(vec![
+            "missing".to_string(),
+            kind_label(&exp.kind).to_string(),
+            exp.surv_name.clone(),
+            exp.impl_path.clone().unwrap_or_else(|| "-".to_string()),
+            "no".to_string(),
+        ]);
+    }
+    for (exp, candidates) in &result.ambiguous {
+        table.add_row(vec![
+            "ambiguous".to_string(),
+            kind_label(&exp.kind).to_string(),
+            exp.surv_name.clone(),
+            exp.impl_path.clone().unwrap_or_else(|| "-".to_string()),
+            format!("{} candidates", candidates.len()),
+        ]);
+    }
+    for symbol in &result.extra {
+        table.add_row(vec![
+            "extra".to_string(),
+            symbol.kind.clone(),
+            "-".to_string(),
+            format_location(&symbol.uri, &symbol.range),
+            symbol.name.clone(),
+        ]);
+    }
+
+    let mut output = table.render();
+    output.push_str(&format!(
+        "\n{} matched, {} missing, {} ambiguous, {} extra\n",
+        result.matched,
+        result.missing.len(),
+        result.ambiguous.len(),
+        result.extra.len()
+    ));
+    output
+}
+
+fn kind_label(kind: &SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Func => "func",
+        SymbolKind::Schema => "schema",
+    }
+}
+
 fn format_location(uri: &str, range: &super::types::SymbolRange) -> String {
     let file_path = uri.strip_prefix("file://").unwrap_or(uri);
     format!("{}:{}:{}", file_path, range.start_line + 1, range.start_char + 1)
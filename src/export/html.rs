@@ -1,8 +1,9 @@
 use crate::ast::Section;
 use crate::deploy::ast::DeployFile;
+use crate::export::matrix::{SchemaMatrix, SchemaMatrixRow};
 use crate::project::ProjectAST;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 #[derive(Serialize)]
 struct Node {
@@ -23,6 +24,10 @@ struct NodeMetadata {
     fields: HashMap<String, String>,
     input: Vec<String>,
     output: Vec<String>,
+    /// Number of funcs that take or produce this schema (0 for funcs and mods)
+    usage: usize,
+    /// `package` header of the file that defines the node
+    package: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -83,6 +88,34 @@ struct TargetInfo {
     domain: String,
 }
 
+// Pipeline view structures
+#[derive(Serialize)]
+struct PipelineNode {
+    id: String,
+    label: String,
+    #[serde(rename = "type")]
+    node_type: String,
+    module: String,
+    intent: Option<String>,
+    layer: usize,
+    row: usize,
+}
+
+#[derive(Serialize)]
+struct PipelineLane {
+    module: String,
+    purpose: String,
+    row: usize,
+    height: usize,
+}
+
+#[derive(Serialize)]
+struct PipelineGraphData {
+    nodes: Vec<PipelineNode>,
+    links: Vec<Link>,
+    lanes: Vec<PipelineLane>,
+}
+
 pub struct HtmlExporter;
 
 impl HtmlExporter {
@@ -97,6 +130,14 @@ impl HtmlExporter {
         self.generate_deploy_html(&graph_json)
     }
 
+    /// The job DAG behind `export_deploy_interactive` as standalone JSON:
+    /// job nodes with target/secret/side-effect metadata, dependency links,
+    /// pipeline info and targets, for external schedulers.
+    pub fn export_deploy_json(&self, deploy: &DeployFile) -> String {
+        let graph_data = self.build_deploy_graph_data(deploy);
+        serde_json::to_string_pretty(&graph_data).unwrap()
+    }
+
     fn build_deploy_graph_data(&self, deploy: &DeployFile) -> DeployGraphData {
         let mut nodes = Vec::new();
         let mut links = Vec::new();
@@ -175,7 +216,184 @@ impl HtmlExporter {
         let graph_data = self.build_graph_data(project);
         let graph_json = serde_json::to_string_pretty(&graph_data).unwrap();
 
-        self.generate_html(&graph_json)
+        self.generate_html(&graph_json, false)
+    }
+
+    /// The graph's module -> schema links as a schema-by-module matrix.
+    /// Schemas no module lists are kept as empty rows.
+    pub fn schema_module_matrix(&self, project: &ProjectAST) -> SchemaMatrix {
+        let graph_data = self.build_graph_data(project);
+        let ids = |node_type: &str| -> Vec<String> {
+            let ids: BTreeSet<&String> = graph_data
+                .nodes
+                .iter()
+                .filter(|node| node.node_type == node_type)
+                .map(|node| &node.id)
+                .collect();
+            ids.into_iter().cloned().collect()
+        };
+        let modules = ids("mod");
+        let uses: BTreeSet<(&str, &str)> = graph_data
+            .links
+            .iter()
+            .filter(|link| link.link_type == "mod_schema")
+            .map(|link| (link.source.as_str(), link.target.as_str()))
+            .collect();
+
+        let rows = ids("schema")
+            .into_iter()
+            .map(|schema| SchemaMatrixRow {
+                used_by: modules
+                    .iter()
+                    .map(|module| uses.contains(&(module.as_str(), schema.as_str())))
+                    .collect(),
+                schema,
+            })
+            .collect();
+        SchemaMatrix { modules, rows }
+    }
+
+    /// Same graph as `export_interactive`, but schema nodes are sized and
+    /// colored by how many funcs reference them (fan-in heatmap).
+    pub fn export_interactive_by_usage(&self, project: &ProjectAST) -> String {
+        let graph_data = self.build_graph_data(project);
+        let graph_json = serde_json::to_string_pretty(&graph_data).unwrap();
+
+        self.generate_html(&graph_json, true)
+    }
+
+    /// Interactive view of every module pipeline as an ordered chain, with
+    /// module `requires` connecting the end of one pipeline to the start of
+    /// the next. Nodes are placed in layers rather than by force simulation.
+    pub fn export_pipelines_interactive(&self, project: &ProjectAST) -> String {
+        let graph_data = self.build_pipeline_graph_data(project);
+        let graph_json = serde_json::to_string_pretty(&graph_data).unwrap();
+
+        self.generate_pipelines_html(&graph_json)
+    }
+
+    fn build_pipeline_graph_data(&self, project: &ProjectAST) -> PipelineGraphData {
+        let mut modules = BTreeMap::new();
+        let mut intents = HashMap::new();
+        for (_file_path, file) in &project.files {
+            for section in &file.sections {
+                match section {
+                    Section::Mod(module) => {
+                        modules.insert(format!("mod.{}", module.name), module);
+                    }
+                    Section::Func(func) => {
+                        intents.insert(format!("func.{}", func.name), func.intent.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut requires: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for req in project.collect_normalized_requires() {
+            if req.from_mod != req.to_mod && modules.contains_key(&req.to_mod) {
+                if let Some((mod_id, _)) = modules.get_key_value(&req.from_mod) {
+                    requires.entry(mod_id.as_str()).or_default().push(req.to_mod);
+                }
+            }
+        }
+
+        // A module with no pipeline still takes one layer so requires can pass through it
+        let stages: BTreeMap<&str, Vec<Vec<String>>> = modules
+            .iter()
+            .map(|(mod_id, module)| {
+                let stages = if module.pipeline_chains.is_empty() {
+                    vec![vec![mod_id.clone()]]
+                } else {
+                    module.pipeline_stages()
+                };
+                (mod_id.as_str(), stages)
+            })
+            .collect();
+
+        // First layer of each module: after every module it requires has finished
+        let mut start_layer: BTreeMap<&str, usize> = BTreeMap::new();
+        for mod_id in modules.keys() {
+            let mut visiting = Vec::new();
+            compute_start_layer(mod_id, &requires, &stages, &mut start_layer, &mut visiting);
+        }
+
+        let mut order: Vec<&str> = modules.keys().map(String::as_str).collect();
+        order.sort_by_key(|mod_id| (start_layer[mod_id], *mod_id));
+
+        let mut nodes = Vec::new();
+        let mut links = Vec::new();
+        let mut lanes = Vec::new();
+        let mut row = 0;
+
+        for mod_id in &order {
+            let module = modules[*mod_id];
+            let module_stages = &stages[mod_id];
+            let height = module_stages.iter().map(Vec::len).max().unwrap_or(1).max(1);
+
+            for (stage_index, stage) in module_stages.iter().enumerate() {
+                for (branch, step) in stage.iter().enumerate() {
+                    let is_module = step == *mod_id;
+                    nodes.push(PipelineNode {
+                        id: pipeline_node_id(mod_id, step),
+                        label: step.strip_prefix("func.").unwrap_or(step).to_string(),
+                        node_type: if is_module { "mod" } else { "func" }.to_string(),
+                        module: mod_id.to_string(),
+                        intent: intents.get(step).cloned(),
+                        layer: start_layer[mod_id] + stage_index,
+                        row: row + branch,
+                    });
+                }
+            }
+
+            for pair in module_stages.windows(2) {
+                for from in &pair[0] {
+                    for to in &pair[1] {
+                        links.push(Link {
+                            source: pipeline_node_id(mod_id, from),
+                            target: pipeline_node_id(mod_id, to),
+                            label: "then".to_string(),
+                            link_type: "pipeline".to_string(),
+                        });
+                    }
+                }
+            }
+
+            lanes.push(PipelineLane {
+                module: mod_id.to_string(),
+                purpose: module.purpose.clone(),
+                row,
+                height,
+            });
+            row += height;
+        }
+
+        // requires: last stage of the required module feeds the first stage of the dependent
+        for (from_mod, deps) in &requires {
+            for to_mod in deps {
+                let (Some(dep_stages), Some(own_stages)) =
+                    (stages.get(to_mod.as_str()), stages.get(from_mod))
+                else {
+                    continue;
+                };
+                for last in dep_stages.last().into_iter().flatten() {
+                    for first in own_stages.first().into_iter().flatten() {
+                        links.push(Link {
+                            source: pipeline_node_id(to_mod, last),
+                            target: pipeline_node_id(from_mod, first),
+                            label: "requires".to_string(),
+                            link_type: "mod_require".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        PipelineGraphData {
+            nodes,
+            links,
+            lanes,
+        }
     }
 
     fn build_graph_data(&self, project: &ProjectAST) -> GraphData {
@@ -200,6 +418,8 @@ impl HtmlExporter {
                                 fields: schema.fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
                                 input: Vec::new(),
                                 output: Vec::new(),
+                                usage: 0,
+                                package: file.package.clone(),
                             },
                         });
 
@@ -260,6 +480,8 @@ impl HtmlExporter {
                                 fields: HashMap::new(),
                                 input: func.input.clone(),
                                 output: func.output.clone(),
+                                usage: 0,
+                                package: file.package.clone(),
                             },
                         });
 
@@ -295,6 +517,8 @@ impl HtmlExporter {
                                 fields: HashMap::new(),
                                 input: Vec::new(),
                                 output: Vec::new(),
+                                usage: 0,
+                                package: file.package.clone(),
                             },
                         });
 
@@ -334,10 +558,27 @@ impl HtmlExporter {
             });
         }
 
+        // Fan-in per schema: distinct funcs with it as input or output
+        let mut users: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for link in &links {
+            match link.link_type.as_str() {
+                "func_input" => users.entry(&link.source).or_default().insert(&link.target),
+                "func_output" => users.entry(&link.target).or_default().insert(&link.source),
+                _ => continue,
+            };
+        }
+        let usage: HashMap<String, usize> = users
+            .into_iter()
+            .map(|(schema, funcs)| (schema.to_string(), funcs.len()))
+            .collect();
+        for node in nodes.iter_mut().filter(|n| n.node_type == "schema") {
+            node.metadata.usage = usage.get(&node.id).copied().unwrap_or(0);
+        }
+
         GraphData { nodes, links }
     }
 
-    fn generate_html(&self, graph_json: &str) -> String {
+    fn generate_html(&self, graph_json: &str, color_by_usage: bool) -> String {
         format!(
             r#"<!DOCTYPE html>
 <html lang="en">
@@ -658,6 +899,10 @@ impl HtmlExporter {
                     <label for="link-schema">Schema Relations</label>
                 </div>
             </div>
+
+            <div class="filter-group" id="package-filters">
+                <h3>Packages</h3>
+            </div>
         </div>
 
         <div id="graph">
@@ -677,6 +922,7 @@ impl HtmlExporter {
 
     <script>
         const data = {graph_json};
+        const colorByUsage = {color_by_usage};
 
         const width = document.getElementById('graph').clientWidth;
         const height = document.getElementById('graph').clientHeight;
@@ -701,11 +947,19 @@ impl HtmlExporter {
             'module': '#8e44ad'
         }};
 
+        // --color-by usage: schema radius and hue follow func fan-in
+        const maxUsage = d3.max(data.nodes, d => d.metadata.usage) || 1;
+        const usageRadius = d3.scaleSqrt().domain([0, maxUsage]).range([12, 32]);
+        const usageColor = d3.scaleSequential(d3.interpolateYlOrRd).domain([0, maxUsage]);
+        const isHeatNode = d => colorByUsage && d.type === 'schema';
+        const nodeRadius = d => isHeatNode(d) ? usageRadius(d.metadata.usage) : 20;
+        const nodeColor = d => isHeatNode(d) ? usageColor(d.metadata.usage) : (colorMap[d.group] || '#999');
+
         const simulation = d3.forceSimulation(data.nodes)
             .force('link', d3.forceLink(data.links).id(d => d.id).distance(150))
             .force('charge', d3.forceManyBody().strength(-400))
             .force('center', d3.forceCenter(width / 2, height / 2))
-            .force('collision', d3.forceCollide().radius(40));
+            .force('collision', d3.forceCollide().radius(d => nodeRadius(d) + 20));
 
         const link = g.append('g')
             .selectAll('path')
@@ -740,11 +994,11 @@ impl HtmlExporter {
                 .on('end', dragended));
 
         node.append('circle')
-            .attr('r', 20)
-            .attr('fill', d => colorMap[d.group] || '#999');
+            .attr('r', nodeRadius)
+            .attr('fill', nodeColor);
 
         node.append('text')
-            .attr('dy', 35)
+            .attr('dy', d => nodeRadius(d) + 15)
             .text(d => d.label);
 
         node.on('click', (event, d) => {{
@@ -800,6 +1054,14 @@ impl HtmlExporter {
                 html += `<div class="detail-section"><h4>Role</h4><p>${{d.metadata.role}}</p></div>`;
             }}
 
+            if (d.metadata.package) {{
+                html += `<div class="detail-section"><h4>Package</h4><p>${{d.metadata.package}}</p></div>`;
+            }}
+
+            if (d.type === 'schema') {{
+                html += `<div class="detail-section"><h4>Used by</h4><p>${{d.metadata.usage}} func(s)</p></div>`;
+            }}
+
             if (d.metadata.intent) {{
                 html += `<div class="detail-section"><h4>Intent</h4><p>${{d.metadata.intent}}</p></div>`;
             }}
@@ -866,6 +1128,28 @@ impl HtmlExporter {
             node.classed('dimmed', d => !d.label.toLowerCase().includes(query));
         }});
 
+        // One checkbox per package; hidden unless there is more than one
+        const packageOf = d => d.metadata.package || '(no package)';
+        const packages = [...new Set(data.nodes.map(packageOf))].sort();
+        const packageGroup = document.getElementById('package-filters');
+        if (packages.length < 2) {{
+            packageGroup.style.display = 'none';
+        }}
+        packages.forEach((pkg, i) => {{
+            const option = document.createElement('div');
+            option.className = 'filter-option';
+            const checkbox = document.createElement('input');
+            checkbox.type = 'checkbox';
+            checkbox.id = `filter-package-${{i}}`;
+            checkbox.checked = true;
+            checkbox.dataset.package = pkg;
+            const label = document.createElement('label');
+            label.htmlFor = checkbox.id;
+            label.textContent = pkg;
+            option.append(checkbox, label);
+            packageGroup.appendChild(option);
+        }});
+
         // Filters
         function updateFilters() {{
             const typeFilters = {{
@@ -895,12 +1179,17 @@ impl HtmlExporter {
                 'space': document.getElementById('link-schema').checked
             }};
 
+            const packageFilters = new Set(
+                [...packageGroup.querySelectorAll('input:checked')].map(cb => cb.dataset.package)
+            );
+            const inPackage = d => packageFilters.has(packageOf(d));
+
             node.style('display', d => {{
-                return typeFilters[d.type] && groupFilters[d.group] ? 'block' : 'none';
+                return typeFilters[d.type] && groupFilters[d.group] && inPackage(d) ? 'block' : 'none';
             }});
 
             link.style('display', l => {{
-                return linkFilters[l.type] ? 'block' : 'none';
+                return linkFilters[l.type] && inPackage(l.source) && inPackage(l.target) ? 'block' : 'none';
             }});
         }}
 
@@ -931,6 +1220,268 @@ impl HtmlExporter {
         )
     }
 
+    fn generate_pipelines_html(&self, graph_json: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Surv IR Project Pipelines</title>
+    <script src="https://d3js.org/d3.v7.min.js"></script>
+    <style>
+        * {{
+            margin: 0;
+            padding: 0;
+            box-sizing: border-box;
+        }}
+
+        body {{
+            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
+            background: #1a1a1a;
+            color: #e0e0e0;
+            overflow: hidden;
+        }}
+
+        #graph {{
+            width: 100vw;
+            height: 100vh;
+        }}
+
+        .lane {{
+            fill: #252525;
+            stroke: #444;
+            stroke-width: 1px;
+        }}
+
+        .lane-label {{
+            fill: #999;
+            font-size: 12px;
+            font-weight: 600;
+        }}
+
+        .step {{
+            cursor: pointer;
+            stroke: #fff;
+            stroke-width: 1.5px;
+            rx: 6;
+        }}
+
+        .step.func {{
+            fill: #27ae60;
+        }}
+
+        .step.mod {{
+            fill: #8e44ad;
+        }}
+
+        .step.selected {{
+            stroke: #4a9eff;
+            stroke-width: 3px;
+        }}
+
+        .step-label {{
+            fill: #fff;
+            font-size: 12px;
+            text-anchor: middle;
+            pointer-events: none;
+        }}
+
+        .link {{
+            fill: none;
+            stroke: #999;
+            stroke-width: 2px;
+            marker-end: url(#arrowhead);
+        }}
+
+        .link.mod_require {{
+            stroke: #f39c12;
+            stroke-dasharray: 6 4;
+        }}
+
+        .link.highlighted {{
+            stroke: #4a9eff;
+            stroke-width: 3px;
+        }}
+
+        #details {{
+            position: fixed;
+            top: 20px;
+            right: 20px;
+            width: 320px;
+            background: #252525;
+            border: 1px solid #444;
+            border-radius: 8px;
+            padding: 16px;
+            display: none;
+        }}
+
+        #details h2 {{
+            font-size: 16px;
+            color: #4a9eff;
+            margin-bottom: 10px;
+        }}
+
+        #details p {{
+            font-size: 13px;
+            margin: 6px 0;
+        }}
+
+        #legend {{
+            position: fixed;
+            bottom: 20px;
+            left: 20px;
+            background: #252525;
+            border: 1px solid #444;
+            border-radius: 8px;
+            padding: 12px;
+            font-size: 12px;
+        }}
+
+        #legend div {{
+            margin: 4px 0;
+        }}
+    </style>
+</head>
+<body>
+    <svg id="graph"></svg>
+
+    <div id="legend">
+        <div><span style="color: #27ae60;">■</span> pipeline step</div>
+        <div><span style="color: #8e44ad;">■</span> module without pipeline</div>
+        <div><span style="color: #999;">→</span> pipeline order</div>
+        <div><span style="color: #f39c12;">⇢</span> module requires</div>
+    </div>
+
+    <div id="details"></div>
+
+    <script>
+        const graphData = {graph_json};
+
+        const layerWidth = 200;
+        const rowHeight = 70;
+        const nodeWidth = 150;
+        const nodeHeight = 36;
+        const laneLabelWidth = 160;
+
+        const svg = d3.select('#graph')
+            .attr('width', window.innerWidth)
+            .attr('height', window.innerHeight);
+
+        svg.append('defs').append('marker')
+            .attr('id', 'arrowhead')
+            .attr('viewBox', '0 -5 10 10')
+            .attr('refX', 10)
+            .attr('refY', 0)
+            .attr('markerWidth', 6)
+            .attr('markerHeight', 6)
+            .attr('orient', 'auto')
+            .append('path')
+            .attr('d', 'M0,-5L10,0L0,5')
+            .attr('fill', '#999');
+
+        const g = svg.append('g').attr('transform', 'translate(20, 20)');
+
+        svg.call(d3.zoom()
+            .scaleExtent([0.1, 4])
+            .on('zoom', (event) => {{
+                g.attr('transform', event.transform);
+            }}));
+
+        // Layered layout: x from the node's layer, y from its row within the module lane
+        const maxLayer = d3.max(graphData.nodes, d => d.layer) || 0;
+        graphData.nodes.forEach(d => {{
+            d.x = laneLabelWidth + d.layer * layerWidth + nodeWidth / 2;
+            d.y = d.row * rowHeight + rowHeight / 2;
+        }});
+        const nodeById = new Map(graphData.nodes.map(d => [d.id, d]));
+
+        g.append('g')
+            .selectAll('rect')
+            .data(graphData.lanes)
+            .join('rect')
+            .attr('class', 'lane')
+            .attr('x', 0)
+            .attr('y', d => d.row * rowHeight + 4)
+            .attr('width', laneLabelWidth + (maxLayer + 1) * layerWidth)
+            .attr('height', d => d.height * rowHeight - 8);
+
+        g.append('g')
+            .selectAll('text')
+            .data(graphData.lanes)
+            .join('text')
+            .attr('class', 'lane-label')
+            .attr('x', 10)
+            .attr('y', d => d.row * rowHeight + rowHeight / 2 + 4)
+            .text(d => d.module);
+
+        const link = g.append('g')
+            .selectAll('path')
+            .data(graphData.links.filter(l => nodeById.has(l.source) && nodeById.has(l.target)))
+            .join('path')
+            .attr('class', d => `link ${{d.type}}`)
+            .attr('d', d => {{
+                const s = nodeById.get(d.source);
+                const t = nodeById.get(d.target);
+                const x1 = s.x + nodeWidth / 2;
+                const x2 = t.x - nodeWidth / 2;
+                const mx = (x1 + x2) / 2;
+                return `M${{x1}},${{s.y}} C${{mx}},${{s.y}} ${{mx}},${{t.y}} ${{x2}},${{t.y}}`;
+            }});
+
+        const node = g.append('g')
+            .selectAll('rect')
+            .data(graphData.nodes)
+            .join('rect')
+            .attr('class', d => `step ${{d.type}}`)
+            .attr('x', d => d.x - nodeWidth / 2)
+            .attr('y', d => d.y - nodeHeight / 2)
+            .attr('width', nodeWidth)
+            .attr('height', nodeHeight)
+            .on('click', showDetails);
+
+        g.append('g')
+            .selectAll('text')
+            .data(graphData.nodes)
+            .join('text')
+            .attr('class', 'step-label')
+            .attr('x', d => d.x)
+            .attr('y', d => d.y + 4)
+            .text(d => d.label);
+
+        function showDetails(event, d) {{
+            event.stopPropagation();
+            node.classed('selected', n => n.id === d.id);
+            link.classed('highlighted', l => l.source === d.id || l.target === d.id);
+
+            const lane = graphData.lanes.find(l => l.module === d.module);
+            let html = `<h2>${{d.label}}</h2>`;
+            html += `<p><strong>Module:</strong> ${{d.module}}</p>`;
+            if (lane && lane.purpose) {{
+                html += `<p><strong>Purpose:</strong> ${{lane.purpose}}</p>`;
+            }}
+            if (d.intent) {{
+                html += `<p><strong>Intent:</strong> ${{d.intent}}</p>`;
+            }}
+            html += `<p><strong>Layer:</strong> ${{d.layer}}</p>`;
+
+            const details = document.getElementById('details');
+            details.innerHTML = html;
+            details.style.display = 'block';
+        }}
+
+        svg.on('click', () => {{
+            node.classed('selected', false);
+            link.classed('highlighted', false);
+            document.getElementById('details').style.display = 'none';
+        }});
+    </script>
+</body>
+</html>"#,
+            graph_json = graph_json
+        )
+    }
+
     fn generate_deploy_html(&self, graph_json: &str) -> String {
         format!(
             r#"<!DOCTYPE html>
@@ -1420,8 +1971,225 @@ impl HtmlExporter {
     }
 }
 
+fn pipeline_node_id(mod_id: &str, step: &str) -> String {
+    if step == mod_id {
+        mod_id.to_string()
+    } else {
+        format!("{}::{}", mod_id, step)
+    }
+}
+
+fn compute_start_layer<'a>(
+    mod_id: &'a str,
+    requires: &BTreeMap<&'a str, Vec<String>>,
+    stages: &BTreeMap<&'a str, Vec<Vec<String>>>,
+    start_layer: &mut BTreeMap<&'a str, usize>,
+    visiting: &mut Vec<&'a str>,
+) -> usize {
+    if let Some(layer) = start_layer.get(mod_id) {
+        return *layer;
+    }
+    // A requires cycle is laid out as if the back edge did not exist
+    if visiting.contains(&mod_id) {
+        return 0;
+    }
+    visiting.push(mod_id);
+
+    let mut layer = 0;
+    for dep in requires.get(mod_id).into_iter().flatten() {
+        let Some((dep_id, dep_stages)) = stages.get_key_value(dep.as_str()) else {
+            continue;
+        };
+        let dep_start = compute_start_layer(dep_id, requires, stages, start_layer, visiting);
+        layer = layer.max(dep_start + dep_stages.len());
+    }
+
+    visiting.pop();
+    start_layer.insert(mod_id, layer);
+    layer
+}
+
 impl Default for HtmlExporter {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_surv_str;
+    use std::path::PathBuf;
+
+    #[test]
+    fn lays_out_pipelines_after_required_modules() {
+        let storage = r#"
+[func.load]
+intent = "Load"
+
+[func.save]
+intent = "Save"
+
+[mod.storage]
+purpose = "Storage"
+funcs = ["func.load", "func.save"]
+pipeline = ["func.load", "func.save"]
+"#;
+        let api = r#"
+requires = ["mod.storage"]
+
+[func.notify]
+intent = "Notify"
+
+[mod.api]
+purpose = "API"
+funcs = ["func.notify"]
+pipeline = ["func.notify"]
+"#;
+        let project = ProjectAST::from_files(vec![
+            (PathBuf::from("api.toml"), parse_surv_str(api).unwrap()),
+            (PathBuf::from("storage.toml"), parse_surv_str(storage).unwrap()),
+        ]);
+        let data = HtmlExporter::new().build_pipeline_graph_data(&project);
+
+        let layer = |id: &str| data.nodes.iter().find(|n| n.id == id).unwrap().layer;
+        assert_eq!(layer("mod.storage::func.load"), 0);
+        assert_eq!(layer("mod.storage::func.save"), 1);
+        assert_eq!(layer("mod.api::func.notify"), 2);
+
+        assert!(data.links.iter().any(|l| l.link_type == "mod_require"
+            && l.source == "mod.storage::func.save"
+            && l.target == "mod.api::func.notify"));
+        assert_eq!(data.lanes[0].module, "mod.storage");
+    }
+
+    #[test]
+    fn counts_func_fan_in_per_schema() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.audit_log]
+kind = "node"
+
+[func.load_user]
+intent = "Load"
+output = ["schema.user"]
+
+[func.update_user]
+intent = "Update"
+input = ["schema.user"]
+output = ["schema.user"]
+"#;
+        let project =
+            ProjectAST::from_files(vec![(PathBuf::from("api.toml"), parse_surv_str(ir).unwrap())]);
+        let data = HtmlExporter::new().build_graph_data(&project);
+
+        let usage = |id: &str| data.nodes.iter().find(|n| n.id == id).unwrap().metadata.usage;
+        assert_eq!(usage("schema.user"), 2);
+        assert_eq!(usage("schema.audit_log"), 0);
+        assert!(HtmlExporter::new()
+            .export_interactive_by_usage(&project)
+            .contains("const colorByUsage = true;"));
+    }
+
+    #[test]
+    fn builds_schema_by_module_matrix() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.order]
+kind = "node"
+
+[schema.audit_log]
+kind = "node"
+
+[mod.accounts]
+purpose = "test"
+schemas = ["schema.user"]
+
+[mod.shop]
+purpose = "test"
+schemas = ["schema.user", "schema.order"]
+"#;
+        let project =
+            ProjectAST::from_files(vec![(PathBuf::from("api.toml"), parse_surv_str(ir).unwrap())]);
+        let matrix = HtmlExporter::new().schema_module_matrix(&project);
+
+        assert_eq!(matrix.modules, vec!["mod.accounts", "mod.shop"]);
+        assert_eq!(
+            matrix.to_csv(),
+            "schema,mod.accounts,mod.shop,modules\n\
+             schema.audit_log,,,0\n\
+             schema.order,,x,1\n\
+             schema.user,x,x,2\n"
+        );
+        assert!(matrix
+            .to_markdown()
+            .starts_with("| schema | mod.accounts | mod.shop | modules |\n|---|:-:|:-:|---:|\n"));
+    }
+
+    #[test]
+    fn tags_nodes_with_their_package() {
+        let backend = r#"
+package = "backend"
+
+[schema.user]
+kind = "node"
+
+[mod.users]
+purpose = "Users"
+"#;
+        let shared = r#"
+[schema.money]
+kind = "value"
+"#;
+        let project = ProjectAST::from_files(vec![
+            (PathBuf::from("backend/users.toml"), parse_surv_str(backend).unwrap()),
+            (PathBuf::from("shared.toml"), parse_surv_str(shared).unwrap()),
+        ]);
+        let data = HtmlExporter::new().build_graph_data(&project);
+
+        let package = |id: &str| {
+            data.nodes.iter().find(|n| n.id == id).unwrap().metadata.package.clone()
+        };
+        assert_eq!(package("schema.user").as_deref(), Some("backend"));
+        assert_eq!(package("mod.users").as_deref(), Some("backend"));
+        assert_eq!(package("schema.money"), None);
+        assert!(HtmlExporter::new().export_interactive(&project).contains("id=\"package-filters\""));
+    }
+
+    #[test]
+    fn exports_deploy_graph_as_json() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.target.prod]
+kind = "production"
+
+[deploy.job.build]
+runs = ["npm run build"]
+
+[deploy.job.release]
+requires = ["job.build"]
+runs = ["kubectl apply"]
+uses_target = "target.prod"
+side_effects = ["release"]
+"#,
+        )
+        .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&HtmlExporter::new().export_deploy_json(&deploy)).unwrap();
+
+        let release = json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["id"] == "job.release")
+            .unwrap();
+        assert_eq!(release["metadata"]["target_kind"], "production");
+        assert_eq!(release["metadata"]["side_effects"][0], "release");
+        assert_eq!(json["links"][0]["source"], "job.build");
+        assert_eq!(json["targets"][0]["name"], "prod");
+    }
+}
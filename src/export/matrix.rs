@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+/// Which modules reference which schemas: one row per schema, one column per
+/// module. A schema used by several modules is shared; by one, private.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SchemaMatrix {
+    /// Module ids, in column order
+    pub modules: Vec<String>,
+    pub rows: Vec<SchemaMatrixRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaMatrixRow {
+    pub schema: String,
+    /// One entry per module column: whether the module lists the schema
+    pub used_by: Vec<bool>,
+}
+
+impl SchemaMatrixRow {
+    pub fn module_count(&self) -> usize {
+        self.used_by.iter().filter(|used| **used).count()
+    }
+}
+
+impl SchemaMatrix {
+    /// CSV with a header row and an `x` in each used cell; the last column
+    /// counts the modules using the schema.
+    pub fn to_csv(&self) -> String {
+        let mut output = String::new();
+        let header: Vec<String> = std::iter::once("schema")
+            .chain(self.modules.iter().map(String::as_str))
+            .chain(std::iter::once("modules"))
+            .map(csv_field)
+            .collect();
+        output.push_str(&header.join(","));
+        output.push('\n');
+        for row in &self.rows {
+            let cells: Vec<String> = std::iter::once(csv_field(&row.schema))
+                .chain(row.used_by.iter().map(|used| mark(*used).to_string()))
+                .chain(std::iter::once(row.module_count().to_string()))
+                .collect();
+            output.push_str(&cells.join(","));
+            output.push('\n');
+        }
+        output
+    }
+
+    /// GitHub-flavored Markdown table, same layout as `to_csv`.
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("| schema | {} | modules |\n", self.modules.join(" | ")));
+        output.push_str(&format!("|---|{}---:|\n", ":-:|".repeat(self.modules.len())));
+        for row in &self.rows {
+            let cells: Vec<&str> = row.used_by.iter().map(|used| mark(*used)).collect();
+            output.push_str(&format!(
+                "| {} | {} | {} |\n",
+                row.schema,
+                cells.join(" | "),
+                row.module_count()
+            ));
+        }
+        output
+    }
+}
+
+fn mark(used: bool) -> &'static str {
+    if used {
+        "x"
+    } else {
+        ""
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
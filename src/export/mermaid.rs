@@ -1,17 +1,49 @@
-use crate::ast::{ModSection, Section};
+use crate::ast::{ModSection, SchemaSection, Section};
+use crate::codegen::GitLabCIGenerator;
 use crate::deploy::ast::DeployFile;
 use crate::project::ProjectAST;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-pub struct MermaidExporter;
+pub struct MermaidExporter {
+    /// Wrap intent/purpose text at roughly this many characters per line
+    label_width: Option<usize>,
+}
 
 impl MermaidExporter {
     pub fn new() -> Self {
-        Self
+        Self { label_width: None }
+    }
+
+    /// Wrap func intents and module purposes into `<br/>`-separated lines of
+    /// about `width` characters (`--label-width`).
+    pub fn with_label_width(mut self, width: usize) -> Self {
+        self.label_width = Some(width);
+        self
     }
 
     /// Export Deploy IR job DAG as a Mermaid flowchart
     pub fn export_deploy_pipeline(&self, deploy: &DeployFile) -> String {
+        self.export_deploy_pipeline_for_kind(deploy, None)
+    }
+
+    /// Export the deploy job DAG, keeping only jobs whose target has the given
+    /// kind plus the jobs they depend on. `None` keeps every job.
+    pub fn export_deploy_pipeline_for_kind(
+        &self,
+        deploy: &DeployFile,
+        only_kind: Option<&str>,
+    ) -> String {
+        self.export_deploy_pipeline_grouped(deploy, only_kind, false)
+    }
+
+    /// Like `export_deploy_pipeline_for_kind`; with `group_by_stage`, jobs are
+    /// drawn inside one subgraph per stage, assigned the way codegen does.
+    pub fn export_deploy_pipeline_grouped(
+        &self,
+        deploy: &DeployFile,
+        only_kind: Option<&str>,
+        group_by_stage: bool,
+    ) -> String {
         let mut output = String::from("---\n");
         if let Some(pipeline) = &deploy.pipeline {
             output.push_str(&format!("title: Deploy Pipeline - {}\n", pipeline.name));
@@ -26,24 +58,23 @@ impl MermaidExporter {
             return output;
         }
 
-        // Generate job nodes
+        let shown = match only_kind {
+            Some(kind) => Self::jobs_for_target_kind(deploy, kind),
+            None => deploy.jobs.keys().map(String::as_str).collect(),
+        };
+
+        let mut nodes: Vec<(&String, String)> = Vec::new();
         for (job_name, job) in &deploy.jobs {
+            if !shown.contains(job_name.as_str()) {
+                continue;
+            }
             let job_id = Self::sanitize_id(job_name);
 
             // Determine node style based on target
-            let style = if !job.uses_target.is_empty() {
-                let target_name = job.uses_target.strip_prefix("target.").unwrap_or(&job.uses_target);
-                if let Some(target) = deploy.targets.get(target_name) {
-                    match target.kind.as_str() {
-                        "production" => ":::prod",
-                        "staging" => ":::staging",
-                        _ => "",
-                    }
-                } else {
-                    ""
-                }
-            } else {
-                ""
+            let style = match Self::target_kind(deploy, &job.uses_target) {
+                Some("production") => ":::prod",
+                Some("staging") => ":::staging",
+                _ => "",
             };
 
             // Build node label with metadata
@@ -56,14 +87,55 @@ impl MermaidExporter {
                 label.push_str(&format!("<br/><small>⚠ {}</small>", job.side_effects.join(", ")));
             }
 
-            output.push_str(&format!("    {}[\"{}\"]{}\n", job_id, label, style));
+            nodes.push((job_name, format!("{}[\"{}\"]{}", job_id, label, style)));
+        }
+
+        if group_by_stage {
+            let generator = GitLabCIGenerator::new();
+            let job_stages = generator.assign_job_stages(deploy);
+            let mut stages = generator.determine_stages(deploy);
+            // Explicit stages missing from the declared/guessed list go last
+            for stage in deploy.jobs.keys().filter_map(|name| job_stages.get(name)) {
+                if !stages.contains(stage) {
+                    stages.push(stage.clone());
+                }
+            }
+            for stage in &stages {
+                let in_stage: Vec<&String> = nodes
+                    .iter()
+                    .filter(|(job_name, _)| job_stages.get(*job_name) == Some(stage))
+                    .map(|(_, node)| node)
+                    .collect();
+                if in_stage.is_empty() {
+                    continue;
+                }
+                output.push_str(&format!(
+                    "    subgraph stage_{}[\"{}\"]\n",
+                    Self::sanitize_id(stage),
+                    stage
+                ));
+                for node in in_stage {
+                    output.push_str(&format!("        {}\n", node));
+                }
+                output.push_str("    end\n");
+            }
+        } else {
+            for (_, node) in &nodes {
+                output.push_str(&format!("    {}\n", node));
+            }
         }
 
         // Generate edges
         for (job_name, job) in &deploy.jobs {
+            if !shown.contains(job_name.as_str()) {
+                continue;
+            }
             let job_id = Self::sanitize_id(job_name);
             for req in &job.requires {
                 let req_name = req.strip_prefix("job.").unwrap_or(req);
+                if !shown.contains(req_name) {
+                    continue;
+                }
                 let req_id = Self::sanitize_id(req_name);
                 output.push_str(&format!("    {} --> {}\n", req_id, job_id));
             }
@@ -76,8 +148,46 @@ impl MermaidExporter {
         output
     }
 
+    /// Kind of the target a job's `uses_target` points at, if defined
+    fn target_kind<'d>(deploy: &'d DeployFile, uses_target: &str) -> Option<&'d str> {
+        let target_name = uses_target.strip_prefix("target.").unwrap_or(uses_target);
+        deploy.targets.get(target_name).map(|target| target.kind.as_str())
+    }
+
+    /// Jobs targeting `kind`, plus everything they transitively require
+    fn jobs_for_target_kind<'d>(deploy: &'d DeployFile, kind: &str) -> HashSet<&'d str> {
+        let mut stack: Vec<&str> = deploy
+            .jobs
+            .iter()
+            .filter(|(_, job)| Self::target_kind(deploy, &job.uses_target) == Some(kind))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let mut shown = HashSet::new();
+        while let Some(name) = stack.pop() {
+            if !shown.insert(name) {
+                continue;
+            }
+            if let Some(job) = deploy.jobs.get(name) {
+                stack.extend(job.requires.iter().map(|req| req.strip_prefix("job.").unwrap_or(req)));
+            }
+        }
+        shown
+    }
+
     /// Export a single module's pipeline as a Mermaid flowchart
     pub fn export_pipeline(&self, module: &ModSection, project: &ProjectAST) -> String {
+        self.export_pipeline_highlighted(module, project, &[])
+    }
+
+    /// Export a module's pipeline, emphasizing the given funcs with a `highlight` class.
+    ///
+    /// Entries may be written with or without the `func.` prefix.
+    pub fn export_pipeline_highlighted(
+        &self,
+        module: &ModSection,
+        project: &ProjectAST,
+        highlight: &[String],
+    ) -> String {
         let mut output = String::from("---\n");
         output.push_str(&format!("title: Pipeline - {}\n", module.name));
         output.push_str("---\n");
@@ -98,51 +208,58 @@ impl MermaidExporter {
             }
         }
 
-        // Generate nodes and edges
-        for (i, func_ref) in module.pipeline.iter().enumerate() {
-            let func_id = format!("f{}", i);
-            let func_name = func_ref.strip_prefix("func.").unwrap_or(func_ref);
-
-            // Add node with intent as subtext if available
-            if let Some(func) = funcs.get(func_ref) {
-                let intent = if func.intent.is_empty() {
-                    String::new()
+        // Generate nodes, one per step; parallel branches share a stage
+        let mut stage_ids: Vec<Vec<(String, &String)>> = Vec::new();
+        let mut next_id = 0;
+        for stage in module.pipeline_chains.iter().flatten() {
+            let mut ids = Vec::new();
+            for func_ref in stage {
+                let func_id = format!("f{}", next_id);
+                next_id += 1;
+                let func_name = func_ref.strip_prefix("func.").unwrap_or(func_ref);
+
+                // Add node with intent as subtext if available
+                if let Some(func) = funcs.get(func_ref) {
+                    let intent = if func.intent.is_empty() {
+                        String::new()
+                    } else {
+                        format!("<br/><small>{}</small>", self.wrap_label(&func.intent))
+                    };
+                    output.push_str(&format!("    {}[\"{}{}\"]", func_id, func_name, intent));
                 } else {
-                    format!("<br/><small>{}</small>", Self::escape_html(&func.intent))
-                };
-                output.push_str(&format!("    {}[\"{}{}\"]", func_id, func_name, intent));
-            } else {
-                output.push_str(&format!("    {}[\"{}⚠\"]", func_id, func_name));
-            }
-
-            // Add styling for undefined funcs
-            if !funcs.contains_key(func_ref) {
-                output.push_str(":::error");
-            }
-            output.push('\n');
+                    output.push_str(&format!("    {}[\"{}⚠\"]", func_id, func_name));
+                }
 
-            // Add edge from previous
-            if i > 0 {
-                let prev_id = format!("f{}", i - 1);
+                // Add styling for undefined funcs
+                if !funcs.contains_key(func_ref) {
+                    output.push_str(":::error");
+                }
+                output.push('\n');
 
-                // Check schema compatibility
-                let prev_func = module.pipeline.get(i - 1)
-                    .and_then(|r| funcs.get(r));
-                let curr_func = funcs.get(func_ref);
+                ids.push((func_id, func_ref));
+            }
+            stage_ids.push(ids);
+        }
 
-                if let (Some(prev), Some(curr)) = (prev_func, curr_func) {
-                    let common = Self::find_common_schemas(&prev.output, &curr.input);
-                    if common.is_empty() {
-                        output.push_str(&format!("    {} -.->|⚠ no common schema| {}\n", prev_id, func_id));
+        // Connect every step of a stage to every step of the next stage
+        for pair in stage_ids.windows(2) {
+            for (prev_id, prev_ref) in &pair[0] {
+                for (func_id, func_ref) in &pair[1] {
+                    // Check schema compatibility
+                    if let (Some(prev), Some(curr)) = (funcs.get(*prev_ref), funcs.get(*func_ref)) {
+                        let common = Self::find_common_schemas(&prev.output, &curr.input);
+                        if common.is_empty() {
+                            output.push_str(&format!("    {} -.->|⚠ no common schema| {}\n", prev_id, func_id));
+                        } else {
+                            let label = common.iter()
+                                .map(|s| s.strip_prefix("schema.").unwrap_or(s))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            output.push_str(&format!("    {} -->|{}| {}\n", prev_id, label, func_id));
+                        }
                     } else {
-                        let label = common.iter()
-                            .map(|s| s.strip_prefix("schema.").unwrap_or(s))
-                            .collect::<Vec<_>>()
-                            .join(", ");
-                        output.push_str(&format!("    {} -->|{}| {}\n", prev_id, label, func_id));
+                        output.push_str(&format!("    {} --> {}\n", prev_id, func_id));
                     }
-                } else {
-                    output.push_str(&format!("    {} --> {}\n", prev_id, func_id));
                 }
             }
         }
@@ -150,6 +267,21 @@ impl MermaidExporter {
         // Add styling
         output.push_str("\n    classDef error fill:#ffdddd,stroke:#ff0000\n");
 
+        let highlighted: Vec<&str> = stage_ids
+            .iter()
+            .flatten()
+            .filter(|(_, func_ref)| {
+                highlight.iter().any(|h| {
+                    h == *func_ref || func_ref.strip_prefix("func.") == Some(h.as_str())
+                })
+            })
+            .map(|(id, _)| id.as_str())
+            .collect();
+        if !highlighted.is_empty() {
+            output.push_str("    classDef highlight fill:#fff3b0,stroke:#e6a700,stroke-width:3px\n");
+            output.push_str(&format!("    class {} highlight\n", highlighted.join(",")));
+        }
+
         output
     }
 
@@ -198,6 +330,43 @@ impl MermaidExporter {
         output
     }
 
+    /// Export a mindmap of the project: packages, their modules, and the
+    /// schemas/funcs each module lists. Modules outside a package hang off the root.
+    pub fn export_module_mindmap(&self, project: &ProjectAST, root: &str) -> String {
+        let mut output = format!("---\ntitle: Module Map - {}\n---\n", root);
+        output.push_str("mindmap\n");
+        output.push_str(&format!("  root(({}))\n", root));
+
+        // package (None = no package) -> modules, sorted by package and module name
+        let mut packages: BTreeMap<Option<&str>, Vec<&ModSection>> = BTreeMap::new();
+        for (_, file) in &project.files {
+            for section in &file.sections {
+                if let Section::Mod(module) = section {
+                    packages.entry(file.package.as_deref()).or_default().push(module);
+                }
+            }
+        }
+
+        for (package, mut modules) in packages {
+            modules.sort_by(|a, b| a.name.cmp(&b.name));
+            let indent = match package {
+                Some(package) => {
+                    output.push_str(&format!("    [{}]\n", package));
+                    "      "
+                }
+                None => "    ",
+            };
+            for module in modules {
+                output.push_str(&format!("{}(mod.{})\n", indent, module.name));
+                for symbol in module.schemas.iter().chain(&module.funcs) {
+                    output.push_str(&format!("{}  {}\n", indent, symbol));
+                }
+            }
+        }
+
+        output
+    }
+
     /// Export schema relationship graph
     pub fn export_schema_graph(&self, project: &ProjectAST) -> String {
         let mut output = String::from("---\ntitle: Schema Graph\n---\n");
@@ -232,47 +401,173 @@ impl MermaidExporter {
 
         // Add edges for relationships
         for (schema_id, schema) in &schemas {
-            match schema.kind.as_str() {
-                "edge" => {
-                    // edge: from -> to
-                    if !schema.from.is_empty() && !schema.to.is_empty() {
-                        let from_id = Self::sanitize_id(&schema.from);
-                        let to_id = Self::sanitize_id(&schema.to);
-                        output.push_str(&format!("    {} -.->|{}| {}\n",
-                            from_id, schema.name, to_id));
-                    }
+            Self::push_schema_relations(&mut output, schema_id, schema);
+        }
+
+        // Add styling
+        output.push_str("\n    classDef node fill:#d4e6f1,stroke:#2980b9\n");
+        output.push_str("    classDef edge fill:#d5f4e6,stroke:#27ae60\n");
+        output.push_str("    classDef boundary fill:#fdeaa8,stroke:#f39c12\n");
+        output.push_str("    classDef space fill:#e8daef,stroke:#8e44ad\n");
+
+        output
+    }
+
+    /// Export node schemas as an entity-relationship diagram. Fields become
+    /// attributes (`@unique` as a UK key, `@indexed` as a comment) and edge
+    /// schemas become many-to-many relationships.
+    pub fn export_schema_er(&self, project: &ProjectAST) -> String {
+        let mut output = String::from("---\ntitle: Entity Relationships\n---\n");
+        output.push_str("erDiagram\n");
+
+        let mut schemas = BTreeMap::new();
+        for (_, file) in &project.files {
+            for section in &file.sections {
+                if let Section::Schema(schema) = section {
+                    schemas.insert(schema.name.clone(), schema);
                 }
-                "boundary" => {
-                    // boundary: contains schemas in 'over'
-                    let boundary_id = Self::sanitize_id(schema_id);
-                    for over_schema in &schema.over {
-                        let over_id = Self::sanitize_id(over_schema);
-                        output.push_str(&format!("    {} -.-> {}\n", boundary_id, over_id));
-                    }
+            }
+        }
+
+        for schema in schemas.values().filter(|s| s.kind == "node") {
+            let entity = Self::sanitize_id(&schema.name);
+            let fields = schema.field_specs();
+            if fields.is_empty() {
+                output.push_str(&format!("    {}\n", entity));
+                continue;
+            }
+            output.push_str(&format!("    {} {{\n", entity));
+            for field in fields {
+                let mut line = format!(
+                    "        {} {}",
+                    Self::sanitize_er_word(&field.r#type),
+                    Self::sanitize_er_word(&field.name)
+                );
+                if field.has("unique") {
+                    line.push_str(" UK");
+                }
+                if field.has("indexed") {
+                    line.push_str(" \"indexed\"");
                 }
-                "space" => {
-                    // space: based on another schema
-                    if !schema.base.is_empty() {
-                        let base_id = Self::sanitize_id(&schema.base);
-                        let space_id = Self::sanitize_id(schema_id);
-                        output.push_str(&format!("    {} ==> {}\n", space_id, base_id));
+                output.push_str(&line);
+                output.push('\n');
+            }
+            output.push_str("    }\n");
+        }
+
+        for schema in schemas.values().filter(|s| s.kind == "edge") {
+            if schema.from.is_empty() || schema.to.is_empty() {
+                continue;
+            }
+            let from = schema.from.strip_prefix("schema.").unwrap_or(&schema.from);
+            let to = schema.to.strip_prefix("schema.").unwrap_or(&schema.to);
+            output.push_str(&format!(
+                "    {} }}o--o{{ {} : \"{}\"\n",
+                Self::sanitize_id(from),
+                Self::sanitize_id(to),
+                schema.name
+            ));
+        }
+
+        output
+    }
+
+    /// Export modules and schemas in one diagram, linking each module to the schemas it uses
+    pub fn export_overview(&self, project: &ProjectAST) -> String {
+        let mut output = String::from("---\ntitle: Architecture Overview\n---\n");
+        output.push_str("flowchart LR\n");
+
+        // Collect schemas and modules
+        let mut schemas = BTreeMap::new();
+        let mut modules = BTreeMap::new();
+        for (_, file) in &project.files {
+            for section in &file.sections {
+                match section {
+                    Section::Schema(s) => {
+                        schemas.insert(format!("schema.{}", s.name), s);
+                    }
+                    Section::Mod(m) => {
+                        modules.insert(format!("mod.{}", m.name), m);
                     }
+                    _ => {}
+                }
+            }
+        }
+
+        if schemas.is_empty() && modules.is_empty() {
+            output.push_str("    empty[No modules or schemas defined]\n");
+            return output;
+        }
+
+        // Add module nodes
+        for (mod_id, module) in &modules {
+            output.push_str(&format!("    {}[[\"{}\"]]:::module\n",
+                Self::sanitize_id(mod_id), module.name));
+        }
+
+        // Add schema nodes with kind/role
+        for (schema_id, schema) in &schemas {
+            let label = format!("{}<br/><small>{}/{}</small>",
+                schema.name, schema.kind, schema.role);
+            output.push_str(&format!("    {}[\"{}\"]{}\n",
+                Self::sanitize_id(schema_id),
+                label,
+                Self::get_schema_style(&schema.kind)));
+        }
+
+        // Add module -> schema usage edges
+        for (mod_id, module) in &modules {
+            let from_id = Self::sanitize_id(mod_id);
+            for schema_ref in &module.schemas {
+                let schema_id = Self::sanitize_id(schema_ref);
+                if !schemas.contains_key(schema_ref) {
+                    output.push_str(&format!("    {}[\"{}⚠\"]:::error\n",
+                        schema_id,
+                        schema_ref.strip_prefix("schema.").unwrap_or(schema_ref)));
                 }
-                _ => {}
+                output.push_str(&format!("    {} -->|uses| {}\n", from_id, schema_id));
             }
         }
 
+        // Add module dependency edges
+        for req in project.collect_normalized_requires() {
+            if modules.contains_key(&req.to_mod) {
+                output.push_str(&format!("    {} ==>|requires| {}\n",
+                    Self::sanitize_id(&req.from_mod),
+                    Self::sanitize_id(&req.to_mod)));
+            }
+        }
+
+        // Add schema relationship edges
+        for (schema_id, schema) in &schemas {
+            Self::push_schema_relations(&mut output, schema_id, schema);
+        }
+
         // Add styling
-        output.push_str("\n    classDef node fill:#d4e6f1,stroke:#2980b9\n");
+        output.push_str("\n    classDef module fill:#fadbd8,stroke:#c0392b\n");
+        output.push_str("    classDef node fill:#d4e6f1,stroke:#2980b9\n");
         output.push_str("    classDef edge fill:#d5f4e6,stroke:#27ae60\n");
         output.push_str("    classDef boundary fill:#fdeaa8,stroke:#f39c12\n");
         output.push_str("    classDef space fill:#e8daef,stroke:#8e44ad\n");
+        output.push_str("    classDef error fill:#ffdddd,stroke:#ff0000\n");
 
         output
     }
 
     /// Export all schemas and funcs used by a specific module
     pub fn export_module_detail(&self, module: &ModSection, project: &ProjectAST) -> String {
+        self.export_module_detail_collapsed(module, project, false, false)
+    }
+
+    /// Module detail with the module's schemas and/or funcs collapsed into a
+    /// single counted node each, for a high-level view of large modules.
+    pub fn export_module_detail_collapsed(
+        &self,
+        module: &ModSection,
+        project: &ProjectAST,
+        collapse_schemas: bool,
+        collapse_funcs: bool,
+    ) -> String {
         let mut output = String::from("---\n");
         output.push_str(&format!("title: Module - {}\n", module.name));
         output.push_str("---\n");
@@ -297,10 +592,25 @@ impl MermaidExporter {
 
         // Add module node
         let mod_id = "MOD";
-        output.push_str(&format!("    {}[[\"{}\"]]\n", mod_id, module.name));
+        if module.purpose.is_empty() {
+            output.push_str(&format!("    {}[[\"{}\"]]\n", mod_id, module.name));
+        } else {
+            output.push_str(&format!(
+                "    {}[[\"{}<br/><small>{}</small>\"]]\n",
+                mod_id,
+                module.name,
+                self.wrap_label(&module.purpose)
+            ));
+        }
 
         // Add schemas
-        for schema_ref in &module.schemas {
+        if collapse_schemas && !module.schemas.is_empty() {
+            output.push_str(&Self::collapsed_node("SCHEMAS", "schema", &module.schemas, |r| {
+                schemas.contains_key(r)
+            }));
+            output.push_str(&format!("    {} -.-> SCHEMAS\n", mod_id));
+        }
+        for schema_ref in module.schemas.iter().filter(|_| !collapse_schemas) {
             let schema_id = Self::sanitize_id(schema_ref);
             if let Some(schema) = schemas.get(schema_ref) {
                 output.push_str(&format!("    {}[\"schema: {}\"]:::schema\n",
@@ -313,7 +623,13 @@ impl MermaidExporter {
         }
 
         // Add funcs
-        for func_ref in &module.funcs {
+        if collapse_funcs && !module.funcs.is_empty() {
+            output.push_str(&Self::collapsed_node("FUNCS", "func", &module.funcs, |r| {
+                funcs.contains_key(r)
+            }));
+            output.push_str(&format!("    {} --> FUNCS\n", mod_id));
+        }
+        for func_ref in module.funcs.iter().filter(|_| !collapse_funcs) {
             let func_id = Self::sanitize_id(func_ref);
             if let Some(func) = funcs.get(func_ref) {
                 output.push_str(&format!("    {}[\"func: {}\"]:::func\n",
@@ -335,6 +651,81 @@ impl MermaidExporter {
 
     // Helper functions
 
+    /// One node standing for all of `refs`, e.g. `"funcs (5)"`; undefined
+    /// references are counted separately and flag the node as an error.
+    fn collapsed_node(
+        id: &str,
+        class: &str,
+        refs: &[String],
+        is_defined: impl Fn(&String) -> bool,
+    ) -> String {
+        let undefined = refs.iter().filter(|r| !is_defined(r)).count();
+        if undefined == 0 {
+            format!("    {}[\"{}s ({})\"]:::{}\n", id, class, refs.len(), class)
+        } else {
+            format!(
+                "    {}[\"{}s ({}, {} undefined ⚠)\"]:::error\n",
+                id,
+                class,
+                refs.len(),
+                undefined
+            )
+        }
+    }
+
+    /// Wrap a diagram in a ```` ```mermaid ```` fenced block for GitHub markdown.
+    /// The front-matter title, if any, becomes a `##` heading above the block.
+    pub fn wrap_markdown(&self, diagram: &str) -> String {
+        let mut title = None;
+        let mut body = diagram;
+        if let Some(rest) = diagram.strip_prefix("---\n") {
+            if let Some((front_matter, after)) = rest.split_once("---\n") {
+                title = front_matter
+                    .lines()
+                    .find_map(|line| line.strip_prefix("title:"))
+                    .map(str::trim);
+                body = after;
+            }
+        }
+
+        format!(
+            "## {}\n\n```mermaid\n{}\n```",
+            title.unwrap_or("Diagram"),
+            body.trim_end()
+        )
+    }
+
+    fn push_schema_relations(output: &mut String, schema_id: &str, schema: &SchemaSection) {
+        match schema.kind.as_str() {
+            "edge" => {
+                // edge: from -> to
+                if !schema.from.is_empty() && !schema.to.is_empty() {
+                    let from_id = Self::sanitize_id(&schema.from);
+                    let to_id = Self::sanitize_id(&schema.to);
+                    output.push_str(&format!("    {} -.->|{}| {}\n",
+                        from_id, schema.name, to_id));
+                }
+            }
+            "boundary" => {
+                // boundary: contains schemas in 'over'
+                let boundary_id = Self::sanitize_id(schema_id);
+                for over_schema in &schema.over {
+                    let over_id = Self::sanitize_id(over_schema);
+                    output.push_str(&format!("    {} -.-> {}\n", boundary_id, over_id));
+                }
+            }
+            "space" => {
+                // space: based on another schema
+                if !schema.base.is_empty() {
+                    let base_id = Self::sanitize_id(&schema.base);
+                    let space_id = Self::sanitize_id(schema_id);
+                    output.push_str(&format!("    {} ==> {}\n", space_id, base_id));
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn find_common_schemas(a: &[String], b: &[String]) -> Vec<String> {
         let set_a: HashSet<_> = a.iter().collect();
         b.iter()
@@ -347,6 +738,15 @@ impl MermaidExporter {
         id.replace('.', "_").replace('-', "_")
     }
 
+    /// ER attribute types and names may only contain word characters.
+    fn sanitize_er_word(s: &str) -> String {
+        let word: String = s
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+            .collect();
+        if word.is_empty() { "any".to_string() } else { word }
+    }
+
     fn escape_html(s: &str) -> String {
         s.replace('&', "&amp;")
             .replace('<', "&lt;")
@@ -354,6 +754,31 @@ impl MermaidExporter {
             .replace('"', "&quot;")
     }
 
+    /// Escaped label text, greedily wrapped at word boundaries when a label
+    /// width is set. A word longer than the width gets a line of its own.
+    fn wrap_label(&self, s: &str) -> String {
+        let Some(width) = self.label_width else {
+            return Self::escape_html(s);
+        };
+        let mut lines: Vec<String> = Vec::new();
+        let mut line = String::new();
+        for word in s.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+        lines
+            .iter()
+            .map(|line| Self::escape_html(line))
+            .collect::<Vec<_>>()
+            .join("<br/>")
+    }
+
     fn get_schema_style(kind: &str) -> &'static str {
         match kind {
             "node" => ":::node",
@@ -375,12 +800,11 @@ impl Default for MermaidExporter {
 mod tests {
     use super::*;
     use crate::ast::SurvFile;
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
     use std::path::PathBuf;
 
     fn file(path: &str, text: &str) -> (PathBuf, SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -429,6 +853,12 @@ pipeline = ["func.create_user", "func.save_user"]
         assert!(output.contains("f0"));
         assert!(output.contains("f1"));
         assert!(output.contains("f0 -->"));
+        assert!(!output.contains("classDef highlight"));
+
+        let highlighted =
+            exporter.export_pipeline_highlighted(module, &project, &["func.save_user".to_string()]);
+        assert!(highlighted.contains("classDef highlight"));
+        assert!(highlighted.contains("class f1 highlight"));
     }
 
     #[test]
@@ -466,6 +896,46 @@ funcs = []
         assert!(output.contains("-->"));
     }
 
+    #[test]
+    fn exports_module_mindmap() {
+        let files = vec![
+            file(
+                "backend/api.toml",
+                r#"
+package = "backend"
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user"]
+funcs = ["func.create_user"]
+"#,
+            ),
+            file(
+                "tools.toml",
+                r#"
+[mod.tools]
+purpose = "test"
+"#,
+            ),
+        ];
+
+        let project = ProjectAST::from_files(files);
+        let output = MermaidExporter::new().export_module_mindmap(&project, "demo");
+        let lines: Vec<&str> = output.lines().skip(3).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "mindmap",
+                "  root((demo))",
+                "    (mod.tools)",
+                "    [backend]",
+                "      (mod.api)",
+                "        schema.user",
+                "        func.create_user",
+            ]
+        );
+    }
+
     #[test]
     fn exports_schema_graph() {
         let files = vec![file(
@@ -497,4 +967,245 @@ to = "schema.post"
         assert!(output.contains("schema_post"));
         assert!(output.contains("node/data"));
     }
+
+    #[test]
+    fn exports_schema_er_diagram() {
+        let files = vec![file(
+            "test.toml",
+            r#"
+[schema.user]
+kind = "node"
+fields = { id = "uuid @unique", name = "string" }
+
+[schema.user.field.email]
+type = "string"
+unique = true
+indexed = true
+
+[schema.post]
+kind = "node"
+
+[schema.user_posts]
+kind = "edge"
+from = "schema.user"
+to = "schema.post"
+"#,
+        )];
+
+        let project = ProjectAST::from_files(files);
+        let output = MermaidExporter::new().export_schema_er(&project);
+
+        assert!(output.contains("erDiagram"));
+        assert!(output.contains("    user {\n"));
+        assert!(output.contains("        string email UK \"indexed\"\n"));
+        assert!(output.contains("        uuid id UK\n"));
+        assert!(output.contains("        string name\n"));
+        assert!(output.contains("    post\n"));
+        assert!(output.contains("    user }o--o{ post : \"user_posts\"\n"));
+    }
+
+    #[test]
+    fn exports_overview_with_usage_edges() {
+        let files = vec![file(
+            "test.toml",
+            r#"
+[schema.user]
+kind = "node"
+role = "data"
+type = "User"
+
+[mod.user_api]
+purpose = "User API"
+schemas = ["schema.user", "schema.missing"]
+funcs = []
+"#,
+        )];
+
+        let project = ProjectAST::from_files(files);
+        let exporter = MermaidExporter::new();
+        let output = exporter.export_overview(&project);
+
+        assert!(output.contains("mod_user_api[[\"user_api\"]]:::module"));
+        assert!(output.contains("mod_user_api -->|uses| schema_user"));
+        assert!(output.contains("schema_missing[\"missing⚠\"]:::error"));
+    }
+
+    #[test]
+    fn exports_branching_pipeline() {
+        let files = vec![file(
+            "test.toml",
+            r#"
+[mod.api]
+purpose = "test"
+pipeline = "func.a -> { func.b, func.c } -> func.d"
+"#,
+        )];
+
+        let project = ProjectAST::from_files(files);
+        let exporter = MermaidExporter::new();
+        let module = project.files[0]
+            .1
+            .sections
+            .iter()
+            .find_map(|s| match s {
+                Section::Mod(m) => Some(m),
+                _ => None,
+            })
+            .unwrap();
+
+        let output = exporter.export_pipeline(module, &project);
+        assert!(output.contains("f0 --> f1"));
+        assert!(output.contains("f0 --> f2"));
+        assert!(output.contains("f1 --> f3"));
+        assert!(output.contains("f2 --> f3"));
+        assert!(!output.contains("f1 --> f2"));
+    }
+
+    #[test]
+    fn collapses_module_detail_schemas_and_funcs() {
+        let files = vec![file(
+            "api.toml",
+            r#"
+[schema.user]
+kind = "node"
+
+[schema.order]
+kind = "node"
+
+[func.create_user]
+intent = "test"
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user", "schema.order"]
+funcs = ["func.create_user", "func.missing"]
+"#,
+        )];
+        let project = ProjectAST::from_files(files);
+        let module = match &project.files[0].1.sections[..] {
+            [.., Section::Mod(module)] => module.clone(),
+            _ => panic!("expected mod.api last"),
+        };
+        let exporter = MermaidExporter::new();
+
+        let schemas_only = exporter.export_module_detail_collapsed(&module, &project, true, false);
+        assert!(schemas_only.contains("SCHEMAS[\"schemas (2)\"]:::schema"));
+        assert!(!schemas_only.contains("schema: user"));
+        assert!(schemas_only.contains("func: create_user"));
+
+        let both = exporter.export_module_detail_collapsed(&module, &project, true, true);
+        assert!(both.contains("FUNCS[\"funcs (2, 1 undefined ⚠)\"]:::error"));
+        assert!(both.contains("MOD --> FUNCS"));
+    }
+
+    #[test]
+    fn wraps_long_labels_at_label_width() {
+        let files = vec![file(
+            "api.toml",
+            r#"
+[func.create]
+intent = "Validate the request & build a user record"
+
+[mod.api]
+purpose = "User accounts API"
+funcs = ["func.create"]
+pipeline = ["func.create"]
+"#,
+        )];
+        let project = ProjectAST::from_files(files);
+        let module = match &project.files[0].1.sections[..] {
+            [.., Section::Mod(module)] => module.clone(),
+            _ => panic!("expected mod.api last"),
+        };
+
+        let unwrapped = MermaidExporter::new().export_pipeline(&module, &project);
+        assert!(unwrapped.contains("<small>Validate the request &amp; build a user record</small>"));
+
+        let exporter = MermaidExporter::new().with_label_width(16);
+        let pipeline = exporter.export_pipeline(&module, &project);
+        assert!(pipeline
+            .contains("<small>Validate the<br/>request &amp; build<br/>a user record</small>"));
+        let detail = exporter.export_module_detail(&module, &project);
+        assert!(detail.contains("MOD[[\"api<br/><small>User accounts<br/>API</small>\"]]"));
+    }
+
+    #[test]
+    fn wraps_diagram_in_markdown_fence() {
+        let exporter = MermaidExporter::new();
+        let wrapped =
+            exporter.wrap_markdown("---\ntitle: Module Dependencies\n---\nflowchart TD\n  a --> b\n\n");
+        assert_eq!(
+            wrapped,
+            "## Module Dependencies\n\n```mermaid\nflowchart TD\n  a --> b\n```"
+        );
+
+        let untitled = exporter.wrap_markdown("flowchart LR\n  x --> y\n");
+        assert!(untitled.starts_with("## Diagram\n\n```mermaid\nflowchart LR\n"));
+    }
+
+    #[test]
+    fn filters_deploy_pipeline_by_target_kind() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.target.staging]
+kind = "staging"
+
+[deploy.target.prod]
+kind = "production"
+
+[deploy.job.build]
+runs = ["npm run build"]
+
+[deploy.job.deploy_staging]
+requires = ["job.build"]
+runs = ["kubectl apply"]
+uses_target = "target.staging"
+
+[deploy.job.deploy_prod]
+requires = ["job.build"]
+runs = ["kubectl apply"]
+uses_target = "target.prod"
+"#,
+        )
+        .unwrap();
+        let exporter = MermaidExporter::new();
+
+        let full = exporter.export_deploy_pipeline(&deploy);
+        assert!(full.contains("deploy_staging["));
+
+        let prod = exporter.export_deploy_pipeline_for_kind(&deploy, Some("production"));
+        assert!(prod.contains("deploy_prod["));
+        assert!(prod.contains("build["));
+        assert!(prod.contains("build --> deploy_prod"));
+        assert!(!prod.contains("deploy_staging"));
+    }
+
+    #[test]
+    fn groups_deploy_jobs_by_stage() {
+        let deploy = crate::deploy::parser::parse_deploy_str(
+            r#"
+[deploy.pipeline]
+name = "app"
+stages = ["build", "release"]
+
+[deploy.job.build]
+runs = ["npm run build"]
+
+[deploy.job.publish]
+requires = ["job.build"]
+runs = ["npm publish"]
+stage = "release"
+"#,
+        )
+        .unwrap();
+        let exporter = MermaidExporter::new();
+
+        let grouped = exporter.export_deploy_pipeline_grouped(&deploy, None, true);
+        assert!(grouped.contains(
+            "    subgraph stage_build[\"build\"]\n        build[\"build\"]\n    end\n"
+        ));
+        assert!(grouped.contains("    subgraph stage_release[\"release\"]\n        publish["));
+        assert!(grouped.contains("    build --> publish\n"));
+        assert!(!exporter.export_deploy_pipeline(&deploy).contains("subgraph"));
+    }
 }
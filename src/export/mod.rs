@@ -1,5 +1,9 @@
 pub mod html;
+pub mod matrix;
 pub mod mermaid;
+pub mod openapi;
 
 pub use html::HtmlExporter;
+pub use matrix::SchemaMatrix;
 pub use mermaid::MermaidExporter;
+pub use openapi::OpenApiExporter;
@@ -0,0 +1,264 @@
+use crate::ast::{FieldSpec, FuncSection, ModSection, SchemaSection, Section, SurvFile};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Best-effort OpenAPI 3 skeleton for a module that describes an HTTP API.
+///
+/// Every pipeline chain becomes one `POST /<entry func>` operation: the
+/// request body is the entry func's input and the response is the output of
+/// the chain's last stage. Schema `fields` become component properties.
+pub struct OpenApiExporter;
+
+impl OpenApiExporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// A module is exported when it has `kind = "http"` or, by convention,
+    /// its name ends in `_http_api`.
+    pub fn is_http_module(module: &ModSection) -> bool {
+        module.kind == "http" || module.name.ends_with("_http_api")
+    }
+
+    pub fn export_module(&self, module: &ModSection, file: &SurvFile) -> Result<Value, String> {
+        if !Self::is_http_module(module) {
+            return Err(format!(
+                "mod.{} is not marked as an HTTP API (add kind = \"http\" to the module)",
+                module.name
+            ));
+        }
+
+        let mut funcs = BTreeMap::new();
+        let mut schemas = BTreeMap::new();
+        for section in &file.sections {
+            match section {
+                Section::Func(func) => {
+                    funcs.insert(format!("func.{}", func.name), func);
+                }
+                Section::Schema(schema) => {
+                    schemas.insert(format!("schema.{}", schema.name), schema);
+                }
+                _ => {}
+            }
+        }
+
+        let mut paths = Map::new();
+        let mut referenced = BTreeSet::new();
+        for chain in &module.pipeline_chains {
+            let (Some(first), Some(last)) = (chain.first(), chain.last()) else {
+                continue;
+            };
+            let outputs: Vec<&String> = last
+                .iter()
+                .filter_map(|step| funcs.get(step))
+                .flat_map(|func| &func.output)
+                .collect();
+            for entry in first.iter().filter_map(|step| funcs.get(step)) {
+                referenced.extend(entry.input.iter().chain(outputs.iter().copied()));
+                paths.insert(format!("/{}", entry.name), Self::operation(entry, &outputs));
+            }
+        }
+        if paths.is_empty() {
+            return Err(format!("mod.{} has no pipeline funcs to export", module.name));
+        }
+
+        Ok(json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": module.name,
+                "description": module.purpose,
+                "version": "0.1.0",
+            },
+            "paths": paths,
+            "components": { "schemas": Self::components(&referenced, &schemas) },
+        }))
+    }
+
+    fn operation(func: &FuncSection, outputs: &[&String]) -> Value {
+        let mut operation = json!({
+            "operationId": func.name,
+            "summary": func.intent,
+            "responses": { "200": { "description": "Success" } },
+        });
+        if let Some(body) = Self::body_schema(func.input.iter()) {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": body } },
+            });
+        }
+        if let Some(body) = Self::body_schema(outputs.iter().copied()) {
+            operation["responses"]["200"]["content"] = json!({
+                "application/json": { "schema": body },
+            });
+        }
+        json!({ "post": operation })
+    }
+
+    /// One schema is referenced directly; several are combined with `allOf`.
+    fn body_schema<'a>(schemas: impl Iterator<Item = &'a String>) -> Option<Value> {
+        let refs: Vec<Value> = schemas.filter_map(|id| Self::schema_ref(id)).collect();
+        match refs.len() {
+            0 => None,
+            1 => refs.into_iter().next(),
+            _ => Some(json!({ "allOf": refs })),
+        }
+    }
+
+    fn schema_ref(id: &str) -> Option<Value> {
+        let name = id.strip_prefix("schema.")?;
+        Some(json!({ "$ref": format!("#/components/schemas/{}", name) }))
+    }
+
+    /// Components for the referenced schemas and any schemas their fields
+    /// point at, transitively.
+    fn components(
+        referenced: &BTreeSet<&String>,
+        schemas: &BTreeMap<String, &SchemaSection>,
+    ) -> Map<String, Value> {
+        let mut components = Map::new();
+        let mut pending: Vec<String> = referenced.iter().map(|id| id.to_string()).collect();
+        while let Some(id) = pending.pop() {
+            let Some(schema) = schemas.get(&id) else {
+                continue;
+            };
+            if components.contains_key(&schema.name) {
+                continue;
+            }
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for field in schema.field_specs() {
+                let (property, optional) = Self::field_schema(&field);
+                pending.extend(Self::nested_ref(&field.r#type));
+                if !optional {
+                    required.push(field.name.clone());
+                }
+                properties.insert(field.name, property);
+            }
+            let mut component = json!({ "type": "object", "properties": properties });
+            if !required.is_empty() {
+                component["required"] = json!(required);
+            }
+            if !schema.label.is_empty() {
+                component["description"] = json!(schema.label);
+            }
+            components.insert(schema.name.clone(), component);
+        }
+        components
+    }
+
+    /// The JSON schema for a field, and whether it is optional (`type?`).
+    fn field_schema(field: &FieldSpec) -> (Value, bool) {
+        let ty = field.r#type.trim();
+        let (ty, optional) = match ty.strip_suffix('?') {
+            Some(inner) => (inner, true),
+            None => (ty, false),
+        };
+        (Self::type_schema(ty), optional)
+    }
+
+    fn type_schema(ty: &str) -> Value {
+        let item = ty
+            .strip_suffix("[]")
+            .or_else(|| ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')));
+        if let Some(item) = item {
+            return json!({ "type": "array", "items": Self::type_schema(item.trim()) });
+        }
+        match ty {
+            "string" | "String" => json!({ "type": "string" }),
+            "int" | "integer" => json!({ "type": "integer" }),
+            "float" | "number" => json!({ "type": "number" }),
+            "bool" | "boolean" => json!({ "type": "boolean" }),
+            "uuid" => json!({ "type": "string", "format": "uuid" }),
+            "timestamp" => json!({ "type": "string", "format": "date-time" }),
+            "date" => json!({ "type": "string", "format": "date" }),
+            "json" | "object" => json!({ "type": "object" }),
+            other => Self::schema_ref(other)
+                .unwrap_or_else(|| json!({ "description": format!("Surv type `{}`", other) })),
+        }
+    }
+
+    fn nested_ref(ty: &str) -> Option<String> {
+        let name = ty.trim_matches(|c| c == '[' || c == ']' || c == '?');
+        name.starts_with("schema.").then(|| name.to_string())
+    }
+}
+
+impl Default for OpenApiExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_surv_str;
+
+    #[test]
+    fn exports_pipeline_entries_as_operations() {
+        let file = parse_surv_str(
+            r#"
+[schema.CreateUserRequest]
+kind = "node"
+fields = { email = "string", name = "string?" }
+
+[schema.User]
+kind = "node"
+fields = { id = "uuid @unique", tags = "string[]" }
+
+[schema.CreateUserResponse]
+kind = "node"
+fields = { user = "schema.User" }
+
+[func.createUser]
+intent = "Create a user"
+input = ["schema.CreateUserRequest"]
+output = ["schema.User"]
+
+[func.buildResponse]
+intent = "Wrap the user"
+input = ["schema.User"]
+output = ["schema.CreateUserResponse"]
+
+[mod.user_http_api]
+purpose = "Users over HTTP"
+pipeline = ["func.createUser", "func.buildResponse"]
+
+[mod.user_domain]
+purpose = "Not an API"
+pipeline = ["func.createUser"]
+"#,
+        )
+        .unwrap();
+        let module = |name: &str| {
+            file.sections
+                .iter()
+                .find_map(|section| match section {
+                    Section::Mod(m) if m.name == name => Some(m),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        let exporter = OpenApiExporter::new();
+
+        let doc = exporter.export_module(module("user_http_api"), &file).unwrap();
+        let op = &doc["paths"]["/createUser"]["post"];
+        assert_eq!(op["summary"], "Create a user");
+        assert_eq!(
+            op["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/CreateUserRequest"
+        );
+        assert_eq!(
+            op["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/CreateUserResponse"
+        );
+        assert!(doc["paths"].get("/buildResponse").is_none());
+
+        let schemas = &doc["components"]["schemas"];
+        assert_eq!(schemas["CreateUserRequest"]["required"], json!(["email"]));
+        assert_eq!(schemas["User"]["properties"]["id"]["format"], "uuid");
+        assert_eq!(schemas["User"]["properties"]["tags"]["type"], "array");
+
+        assert!(exporter.export_module(module("user_domain"), &file).is_err());
+    }
+}
@@ -49,12 +49,15 @@ pub fn parse_imports_with_alias(
             match parse_import_entry(&raw.target) {
                 Ok((package_name, alias)) => {
                     if !known_packages.is_empty() && !known_packages.contains_key(&package_name) {
-                        diagnostics.push(Diagnostic {
-                            severity: "error".into(),
-                            kind: "E_IMPORT_UNKNOWN_PACKAGE".into(),
-                            message: format!("Unknown import package '{}'", package_name),
-                            location: path.display().to_string(),
-                        });
+                        diagnostics.push(
+                            Diagnostic::new(
+                                "error",
+                                "E_IMPORT_UNKNOWN_PACKAGE",
+                                format!("Unknown import package '{}'", package_name),
+                                path.display().to_string(),
+                            )
+                            .with_package(package_name.clone()),
+                        );
                         continue;
                     }
                     imports.push(ImportEntry {
@@ -62,12 +65,12 @@ pub fn parse_imports_with_alias(
                         alias: alias.or_else(|| raw.alias.clone()),
                     });
                 }
-                Err(kind) => diagnostics.push(Diagnostic {
-                    severity: "error".into(),
+                Err(kind) => diagnostics.push(Diagnostic::new(
+                    "error",
                     kind,
-                    message: format!("Invalid import syntax '{}'", raw.target),
-                    location: path.display().to_string(),
-                }),
+                    format!("Invalid import syntax '{}'", raw.target),
+                    path.display().to_string(),
+                )),
             }
         }
 
@@ -97,8 +100,7 @@ fn parse_import_entry(raw: &str) -> Result<(String, Option<String>), String> {
 mod tests {
     use super::*;
     use crate::manifest::{Manifest, PathsSection, ProjectSection};
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
 
     fn manifest_with_packages(packages: &[&str]) -> Manifest {
         let mut map = HashMap::new();
@@ -115,6 +117,7 @@ mod tests {
         Manifest {
             project: ProjectSection {
                 name: "test".into(),
+                strict_namespaces: false,
             },
             paths: PathsSection {
                 ir_root: "ir".into(),
@@ -124,7 +127,7 @@ mod tests {
     }
 
     fn file(path: &str, text: &str) -> (PathBuf, SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -0,0 +1,89 @@
+use survibe_parser_rs::samples::{find_sample, SAMPLES};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn run_init(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut sample_name = "minimal";
+    let mut output: Option<PathBuf> = None;
+    let mut force = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_init_usage();
+                return Ok(());
+            }
+            "--sample" => {
+                if i + 1 < args.len() {
+                    sample_name = &args[i + 1];
+                    i += 2;
+                } else {
+                    return Err("--sample requires a name (minimal, crud, deploy)".into());
+                }
+            }
+            "--force" => {
+                force = true;
+                i += 1;
+            }
+            other if other.starts_with('-') => {
+                return Err(format!("Unknown option: {}", other).into());
+            }
+            path => {
+                output = Some(PathBuf::from(path));
+                i += 1;
+            }
+        }
+    }
+
+    let Some(sample) = find_sample(sample_name) else {
+        let names: Vec<&str> = SAMPLES.iter().map(|s| s.name).collect();
+        return Err(format!(
+            "Unknown sample: {} (expected one of: {})",
+            sample_name,
+            names.join(", ")
+        )
+        .into());
+    };
+
+    let path = output.unwrap_or_else(|| PathBuf::from(sample.file_name));
+    if path.exists() && !force {
+        return Err(format!("{} already exists (use --force to overwrite)", path.display()).into());
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, sample.contents)?;
+
+    println!("Wrote {} sample to {}", sample.name, path.display());
+    if sample.name == "deploy" {
+        println!("Next: surc deploy-check {}", path.display());
+    } else {
+        println!("Next: surc check {}", path.display());
+    }
+    Ok(())
+}
+
+fn print_init_usage() {
+    eprintln!("Usage: surc init [<path>] [--sample <name>] [--force]");
+    eprintln!();
+    eprintln!("Write a starter IR file. Without --sample, a minimal scaffold.");
+    eprintln!();
+    eprintln!("Samples:");
+    for sample in SAMPLES {
+        eprintln!(
+            "  {:<10} {} [default path: {}]",
+            sample.name, sample.description, sample.file_name
+        );
+    }
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --sample <name>    Which sample to write [default: minimal]");
+    eprintln!("  --force            Overwrite the output file if it exists");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc init");
+    eprintln!("  surc init --sample crud");
+    eprintln!("  surc init design/deploy.toml --sample deploy");
+}
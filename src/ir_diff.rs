@@ -0,0 +1,151 @@
+use crate::ast::SurvFile;
+use crate::checker::FileIndex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Structural difference between two versions of a Surv IR file, keyed by
+/// symbol id (`schema.x`, `func.y`, `mod.z`).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IrDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<SymbolChange>,
+}
+
+impl IrDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// A symbol present in both versions whose definition changed.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolChange {
+    pub id: String,
+    pub fields: Vec<FieldChange>,
+}
+
+/// One changed field, with its old and new value as JSON (`null` if unset).
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// Compare the schemas, funcs and mods of `old` and `new`.
+pub fn diff_surv_files(old: &SurvFile, new: &SurvFile) -> IrDiff {
+    let old = symbol_values(old);
+    let new = symbol_values(new);
+
+    let mut diff = IrDiff::default();
+    for (id, old_value) in &old {
+        match new.get(id) {
+            None => diff.removed.push(id.clone()),
+            Some(new_value) => {
+                let fields = diff_fields(old_value, new_value);
+                if !fields.is_empty() {
+                    diff.modified.push(SymbolChange {
+                        id: id.clone(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    diff.added = new.keys().filter(|id| !old.contains_key(*id)).cloned().collect();
+    diff
+}
+
+fn symbol_values(file: &SurvFile) -> BTreeMap<String, Value> {
+    let index = FileIndex::new(file);
+    let schemas = index.schemas.iter().map(|(id, s)| (id.clone(), serde_json::to_value(s)));
+    let funcs = index.funcs.iter().map(|(id, f)| (id.clone(), serde_json::to_value(f)));
+    let mods = index.mods.iter().map(|(id, m)| (id.clone(), serde_json::to_value(m)));
+    schemas
+        .chain(funcs)
+        .chain(mods)
+        .filter_map(|(id, value)| value.ok().map(|value| (id, value)))
+        .collect()
+}
+
+fn diff_fields(old: &Value, new: &Value) -> Vec<FieldChange> {
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Vec::new();
+    };
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    let mut fields: Vec<FieldChange> = keys
+        .into_iter()
+        .filter(|key| old.get(*key) != new.get(*key))
+        .map(|key| FieldChange {
+            field: key.clone(),
+            old: old.get(key).cloned().unwrap_or(Value::Null),
+            new: new.get(key).cloned().unwrap_or(Value::Null),
+        })
+        .collect();
+    // `pipeline` is flattened from the chains; only report the chains on
+    // their own when the steps are unchanged (e.g. a branch was regrouped)
+    if fields.iter().any(|f| f.field == "pipeline") {
+        fields.retain(|f| f.field != "pipeline_chains");
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_surv_str;
+
+    #[test]
+    fn reports_added_removed_and_changed_fields() {
+        let old = parse_surv_str(
+            r#"
+[schema.user]
+kind = "node"
+
+[schema.legacy_user]
+kind = "node"
+
+[func.create_user]
+intent = "Create"
+input = ["schema.user"]
+output = ["schema.user"]
+
+[mod.users]
+purpose = "Users"
+pipeline = ["func.create_user"]
+"#,
+        )
+        .unwrap();
+        let new = parse_surv_str(
+            r#"
+[schema.user]
+kind = "node"
+
+[schema.audit]
+kind = "node"
+
+[func.create_user]
+intent = "Create a user"
+input = ["schema.user", "schema.audit"]
+output = ["schema.user"]
+
+[mod.users]
+purpose = "Users"
+pipeline = ["func.create_user"]
+"#,
+        )
+        .unwrap();
+
+        let diff = diff_surv_files(&old, &new);
+        assert_eq!(diff.added, vec!["schema.audit"]);
+        assert_eq!(diff.removed, vec!["schema.legacy_user"]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].id, "func.create_user");
+        let fields: Vec<&str> = diff.modified[0].fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, vec!["input", "intent"]);
+        assert!(diff_surv_files(&new, &new).is_empty());
+    }
+}
@@ -6,29 +6,52 @@ pub mod diagnostic;
 pub mod diff_impl;
 pub mod export;
 pub mod imports;
+pub mod ir_diff;
 pub mod loader;
 pub mod manifest;
+pub mod merge;
 pub mod package;
 pub mod project;
 pub mod project_checker;
+pub mod rules;
+pub mod samples;
+pub mod schema_spec;
+pub mod stats;
 mod simple_toml;
+pub mod suggest;
 pub mod symbol;
+pub mod text_table;
+pub mod writer;
 
 pub mod parser;
 
 pub use ast::*;
-pub use checker::{check_surv_ast, check_surv_file};
-pub use deploy::{check_deploy_file, parse_deploy_file};
-pub use diagnostic::Diagnostic;
-pub use export::{HtmlExporter, MermaidExporter};
+pub use checker::{
+    check_boundary_overlap, check_duplicate_funcs, check_impl_bindings, check_impl_langs,
+    check_intent_order, check_meta_version, check_module_owners, check_module_pipelines,
+    check_surv_ast, check_surv_file,
+};
+pub use deploy::{
+    check_deploy_file, check_deploy_job, parse_deploy_file, parse_deploy_file_from_path,
+    parse_deploy_str,
+};
+pub use diagnostic::{render_junit, Diagnostic};
+pub use export::{HtmlExporter, MermaidExporter, OpenApiExporter, SchemaMatrix};
 pub use imports::{parse_imports_with_alias, FileImportContext, ImportEntry};
 pub use loader::load_project;
 pub use manifest::Manifest;
 pub use package::{assign_packages_to_files, PackageAssignment};
-pub use parser::{parse_file, parse_surv_file, parse_surv_ir};
+pub use parser::{
+    parse_file, parse_surv_file, parse_surv_file_with_unknown_keys, parse_surv_ir, parse_surv_str,
+    parse_surv_str_in_source_order, sort_sections_by_source,
+};
 pub use project::{ModRef, NormalizedRequire, ProjectAST};
-pub use project_checker::check_project;
+pub use project_checker::{
+    check_duplicate_funcs_in_project, check_empty_packages, check_package_dependencies,
+    check_project, check_self_dependent_packages,
+};
 pub use symbol::{
-    build_symbol_table, build_symbol_table_with_packages, resolve_schema_and_func_references,
+    build_symbol_table, build_symbol_table_with_packages, resolve_project_references,
+    resolve_schema_and_func_references, resolve_to_fq_names, reverse_dependency_closure,
     SymbolEntry, SymbolKind, SymbolTable,
 };
@@ -4,8 +4,17 @@ use crate::project::ProjectAST;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
+/// Parse timing for one file, collected by `load_project_profiled`.
+#[derive(Debug, Clone)]
+pub struct FileLoadStats {
+    pub path: PathBuf,
+    pub parse_time: Duration,
+    pub sections: usize,
+}
+
 pub fn load_manifest(path: &Path) -> Result<Manifest, Box<dyn std::error::Error>> {
     let text = fs::read_to_string(path)?;
     let manifest: Manifest = toml::from_str(&text)?;
@@ -29,6 +38,13 @@ fn collect_ir_files(ir_root: &Path) -> io::Result<Vec<PathBuf>> {
 }
 
 pub fn load_project(manifest_path: &Path) -> Result<ProjectAST, Box<dyn std::error::Error>> {
+    load_project_profiled(manifest_path).map(|(project, _)| project)
+}
+
+/// Like `load_project`, also returning how long each file took to parse.
+pub fn load_project_profiled(
+    manifest_path: &Path,
+) -> Result<(ProjectAST, Vec<FileLoadStats>), Box<dyn std::error::Error>> {
     let manifest = load_manifest(manifest_path)?;
     let manifest_dir = manifest_path
         .parent()
@@ -38,13 +54,49 @@ pub fn load_project(manifest_path: &Path) -> Result<ProjectAST, Box<dyn std::err
 
     let files = collect_ir_files(&ir_root)?;
     let mut parsed_files = Vec::new();
+    let mut stats = Vec::new();
 
     for path in files {
+        let start = Instant::now();
         match parse_file(&path) {
-            Ok(file) => parsed_files.push((path, file)),
+            Ok(file) => {
+                stats.push(FileLoadStats {
+                    path: path.clone(),
+                    parse_time: start.elapsed(),
+                    sections: file.sections.len(),
+                });
+                parsed_files.push((path, file));
+            }
             Err(err) => return Err(Box::new(err)),
         }
     }
 
-    Ok(ProjectAST::from_files(parsed_files))
+    Ok((ProjectAST::from_files(parsed_files), stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_every_loaded_file() {
+        let dir = std::env::temp_dir().join(format!("surv-loader-profile-{}", std::process::id()));
+        fs::create_dir_all(dir.join("ir")).unwrap();
+        fs::write(dir.join("surv.toml"), "[project]\nname = \"demo\"\n\n[paths]\nir_root = \"ir\"\n")
+            .unwrap();
+        fs::write(dir.join("ir/a.toml"), "[mod.a]\npurpose = \"x\"\n\n[mod.b]\npurpose = \"y\"\n")
+            .unwrap();
+        fs::write(dir.join("ir/c.toml"), "[mod.c]\npurpose = \"z\"\n").unwrap();
+
+        let (project, mut stats) = load_project_profiled(&dir.join("surv.toml")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(project.files.len(), 2);
+        stats.sort_by(|a, b| a.path.cmp(&b.path));
+        let sections: Vec<(&str, usize)> = stats
+            .iter()
+            .map(|stat| (stat.path.file_name().unwrap().to_str().unwrap(), stat.sections))
+            .collect();
+        assert_eq!(sections, [("a.toml", 2), ("c.toml", 1)]);
+    }
 }
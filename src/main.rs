@@ -2,22 +2,51 @@ use std::env;
 use std::error::Error;
 use std::fs::File;
 use std::io::{self, Read, Write};
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
 
 use survibe_parser_rs::{
-    check_deploy_file, check_project, check_surv_file, load_project, parse_deploy_file,
-    parse_surv_file, HtmlExporter, MermaidExporter, ProjectAST, Section,
+    build_symbol_table, check_boundary_overlap, check_deploy_file, check_deploy_job,
+    check_duplicate_funcs, check_duplicate_funcs_in_project,
+    check_empty_packages, check_impl_bindings, check_impl_langs, check_intent_order,
+    check_meta_version, check_module_owners, check_module_pipelines, check_package_dependencies,
+    check_project, check_self_dependent_packages, check_surv_file, load_project, parse_deploy_file_from_path, parse_surv_file,
+    parse_surv_file_with_unknown_keys, render_junit, resolve_project_references, resolve_to_fq_names, reverse_dependency_closure, sort_sections_by_source,
+    Diagnostic, HtmlExporter, MermaidExporter, OpenApiExporter, ProjectAST, Section,
 };
-use survibe_parser_rs::codegen::{GitHubActionsGenerator, GitLabCIGenerator};
+use survibe_parser_rs::codegen;
+use survibe_parser_rs::deploy::ast::DeployFile;
+use survibe_parser_rs::loader::load_project_profiled;
+use survibe_parser_rs::suggest::closest_match;
+use survibe_parser_rs::text_table::TextTable;
 
 mod status_commands;
 mod deps_commands;
 mod split_commands;
 mod query_commands;
+mod symbols_commands;
+mod stats_commands;
+mod merge_commands;
+mod check_all_commands;
+mod diff_commands;
+mod markers;
+mod rules_commands;
+mod init_commands;
+mod verify_commands;
 use status_commands::run_status;
 use deps_commands::run_deps;
 use split_commands::run_split;
 use query_commands::{run_refs, run_slice, run_trace};
+use symbols_commands::run_symbols;
+use stats_commands::run_stats;
+use merge_commands::run_merge;
+use check_all_commands::run_check_all;
+use diff_commands::run_diff;
+use rules_commands::run_rules;
+use init_commands::run_init;
+use verify_commands::run_verify;
 
 fn main() {
     if let Err(err) = run() {
@@ -27,7 +56,8 @@ fn main() {
 }
 
 fn run() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    markers::init(take_ascii_flag(&mut args));
 
     if args.len() < 2 {
         print_usage();
@@ -41,47 +71,93 @@ fn run() -> Result<(), Box<dyn Error>> {
         }
         "parse" => {
             if args.len() >= 3 && (args[2] == "--help" || args[2] == "-h") {
-                eprintln!("Usage: surc parse <file.toml>");
+                eprintln!("Usage: surc parse <file.toml> [--strict] [--source-order]");
+                eprintln!("       surc parse <surv.toml> --project [--resolve]");
                 eprintln!();
                 eprintln!("Parse a Surv IR file and output its AST as JSON.");
                 eprintln!();
                 eprintln!("Arguments:");
                 eprintln!("  <file.toml>    Path to a single Surv IR file");
+                eprintln!();
+                eprintln!("Options:");
+                eprintln!("  --strict       Fail if the file contains unknown keys");
+                eprintln!("  --source-order Keep sections in declaration order instead of grouping by kind");
+                eprintln!("  --project      Treat the argument as a manifest and output every file, keyed by path");
+                eprintln!("  --resolve      With --project, rewrite references to fully-qualified names");
                 return Ok(());
             }
             if args.len() < 3 {
-                eprintln!("Usage: surc parse <file.toml>");
+                eprintln!("Usage: surc parse <file.toml> [--strict]");
                 eprintln!();
                 eprintln!("Parse a Surv IR file and output its AST as JSON.");
                 std::process::exit(1);
             }
-            run_parse(&args[2])
+            if args[3..].iter().any(|a| a == "--project") {
+                let resolve = args[3..].iter().any(|a| a == "--resolve");
+                return run_parse_project(&args[2], resolve);
+            }
+            let strict = args[3..].iter().any(|a| a == "--strict");
+            let source_order = args[3..].iter().any(|a| a == "--source-order");
+            run_parse(&args[2], strict, source_order)
         }
         "check" => {
             if args.len() < 3 {
-                eprintln!("Usage: surc check <file.toml>");
+                eprintln!("Usage: surc check <file.toml> [--strict] [--require-impl] [--impl-lang] [--lint-intent] [--no-lint-pipeline] [--check-version] [--lint-boundaries] [--require-owners] [--lint-duplicates] [--format text|junit]");
                 std::process::exit(1);
             }
-            run_check(&args[2])
+            let strict = args[3..].iter().any(|a| a == "--strict");
+            let lints = CheckLints {
+                require_impl: args[3..].iter().any(|a| a == "--require-impl"),
+                impl_lang: args[3..].iter().any(|a| a == "--impl-lang"),
+                intent: args[3..].iter().any(|a| a == "--lint-intent"),
+                pipeline: !args[3..].iter().any(|a| a == "--no-lint-pipeline"),
+                version: args[3..].iter().any(|a| a == "--check-version"),
+                boundaries: args[3..].iter().any(|a| a == "--lint-boundaries"),
+                owners: args[3..].iter().any(|a| a == "--require-owners"),
+                duplicates: args[3..].iter().any(|a| a == "--lint-duplicates"),
+            };
+            let junit = junit_format(&args[3..])?;
+            run_check(&args[2], strict, &lints, junit)
         }
         "project-check" => {
             if args.len() < 3 {
-                eprintln!("Usage: surc project-check <surv.toml>");
+                eprintln!("Usage: surc project-check <surv.toml> [--cross-package-only] [--changed-since <rev>] [--lint-duplicates] [--profile] [--format text|junit]");
                 std::process::exit(1);
             }
-            run_project_check(&args[2])
+            let cross_package_only = args[3..].iter().any(|a| a == "--cross-package-only");
+            let profile = args[3..].iter().any(|a| a == "--profile");
+            let duplicates = args[3..].iter().any(|a| a == "--lint-duplicates");
+            let junit = junit_format(&args[3..])?;
+            let changed_since = args[3..]
+                .iter()
+                .position(|a| a == "--changed-since")
+                .map(|i| args.get(i + 4).ok_or("--changed-since requires a git revision"))
+                .transpose()?;
+            run_project_check(
+                &args[2],
+                cross_package_only,
+                changed_since.map(String::as_str),
+                duplicates,
+                profile,
+                junit,
+            )
         }
         "deploy-check" => {
             if args.len() < 3 {
-                eprintln!("Usage: surc deploy-check <deploy.toml>");
+                eprintln!("Usage: surc deploy-check <deploy.toml> [--job <name>]");
                 std::process::exit(1);
             }
-            run_deploy_check(&args[2])
+            let job = args[3..]
+                .iter()
+                .position(|a| a == "--job")
+                .map(|i| args.get(i + 4).ok_or("--job requires a job name"))
+                .transpose()?;
+            run_deploy_check(&args[2], job.map(String::as_str))
         }
         "export" => {
             if args.len() < 3 {
                 eprintln!("Usage: surc export <type> <file>");
-                eprintln!("Types: pipeline, modules, schemas, module-detail, deploy-mermaid, deploy-html");
+                eprintln!("Types: pipeline, modules, schemas, overview, module-detail, deploy-mermaid, deploy-html, deploy-json");
                 std::process::exit(1);
             }
             run_export(&args[2..])
@@ -131,6 +207,21 @@ fn run() -> Result<(), Box<dyn Error>> {
         "trace" => {
             run_trace(&args[2..])
         }
+        "symbols" => {
+            run_symbols(&args[2..])
+        }
+        "stats" => {
+            run_stats(&args[2..])
+        }
+        "merge" => {
+            run_merge(&args[2..])
+        }
+        "check-all" => {
+            run_check_all(&args[2..])
+        }
+        "diff" => {
+            run_diff(&args[2..])
+        }
         "diff-impl" => {
             if args.len() < 4 {
                 print_diff_impl_usage();
@@ -138,10 +229,14 @@ fn run() -> Result<(), Box<dyn Error>> {
             }
             run_diff_impl(&args[2..])
         }
+        "schema-spec" => run_schema_spec(),
+        "rules" => run_rules(&args[2..]),
+        "init" => run_init(&args[2..]),
+        "verify" => run_verify(&args[2..]),
         "-" => run_parse_reader(io::stdin()),
         other => {
             if args.len() == 2 {
-                run_parse(other)
+                run_parse(other, false, false)
             } else {
                 print_usage();
                 std::process::exit(1);
@@ -154,34 +249,77 @@ fn print_usage() {
     eprintln!("Usage: surc <command> [options]");
     eprintln!();
     eprintln!("Commands:");
+    eprintln!("  init [<path>] [--sample s]  Write a starter IR file (samples: minimal, crud, deploy)");
     eprintln!("  parse <file>                Parse IR and output AST as JSON");
-    eprintln!("  check <file>                Run static analysis for a single file");
+    eprintln!("  check <file> [--strict]     Run static analysis for a single file");
+    eprintln!("                              (--require-impl: warn on schemas/funcs without impl bindings)");
+    eprintln!("                              (--impl-lang: warn on schemas bound to another language than their funcs)");
+    eprintln!("                              (--lint-intent: note funcs whose intent conflicts with pipeline position)");
+    eprintln!("                              (--no-lint-pipeline: skip notes on modules with funcs but no pipeline)");
+    eprintln!("                              (--check-version: warn when meta.version is not semver)");
+    eprintln!("                              (--lint-boundaries: note schemas inside more than one boundary)");
+    eprintln!("                              (--require-owners: warn on mods without owners)");
+    eprintln!("                              (--lint-duplicates: note funcs with identical input/output schemas)");
+    eprintln!("                              (--format junit: JUnit XML report, one testsuite per file)");
     eprintln!("  project-check <manifest>    Check a project manifest (surv.toml)");
+    eprintln!("                              (--cross-package-only: report package-boundary issues only,");
+    eprintln!("                               including imports of packages missing from depends)");
+    eprintln!("                              (--changed-since <rev>: only files changed since a git revision and their dependents)");
+    eprintln!("                              (--lint-duplicates: note funcs with identical signatures across files)");
+    eprintln!("                              (--profile: print per-file parse times to stderr)");
+    eprintln!("                              (--format junit: JUnit XML report, one testsuite per file)");
     eprintln!("  deploy-check <file>         Check a deploy IR file");
+    eprintln!("                              (--job <name>: only checks that concern one job)");
+    eprintln!("  check-all <dir>             Check every Surv/Deploy IR .toml file under a directory");
     eprintln!("  inspect <module> <file>     Inspect a module's schemas, funcs, and pipeline");
     eprintln!("  status <subcommand>         Manage implementation status");
     eprintln!("  deps <manifest>             Show package and module dependencies");
     eprintln!("  split <input> --config <c>  Split single IR file into multi-package project");
+    eprintln!("  merge <manifest> [-o file]  Combine a project's IR files into one file");
+    eprintln!("  diff <old> <new>            Compare two IR files: added/removed/modified symbols");
+    eprintln!("  verify <file>...            Check that parse -> emit -> parse gives the same AST");
     eprintln!("  slice <target> <file>       Slice minimal IR fragment for a target");
     eprintln!("  refs <target> <file>        List references to a symbol");
     eprintln!("  trace <target> <file>       Trace pipeline flow for a func or mod");
+    eprintln!("  symbols <manifest>          List all symbols with fully-qualified names");
+    eprintln!("  stats <file>                Report schema fan-in/fan-out across funcs and mods");
+    eprintln!("                              (--schema-depth: longest schema reference chain)");
     eprintln!("  diff-impl <ir> <workspace>  Detect drift between IR and implementation");
     eprintln!("  export <type> <file>        Export visualizations");
     eprintln!("  codegen <platform> <file>   Generate CI/CD configuration");
+    eprintln!("                              (--provenance: embed the IR hash and generation time)");
+    eprintln!("                              (--validate-first: refuse to generate if deploy-check reports errors)");
+    eprintln!("  codegen --verify <platform> <file> <existing>");
+    eprintln!("                              Fail if a --provenance file is stale relative to the IR");
+    eprintln!("  schema-spec                 Print a JSON Schema describing the Surv IR format");
+    eprintln!("  rules                       List every diagnostic kind with severity and description");
     eprintln!();
     eprintln!("Export types:");
     eprintln!("  pipeline <file> <mod>       Export module pipeline as Mermaid");
     eprintln!("  modules <manifest>          Export module dependency graph");
-    eprintln!("  schemas <manifest>          Export schema relationship graph");
+    eprintln!("  schemas <manifest>          Export schema relationship graph or ER diagram");
+    eprintln!("  overview <manifest>         Export modules and the schemas they use");
     eprintln!("  html <manifest>             Export interactive HTML visualization");
     eprintln!("  module-detail <file> <mod>  Export detailed module view");
+    eprintln!("  openapi <file> <mod>        Export an OpenAPI 3 skeleton for an HTTP module (JSON)");
+    eprintln!("  schema-matrix <manifest>    Export which modules use which schemas (--format csv|markdown)");
     eprintln!("  deploy-mermaid <file>       Export deploy pipeline as Mermaid");
+    eprintln!("                              (--only-kind <kind>: jobs on that target kind and their dependencies)");
+    eprintln!("                              (--group-by stage: one subgraph per CI stage, as codegen assigns them)");
     eprintln!("  deploy-html <file>          Export deploy pipeline as interactive HTML");
+    eprintln!("  deploy-json <file>          Export deploy job DAG as JSON");
     eprintln!();
     eprintln!("Codegen platforms:");
     eprintln!("  github-actions              Generate GitHub Actions workflow");
     eprintln!("  gitlab-ci                   Generate GitLab CI configuration");
     eprintln!();
+    eprintln!("Export/codegen options:");
+    eprintln!("  -o, --output <path>         Write output to a file instead of stdout");
+    eprintln!();
+    eprintln!("Global options:");
+    eprintln!("  --no-color, --ascii         Plain ASCII markers ([OK], [ERR], [WARN]) instead of ✓ ✗ ⚠");
+    eprintln!("                              (automatic when stdout is not a terminal)");
+    eprintln!();
     eprintln!("Examples:");
     eprintln!("  surc parse example.toml");
     eprintln!("  surc check example.toml");
@@ -191,7 +329,9 @@ fn print_usage() {
     eprintln!("  surc export modules surv.toml");
     eprintln!("  surc export html surv.toml > output.html");
     eprintln!("  surc deploy-check deploy.toml");
-    eprintln!("  surc codegen github-actions deploy.toml > .github/workflows/deploy.yml");
+    eprintln!("  surc codegen github-actions deploy.toml -o .github/workflows/deploy.yml");
+    eprintln!("  surc codegen all deploy.toml --out-dir ci/");
+    eprintln!("  surc codegen --verify gitlab-ci deploy.toml .gitlab-ci.yml");
 }
 
 fn print_export_usage() {
@@ -201,30 +341,47 @@ fn print_export_usage() {
     eprintln!();
     eprintln!("SURV IR EXPORTS (single file or manifest):");
     eprintln!();
-    eprintln!("  pipeline <file.toml> <module-name>");
+    eprintln!("  pipeline <file.toml> <module-name> [--highlight <func>]...");
     eprintln!("      Export a module's pipeline as Mermaid flowchart");
+    eprintln!("      --highlight emphasizes a func node (repeatable)");
     eprintln!("      Input: Single Surv IR file");
     eprintln!("      Example: surc export pipeline user_api.toml user_http_api");
     eprintln!();
-    eprintln!("  module-detail <file.toml> <module-name>");
+    eprintln!("  module-detail <file.toml> <module-name> [--collapse-schemas] [--collapse-funcs]");
     eprintln!("      Export detailed module view as Mermaid");
+    eprintln!("      --collapse-schemas/--collapse-funcs draw one counted node instead (--collapse: both)");
     eprintln!("      Input: Single Surv IR file");
     eprintln!("      Example: surc export module-detail user_api.toml user_http_api");
     eprintln!();
-    eprintln!("  modules <surv.toml>");
+    eprintln!("  modules <surv.toml> [--diagram flowchart|mindmap]");
     eprintln!("      Export module dependency graph as Mermaid");
+    eprintln!("      --diagram mindmap: project -> packages -> modules -> schemas/funcs");
     eprintln!("      Input: Project manifest (surv.toml)");
     eprintln!("      Example: surc export modules surv.toml");
     eprintln!();
-    eprintln!("  schemas <surv.toml>");
+    eprintln!("  schemas <surv.toml> [--diagram graph|er]");
     eprintln!("      Export schema relationship graph as Mermaid");
+    eprintln!("      --diagram er: node schemas with fields (@unique as UK), edges as relations");
+    eprintln!("      Input: Project manifest (surv.toml)");
+    eprintln!("      Example: surc export schemas surv.toml --diagram er");
+    eprintln!();
+    eprintln!("  overview <surv.toml>");
+    eprintln!("      Export modules, schemas, and module-to-schema usage as one Mermaid diagram");
+    eprintln!("      Input: Project manifest (surv.toml)");
+    eprintln!("      Example: surc export overview surv.toml");
+    eprintln!();
+    eprintln!("  schema-matrix <surv.toml> [--format csv|markdown]");
+    eprintln!("      Export a schema-by-module table marking which modules use each schema");
     eprintln!("      Input: Project manifest (surv.toml)");
-    eprintln!("      Example: surc export schemas surv.toml");
+    eprintln!("      Example: surc export schema-matrix surv.toml --format markdown");
     eprintln!();
-    eprintln!("  html <surv.toml>");
+    eprintln!("  html <surv.toml> [--view graph|pipelines] [--color-by kind|usage]");
     eprintln!("      Export interactive HTML visualization (D3.js)");
+    eprintln!("      --view pipelines lays out every module pipeline as a chain, joined by requires");
+    eprintln!("      --color-by usage sizes and colors schemas by how many funcs reference them");
     eprintln!("      Input: Project manifest (surv.toml)");
     eprintln!("      Example: surc export html surv.toml > output.html");
+    eprintln!("      Example: surc export html surv.toml --view pipelines -o pipelines.html");
     eprintln!();
     eprintln!("DEPLOY IR EXPORTS:");
     eprintln!();
@@ -238,13 +395,76 @@ fn print_export_usage() {
     eprintln!("      Input: Deploy IR file");
     eprintln!("      Example: surc export deploy-html deploy.toml > pipeline.html");
     eprintln!();
+    eprintln!("  deploy-json <deploy.toml>");
+    eprintln!("      Export the job DAG (jobs, dependency links, pipeline, targets) as JSON");
+    eprintln!("      Input: Deploy IR file");
+    eprintln!("      Example: surc export deploy-json deploy.toml -o deploy-graph.json");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -o, --output <path>  Write output to a file (parent dirs are created)");
+    eprintln!("  --markdown           Wrap Mermaid output in a ```mermaid block with a heading");
+    eprintln!("  --profile            Print per-file parse times to stderr (manifest exports)");
+    eprintln!("  --label-width <n>    Wrap func intents and module purposes at about n chars");
+    eprintln!("                       (pipeline, module-detail)");
+    eprintln!();
     eprintln!("Note: Use 'surv.toml' for project-level exports (modules, schemas, html)");
     eprintln!("      Use individual '.toml' files for single-file exports (pipeline, module-detail)");
 }
 
-fn run_parse(filename: &str) -> Result<(), Box<dyn Error>> {
-    let file = File::open(filename)?;
-    run_parse_reader(file)
+fn run_schema_spec() -> Result<(), Box<dyn Error>> {
+    let schema = survibe_parser_rs::schema_spec::surv_ir_json_schema();
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+fn run_parse(filename: &str, strict: bool, source_order: bool) -> Result<(), Box<dyn Error>> {
+    if !strict && !source_order {
+        return run_parse_reader(File::open(filename)?);
+    }
+
+    let source = std::fs::read_to_string(filename)?;
+    let (mut ast, mut unknown) = parse_surv_file_with_unknown_keys(source.as_bytes())?;
+    unknown.retain(|d| d.kind == "UnknownKey");
+    if strict && !unknown.is_empty() {
+        for diag in &unknown {
+            eprintln!("{} [{}] {}", markers::err(), diag.kind, diag.message);
+            eprintln!("  at {}\n", diag.location);
+        }
+        return Err(format!("{} unknown key(s) in strict mode", unknown.len()).into());
+    }
+    if source_order {
+        sort_sections_by_source(&mut ast, &source);
+    }
+    let mut stdout = io::stdout();
+    serde_json::to_writer_pretty(&mut stdout, &ast)?;
+    stdout.write_all(b"\n")?;
+    Ok(())
+}
+
+/// `parse --project`: every file of a project, keyed by path. With `resolve`,
+/// references are rewritten to fully-qualified names where unambiguous.
+fn run_parse_project(manifest: &str, resolve: bool) -> Result<(), Box<dyn Error>> {
+    let project = load_project(Path::new(manifest))?;
+    let files = if resolve {
+        let (symbols, _) = build_symbol_table(&project);
+        let (files, notes) = resolve_to_fq_names(&project, &symbols);
+        for note in &notes {
+            eprintln!("{} [{}] {}", markers::warn(), note.kind, note.message);
+            eprintln!("  at {}\n", note.location);
+        }
+        files
+    } else {
+        project.files
+    };
+
+    let by_path: std::collections::BTreeMap<String, _> = files
+        .into_iter()
+        .map(|(path, file)| (path.display().to_string(), file))
+        .collect();
+    let mut stdout = io::stdout();
+    serde_json::to_writer_pretty(&mut stdout, &by_path)?;
+    stdout.write_all(b"\n")?;
+    Ok(())
 }
 
 fn run_parse_reader<R: Read>(reader: R) -> Result<(), Box<dyn Error>> {
@@ -255,13 +475,69 @@ fn run_parse_reader<R: Read>(reader: R) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn run_check(filename: &str) -> Result<(), Box<dyn Error>> {
+/// Optional checks `surc check` runs on top of `check_surv_file`.
+struct CheckLints {
+    require_impl: bool,
+    impl_lang: bool,
+    intent: bool,
+    pipeline: bool,
+    version: bool,
+    boundaries: bool,
+    owners: bool,
+    duplicates: bool,
+}
+
+fn run_check(
+    filename: &str,
+    strict: bool,
+    lints: &CheckLints,
+    junit: bool,
+) -> Result<(), Box<dyn Error>> {
     let file = File::open(filename)?;
-    let ast = parse_surv_file(file)?;
-    let diags = check_surv_file(&ast);
+    let (ast, mut diags) = parse_surv_file_with_unknown_keys(file)?;
+    if strict {
+        for diag in diags.iter_mut().filter(|d| d.kind == "UnknownKey") {
+            diag.severity = "error".into();
+        }
+    }
+    diags.extend(check_surv_file(&ast));
+    if lints.require_impl {
+        diags.extend(check_impl_bindings(&ast));
+    }
+    if lints.impl_lang {
+        diags.extend(check_impl_langs(&ast));
+    }
+    if lints.intent {
+        diags.extend(check_intent_order(&ast));
+    }
+    if lints.pipeline {
+        diags.extend(check_module_pipelines(&ast));
+    }
+    if lints.version {
+        diags.extend(check_meta_version(&ast));
+    }
+    if lints.boundaries {
+        diags.extend(check_boundary_overlap(&ast));
+    }
+    if lints.owners {
+        diags.extend(check_module_owners(&ast));
+    }
+    if lints.duplicates {
+        diags.extend(check_duplicate_funcs(&ast));
+    }
+
+    if junit {
+        let has_errors = diags.iter().any(|d| d.severity == "error");
+        print!("{}", render_junit(&[(filename.to_string(), diags)]));
+        return if has_errors {
+            Err("diagnostics reported errors".into())
+        } else {
+            Ok(())
+        };
+    }
 
     if diags.is_empty() {
-        println!("✓ No issues found");
+        println!("{} No issues found", markers::ok());
         return Ok(());
     }
 
@@ -272,16 +548,21 @@ fn run_check(filename: &str) -> Result<(), Box<dyn Error>> {
         let icon = match diag.severity.as_str() {
             "error" => {
                 error_count += 1;
-                "✗"
+                markers::err()
             }
             "warning" => {
                 warning_count += 1;
-                "⚠"
+                markers::warn()
             }
+            "info" => markers::info(),
             _ => "?",
         };
         println!("{icon} [{}] {}", diag.kind, diag.message);
-        println!("  at {}\n", diag.location);
+        println!("  at {}", diag.location);
+        if let Some(suggestion) = &diag.suggestion {
+            println!("  help: {}", suggestion);
+        }
+        println!();
     }
 
     println!("---");
@@ -294,12 +575,88 @@ fn run_check(filename: &str) -> Result<(), Box<dyn Error>> {
     }
 }
 
-fn run_project_check(manifest: &str) -> Result<(), Box<dyn Error>> {
-    let project = load_project(Path::new(manifest))?;
-    let diags = check_project(&project);
+fn run_project_check(
+    manifest: &str,
+    cross_package_only: bool,
+    changed_since: Option<&str>,
+    duplicates: bool,
+    profile: bool,
+    junit: bool,
+) -> Result<(), Box<dyn Error>> {
+    let manifest_path = Path::new(manifest);
+    let full_project = load_project_with_profile(manifest_path, profile)?;
+    let manifest = survibe_parser_rs::loader::load_manifest(manifest_path)?;
+    let (symbols, _) = build_symbol_table(&full_project);
+    // --changed-since: the changed files and the files that reference their
+    // symbols, transitively
+    let affected = match changed_since {
+        Some(rev) => {
+            let changed = git_changed_files(rev, manifest_path)?;
+            let changed: BTreeSet<PathBuf> = full_project
+                .files
+                .iter()
+                .filter(|(path, _)| path.canonicalize().is_ok_and(|p| changed.contains(&p)))
+                .map(|(path, _)| path.clone())
+                .collect();
+            let affected = reverse_dependency_closure(&full_project, &symbols, &changed);
+            if !junit {
+                println!(
+                    "Checking {} of {} file(s) changed since {} or depending on them",
+                    affected.len(),
+                    full_project.files.len(),
+                    rev
+                );
+            }
+            Some(affected)
+        }
+        None => None,
+    };
+
+    // Manifest-level problems first
+    let mut diags = check_self_dependent_packages(&manifest);
+    diags.extend(check_project(&full_project));
+    diags.extend(check_empty_packages(&full_project, &manifest));
+    if duplicates {
+        diags.extend(check_duplicate_funcs_in_project(&full_project));
+    }
+    // Opt-in: imports and requires into packages missing from `depends`
+    if cross_package_only {
+        diags.extend(check_package_dependencies(&full_project, &manifest));
+    }
+    // Namespaced reference resolution, which strict_namespaces turns on
+    if manifest.project.strict_namespaces {
+        diags.extend(resolve_project_references(&full_project, &symbols, &manifest));
+    }
+
+    // A filter over the analysis above: only diagnostics that name the
+    // package they cross into
+    if cross_package_only {
+        diags.retain(|diag| diag.package.is_some());
+    }
+    // Likewise for --changed-since: the whole project is analysed, and only
+    // the diagnostics located in an affected file are reported
+    let restricted = affected.as_ref().map(|affected| {
+        retain_in_files(&mut diags, &full_project, affected);
+        full_project.restricted_to(affected)
+    });
+    let project = restricted.as_ref().unwrap_or(&full_project);
+
+    if junit {
+        let has_errors = diags.iter().any(|d| d.severity == "error");
+        print!("{}", render_junit(&junit_suites_by_file(manifest_path, project, diags)));
+        return if has_errors {
+            Err("project diagnostics reported errors".into())
+        } else {
+            Ok(())
+        };
+    }
 
     if diags.is_empty() {
-        println!("✓ No project issues found");
+        if cross_package_only {
+            println!("{} No cross-package issues found", markers::ok());
+        } else {
+            println!("{} No project issues found", markers::ok());
+        }
         return Ok(());
     }
 
@@ -310,16 +667,22 @@ fn run_project_check(manifest: &str) -> Result<(), Box<dyn Error>> {
             error_count += 1;
         }
         let icon = match diag.severity.as_str() {
-            "error" => "✗",
-            "warning" => "⚠",
+            "error" => markers::err(),
+            "warning" => markers::warn(),
+            "info" => markers::info(),
             _ => "?",
         };
         println!("{icon} [{}] {}", diag.kind, diag.message);
+        if let Some(package) = &diag.package {
+            println!("  crosses into package {}", package);
+        }
         if !diag.location.is_empty() {
-            println!("  at {}\n", diag.location);
-        } else {
-            println!();
+            println!("  at {}", diag.location);
+        }
+        if let Some(suggestion) = &diag.suggestion {
+            println!("  help: {}", suggestion);
         }
+        println!();
     }
 
     println!("---");
@@ -332,13 +695,106 @@ fn run_project_check(manifest: &str) -> Result<(), Box<dyn Error>> {
     }
 }
 
-fn run_deploy_check(filename: &str) -> Result<(), Box<dyn Error>> {
-    let file = File::open(filename)?;
-    let deploy = parse_deploy_file(file)?;
-    let diags = check_deploy_file(&deploy);
+/// Strip the global `--no-color` / `--ascii` flag, wherever it appears, and
+/// report whether it was given.
+fn take_ascii_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|a| a != "--no-color" && a != "--ascii");
+    args.len() != before
+}
+
+/// `--format text|junit` on the check commands; true when JUnit is requested.
+fn junit_format(args: &[String]) -> Result<bool, Box<dyn Error>> {
+    match args.iter().position(|a| a == "--format") {
+        None => Ok(false),
+        Some(i) => match args.get(i + 1).map(String::as_str) {
+            Some("text") => Ok(false),
+            Some("junit") => Ok(true),
+            Some(other) => Err(format!("Unknown format '{}' (expected text, junit)", other).into()),
+            None => Err("--format requires a value (text, junit)".into()),
+        },
+    }
+}
+
+/// Keep the diagnostics located in one of `files`, or in no project file
+/// at all, such as manifest-level ones.
+fn retain_in_files(diags: &mut Vec<Diagnostic>, project: &ProjectAST, files: &BTreeSet<PathBuf>) {
+    diags.retain(|diag| {
+        let file = diag.location.split(": ").next().unwrap_or_default();
+        !project
+            .files
+            .iter()
+            .any(|(path, _)| !files.contains(path) && path.display().to_string() == file)
+    });
+}
+
+/// Absolute paths of the files changed since `rev` in the repository that
+/// holds `manifest`, including uncommitted changes and untracked files.
+fn git_changed_files(rev: &str, manifest: &Path) -> Result<BTreeSet<PathBuf>, Box<dyn Error>> {
+    let dir = match manifest.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let git = |args: &[&str]| -> Result<String, Box<dyn Error>> {
+        let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+        if !output.status.success() {
+            return Err(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let root = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?.trim());
+    let diff = git(&["diff", "--name-only", rev, "--"])?;
+    // Paths relative to the top level, like `git diff` prints them
+    let untracked = git(&["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+    Ok(diff
+        .lines()
+        .chain(untracked.lines())
+        .filter_map(|line| root.join(line).canonicalize().ok())
+        .collect())
+}
+
+/// Group project diagnostics into one JUnit suite per file, by the file path
+/// their location starts with; the rest go to the manifest's suite.
+fn junit_suites_by_file(
+    manifest: &Path,
+    project: &ProjectAST,
+    diags: Vec<Diagnostic>,
+) -> Vec<(String, Vec<Diagnostic>)> {
+    let mut suites: Vec<(String, Vec<Diagnostic>)> = std::iter::once(manifest)
+        .chain(project.files.iter().map(|(path, _)| path.as_path()))
+        .map(|path| (path.display().to_string(), Vec::new()))
+        .collect();
+    for diag in diags {
+        let file = diag.location.split(": ").next().unwrap_or_default();
+        let index = suites.iter().position(|(name, _)| name == file).unwrap_or(0);
+        suites[index].1.push(diag);
+    }
+    suites
+}
+
+fn run_deploy_check(filename: &str, job: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let deploy = parse_deploy_file_from_path(Path::new(filename))?;
+    let diags = match job {
+        Some(job) => {
+            let name = job.strip_prefix("job.").unwrap_or(job);
+            if !deploy.jobs.contains_key(name) {
+                return Err(format!("Job '{}' not found in {}", name, filename).into());
+            }
+            check_deploy_job(&deploy, name)
+        }
+        None => check_deploy_file(&deploy),
+    };
 
     if diags.is_empty() {
-        println!("✓ No deploy issues found");
+        match job {
+            Some(job) => println!("{} No deploy issues found for {}", markers::ok(), job),
+            None => println!("{} No deploy issues found", markers::ok()),
+        }
         return Ok(());
     }
 
@@ -349,16 +805,20 @@ fn run_deploy_check(filename: &str) -> Result<(), Box<dyn Error>> {
         let icon = match diag.severity.as_str() {
             "error" => {
                 error_count += 1;
-                "✗"
+                markers::err()
             }
             "warning" => {
                 warning_count += 1;
-                "⚠"
+                markers::warn()
             }
-            _ => "ℹ",
+            _ => markers::info(),
         };
         println!("{icon} [{}] {}", diag.kind, diag.message);
-        println!("  at {}\n", diag.location);
+        println!("  at {}", diag.location);
+        if let Some(suggestion) = &diag.suggestion {
+            println!("  help: {}", suggestion);
+        }
+        println!();
     }
 
     println!("---");
@@ -372,6 +832,24 @@ fn run_deploy_check(filename: &str) -> Result<(), Box<dyn Error>> {
 }
 
 fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut args, output_path) = extract_output_option(args)?;
+    let markdown = args.iter().any(|a| a == "--markdown");
+    let profile = args.iter().any(|a| a == "--profile");
+    args.retain(|a| a != "--markdown" && a != "--profile");
+    let label_width = match args.iter().position(|a| a == "--label-width") {
+        Some(pos) => {
+            let width = args
+                .get(pos + 1)
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|&w| w > 0)
+                .ok_or("--label-width requires a positive number of characters")?;
+            args.drain(pos..pos + 2);
+            Some(width)
+        }
+        None => None,
+    };
+    let args = args.as_slice();
+
     if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
         print_export_usage();
         if args.is_empty() {
@@ -382,12 +860,15 @@ fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
     }
 
     let export_type = &args[0];
-    let exporter = MermaidExporter::new();
+    let exporter = match label_width {
+        Some(width) => MermaidExporter::new().with_label_width(width),
+        None => MermaidExporter::new(),
+    };
 
     match export_type.as_str() {
         "pipeline" => {
             if args.len() < 3 {
-                eprintln!("Usage: surc export pipeline <file> <module-name>");
+                eprintln!("Usage: surc export pipeline <file> <module-name> [--highlight <func>]...");
                 std::process::exit(1);
             }
             let file = File::open(&args[1])?;
@@ -405,42 +886,91 @@ fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
                     Section::Mod(m) if m.name == module_name => Some(m),
                     _ => None,
                 })
-                .ok_or_else(|| format!("Module '{}' not found", module_name))?;
+                .ok_or_else(|| module_not_found(&module_name, &project))?;
 
-            let output = exporter.export_pipeline(module, &project);
-            println!("{}", output);
+            let mut highlight = Vec::new();
+            let mut i = 3;
+            while i < args.len() {
+                if args[i] == "--highlight" && i + 1 < args.len() {
+                    highlight.push(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: Unknown option: {}", args[i]);
+                    std::process::exit(1);
+                }
+            }
+
+            let output = exporter.export_pipeline_highlighted(module, &project, &highlight);
+            write_mermaid(&exporter, &output, markdown, output_path.as_deref())?;
         }
         "modules" => {
             if args.len() < 2 {
-                eprintln!("Usage: surc export modules <surv.toml>");
+                eprintln!("Usage: surc export modules <surv.toml> [--diagram flowchart|mindmap]");
                 eprintln!();
                 eprintln!("Error: Missing manifest file");
                 eprintln!("Expected: A project manifest file (surv.toml) with [project] section");
                 std::process::exit(1);
             }
-            let project = load_project(Path::new(&args[1])).map_err(|e| {
+            let project = load_project_with_profile(Path::new(&args[1]), profile).map_err(|e| {
                 format!("Failed to load project manifest '{}':\n  {}\n\nExpected: A surv.toml file with [project] and [files] sections", args[1], e)
             })?;
-            let output = exporter.export_module_dependencies(&project);
-            println!("{}", output);
+            let output = match args.iter().position(|a| a == "--diagram") {
+                None => exporter.export_module_dependencies(&project),
+                Some(i) => match args.get(i + 1).map(String::as_str) {
+                    Some("flowchart") => exporter.export_module_dependencies(&project),
+                    Some("mindmap") => {
+                        let manifest = survibe_parser_rs::loader::load_manifest(Path::new(&args[1]))?;
+                        exporter.export_module_mindmap(&project, &manifest.project.name)
+                    }
+                    Some(other) => {
+                        return Err(format!("Unknown diagram '{}' (expected flowchart, mindmap)", other).into())
+                    }
+                    None => return Err("--diagram requires a value (flowchart, mindmap)".into()),
+                },
+            };
+            write_mermaid(&exporter, &output, markdown, output_path.as_deref())?;
         }
         "schemas" => {
             if args.len() < 2 {
-                eprintln!("Usage: surc export schemas <surv.toml>");
+                eprintln!("Usage: surc export schemas <surv.toml> [--diagram graph|er]");
                 eprintln!();
                 eprintln!("Error: Missing manifest file");
                 eprintln!("Expected: A project manifest file (surv.toml) with [project] section");
                 std::process::exit(1);
             }
-            let project = load_project(Path::new(&args[1])).map_err(|e| {
+            let project = load_project_with_profile(Path::new(&args[1]), profile).map_err(|e| {
                 format!("Failed to load project manifest '{}':\n  {}\n\nExpected: A surv.toml file with [project] and [files] sections", args[1], e)
             })?;
-            let output = exporter.export_schema_graph(&project);
-            println!("{}", output);
+            let output = match args.iter().position(|a| a == "--diagram") {
+                None => exporter.export_schema_graph(&project),
+                Some(i) => match args.get(i + 1).map(String::as_str) {
+                    Some("graph") => exporter.export_schema_graph(&project),
+                    Some("er") => exporter.export_schema_er(&project),
+                    Some(other) => {
+                        return Err(format!("Unknown diagram '{}' (expected graph, er)", other).into())
+                    }
+                    None => return Err("--diagram requires a value (graph, er)".into()),
+                },
+            };
+            write_mermaid(&exporter, &output, markdown, output_path.as_deref())?;
+        }
+        "overview" => {
+            if args.len() < 2 {
+                eprintln!("Usage: surc export overview <surv.toml>");
+                eprintln!();
+                eprintln!("Error: Missing manifest file");
+                eprintln!("Expected: A project manifest file (surv.toml) with [project] section");
+                std::process::exit(1);
+            }
+            let project = load_project_with_profile(Path::new(&args[1]), profile).map_err(|e| {
+                format!("Failed to load project manifest '{}':\n  {}\n\nExpected: A surv.toml file with [project] and [files] sections", args[1], e)
+            })?;
+            let output = exporter.export_overview(&project);
+            write_mermaid(&exporter, &output, markdown, output_path.as_deref())?;
         }
         "html" => {
             if args.len() < 2 {
-                eprintln!("Usage: surc export html <surv.toml>");
+                eprintln!("Usage: surc export html <surv.toml> [--view graph|pipelines] [--color-by kind|usage]");
                 eprintln!();
                 eprintln!("Error: Missing manifest file");
                 eprintln!("Expected: A project manifest file (surv.toml) with [project] section");
@@ -453,22 +983,100 @@ fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
                 eprintln!("  \"api.toml\" = {{}}");
                 std::process::exit(1);
             }
-            let project = load_project(Path::new(&args[1])).map_err(|e| {
+            let project = load_project_with_profile(Path::new(&args[1]), profile).map_err(|e| {
                 format!("Failed to load project manifest '{}':\n  {}\n\nExpected: A surv.toml file with [project] and [files] sections.\n\nIf you have a single Surv IR file, use 'surc export pipeline <file> <module>' instead.", args[1], e)
             })?;
+            let view = match args.iter().position(|a| a == "--view") {
+                Some(i) => args
+                    .get(i + 1)
+                    .map(String::as_str)
+                    .ok_or("--view requires a value (graph, pipelines)")?,
+                None => "graph",
+            };
+            let color_by = match args.iter().position(|a| a == "--color-by") {
+                Some(i) => args
+                    .get(i + 1)
+                    .map(String::as_str)
+                    .ok_or("--color-by requires a value (kind, usage)")?,
+                None => "kind",
+            };
+            if markdown {
+                return Err("--markdown only applies to Mermaid exports".into());
+            }
             let html_exporter = HtmlExporter::new();
-            let output = html_exporter.export_interactive(&project);
-            println!("{}", output);
+            let output = match (view, color_by) {
+                ("graph", "kind") => html_exporter.export_interactive(&project),
+                ("graph", "usage") => html_exporter.export_interactive_by_usage(&project),
+                ("graph", other) => {
+                    return Err(format!("Unknown --color-by: {} (expected kind, usage)", other).into())
+                }
+                ("pipelines", "kind") => html_exporter.export_pipelines_interactive(&project),
+                ("pipelines", _) => return Err("--color-by only applies to --view graph".into()),
+                (other, _) => return Err(format!("Unknown view: {} (expected graph, pipelines)", other).into()),
+            };
+            write_output(&output, output_path.as_deref())?;
+        }
+        "schema-matrix" => {
+            if args.len() < 2 {
+                eprintln!("Usage: surc export schema-matrix <surv.toml> [--format csv|markdown]");
+                std::process::exit(1);
+            }
+            if markdown {
+                return Err("--markdown only applies to Mermaid exports; use --format markdown".into());
+            }
+            let project = load_project_with_profile(Path::new(&args[1]), profile)?;
+            let matrix = HtmlExporter::new().schema_module_matrix(&project);
+            let output = match args.iter().position(|a| a == "--format") {
+                None => matrix.to_csv(),
+                Some(i) => match args.get(i + 1).map(String::as_str) {
+                    Some("csv") => matrix.to_csv(),
+                    Some("markdown") => matrix.to_markdown(),
+                    Some(other) => {
+                        return Err(format!("Unknown format '{}' (expected csv, markdown)", other).into())
+                    }
+                    None => return Err("--format requires a value (csv, markdown)".into()),
+                },
+            };
+            write_output(&output, output_path.as_deref())?;
+        }
+        "openapi" => {
+            if args.len() < 3 {
+                eprintln!("Usage: surc export openapi <file> <module-name>");
+                eprintln!();
+                eprintln!("The module must have kind = \"http\" or a name ending in _http_api.");
+                std::process::exit(1);
+            }
+            if markdown {
+                return Err("--markdown only applies to Mermaid exports".into());
+            }
+            let file = parse_surv_file(File::open(&args[1])?)?;
+            let module_name = &args[2];
+            let module = file
+                .sections
+                .iter()
+                .find_map(|s| match s {
+                    Section::Mod(m) if &m.name == module_name => Some(m),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    let project = ProjectAST::from_files(vec![(args[1].clone().into(), file.clone())]);
+                    module_not_found(module_name, &project)
+                })?;
+            let document = OpenApiExporter::new().export_module(module, &file)?;
+            write_output(&serde_json::to_string_pretty(&document)?, output_path.as_deref())?;
         }
         "module-detail" => {
             if args.len() < 3 {
-                eprintln!("Usage: surc export module-detail <file> <module-name>");
+                eprintln!("Usage: surc export module-detail <file> <module-name> [--collapse] [--collapse-schemas] [--collapse-funcs]");
                 std::process::exit(1);
             }
             let file = File::open(&args[1])?;
             let ast = parse_surv_file(file)?;
             let module_name = args[2].clone();
             let file_path = args[1].clone();
+            let collapse = args[3..].iter().any(|a| a == "--collapse");
+            let collapse_schemas = collapse || args[3..].iter().any(|a| a == "--collapse-schemas");
+            let collapse_funcs = collapse || args[3..].iter().any(|a| a == "--collapse-funcs");
 
             let project = ProjectAST::from_files(vec![(file_path.into(), ast)]);
 
@@ -480,35 +1088,80 @@ fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
                     Section::Mod(m) if m.name == module_name => Some(m),
                     _ => None,
                 })
-                .ok_or_else(|| format!("Module '{}' not found", module_name))?;
-
-            let output = exporter.export_module_detail(module, &project);
-            println!("{}", output);
+                .ok_or_else(|| module_not_found(&module_name, &project))?;
+
+            let output = exporter.export_module_detail_collapsed(
+                module,
+                &project,
+                collapse_schemas,
+                collapse_funcs,
+            );
+            write_mermaid(&exporter, &output, markdown, output_path.as_deref())?;
         }
         "deploy-mermaid" => {
             if args.len() < 2 {
-                eprintln!("Usage: surc export deploy-mermaid <deploy.toml>");
+                eprintln!("Usage: surc export deploy-mermaid <deploy.toml> [--only-kind <kind>] [--group-by stage|none]");
                 std::process::exit(1);
             }
-            let file = File::open(&args[1])?;
-            let deploy = parse_deploy_file(file)?;
-            let output = exporter.export_deploy_pipeline(&deploy);
-            println!("{}", output);
+            let mut only_kind = None;
+            let mut group_by_stage = false;
+            let mut i = 2;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--only-kind" | "--target-kind" => {
+                        let Some(kind) = args.get(i + 1) else {
+                            return Err("--only-kind requires a target kind (e.g. production)".into());
+                        };
+                        only_kind = Some(kind.as_str());
+                        i += 2;
+                    }
+                    "--group-by" => {
+                        group_by_stage = match args.get(i + 1).map(String::as_str) {
+                            Some("stage") => true,
+                            Some("none") => false,
+                            _ => return Err("--group-by requires a value (stage, none)".into()),
+                        };
+                        i += 2;
+                    }
+                    _ => {
+                        eprintln!("Error: Unknown option: {}", args[i]);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            let deploy = parse_deploy_file_from_path(Path::new(&args[1]))?;
+            let output =
+                exporter.export_deploy_pipeline_grouped(&deploy, only_kind, group_by_stage);
+            write_mermaid(&exporter, &output, markdown, output_path.as_deref())?;
+        }
+        "deploy-json" => {
+            if args.len() < 2 {
+                eprintln!("Usage: surc export deploy-json <deploy.toml>");
+                std::process::exit(1);
+            }
+            if markdown {
+                return Err("--markdown only applies to Mermaid exports".into());
+            }
+            let deploy = parse_deploy_file_from_path(Path::new(&args[1]))?;
+            let output = HtmlExporter::new().export_deploy_json(&deploy);
+            write_output(&output, output_path.as_deref())?;
         }
         "deploy-html" => {
             if args.len() < 2 {
                 eprintln!("Usage: surc export deploy-html <deploy.toml>");
                 std::process::exit(1);
             }
-            let file = File::open(&args[1])?;
-            let deploy = parse_deploy_file(file)?;
+            if markdown {
+                return Err("--markdown only applies to Mermaid exports".into());
+            }
+            let deploy = parse_deploy_file_from_path(Path::new(&args[1]))?;
             let html_exporter = HtmlExporter::new();
             let output = html_exporter.export_deploy_interactive(&deploy);
-            println!("{}", output);
+            write_output(&output, output_path.as_deref())?;
         }
         other => {
             eprintln!("Unknown export type: {}", other);
-            eprintln!("Valid types: pipeline, modules, schemas, html, module-detail, deploy-mermaid, deploy-html");
+            eprintln!("Valid types: pipeline, modules, schemas, overview, html, module-detail, deploy-mermaid, deploy-html, deploy-json");
             std::process::exit(1);
         }
     }
@@ -517,46 +1170,255 @@ fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
 }
 
 fn run_codegen(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut args, output_path) = extract_output_option(args)?;
+    let provenance = args.iter().any(|a| a == "--provenance");
+    let validate_first = args.iter().any(|a| a == "--validate-first");
+    args.retain(|a| a != "--provenance" && a != "--validate-first");
+    let args = args.as_slice();
+
     if args.is_empty() {
-        eprintln!("Usage: surc codegen <platform> <deploy.toml>");
+        eprintln!("Usage: surc codegen <platform> <deploy.toml> [--provenance] [--validate-first]");
         std::process::exit(1);
     }
 
     let platform = &args[0];
+    if platform == "--verify" {
+        return run_codegen_verify(&args[1..]);
+    }
+    // `--provenance`: stamp the IR hash and generation time into the header
+    let render = |generator: &dyn codegen::DeployGenerator, deploy: &DeployFile| {
+        let generated = generator.generate(deploy);
+        if provenance {
+            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+            codegen::with_provenance(&generated, deploy, &now)
+        } else {
+            generated
+        }
+    };
 
-    match platform.as_str() {
-        "github-actions" => {
-            if args.len() < 2 {
-                eprintln!("Usage: surc codegen github-actions <deploy.toml>");
-                std::process::exit(1);
-            }
-            let file = File::open(&args[1])?;
-            let deploy = parse_deploy_file(file)?;
-            let generator = GitHubActionsGenerator::new();
-            let yaml = generator.generate(&deploy);
-            println!("{}", yaml);
+    if platform == "all" {
+        if args.len() < 2 {
+            eprintln!("Usage: surc codegen all <deploy.toml> [--out-dir <dir>]");
+            std::process::exit(1);
         }
-        "gitlab-ci" => {
-            if args.len() < 2 {
-                eprintln!("Usage: surc codegen gitlab-ci <deploy.toml>");
+        if output_path.is_some() {
+            return Err("codegen all writes one file per platform; use --out-dir instead of -o".into());
+        }
+        let mut out_dir = ".".to_string();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--out-dir" && i + 1 < args.len() {
+                out_dir = args[i + 1].clone();
+                i += 2;
+            } else {
+                eprintln!("Error: Unknown option: {}", args[i]);
                 std::process::exit(1);
             }
-            let file = File::open(&args[1])?;
-            let deploy = parse_deploy_file(file)?;
-            let generator = GitLabCIGenerator::new();
-            let yaml = generator.generate(&deploy);
-            println!("{}", yaml);
         }
-        other => {
-            eprintln!("Unknown platform: {}", other);
-            eprintln!("Valid platforms: github-actions, gitlab-ci");
-            std::process::exit(1);
+
+        let deploy = parse_deploy_file_from_path(Path::new(&args[1]))?;
+        if validate_first {
+            refuse_invalid_deploy(&args[1], &deploy)?;
+        }
+        for generator in codegen::generators() {
+            let path = Path::new(&out_dir).join(generator.default_path());
+            write_output(&render(generator.as_ref(), &deploy), path.to_str())?;
+        }
+        return Ok(());
+    }
+
+    let Some(generator) = codegen::find_generator(platform) else {
+        let names: Vec<String> = codegen::generators()
+            .iter()
+            .map(|g| g.name().to_string())
+            .collect();
+        eprintln!("Unknown platform: {}", platform);
+        eprintln!("Valid platforms: {}, all", names.join(", "));
+        std::process::exit(1);
+    };
+    if args.len() < 2 {
+        eprintln!("Usage: surc codegen {} <deploy.toml>", platform);
+        std::process::exit(1);
+    }
+    let deploy = parse_deploy_file_from_path(Path::new(&args[1]))?;
+    if validate_first {
+        refuse_invalid_deploy(&args[1], &deploy)?;
+    }
+    write_output(&render(generator.as_ref(), &deploy), output_path.as_deref())?;
+
+    Ok(())
+}
+
+/// `codegen --validate-first`: print deploy-check errors to stderr and fail
+/// instead of generating. Warnings don't block generation.
+fn refuse_invalid_deploy(filename: &str, deploy: &DeployFile) -> Result<(), Box<dyn Error>> {
+    let errors: Vec<Diagnostic> = check_deploy_file(deploy)
+        .into_iter()
+        .filter(|diag| diag.severity == "error")
+        .collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+    for diag in &errors {
+        eprintln!("{} [{}] {}", markers::err(), diag.kind, diag.message);
+        eprintln!("  at {}", diag.location);
+        if let Some(suggestion) = &diag.suggestion {
+            eprintln!("  help: {}", suggestion);
+        }
+    }
+    Err(format!("{} has {} error(s); not generating", filename, errors.len()).into())
+}
+
+/// `codegen --verify`: fail when a generated file's embedded source hash no
+/// longer matches the IR, or its body differs from a fresh generation.
+fn run_codegen_verify(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.len() < 3 {
+        eprintln!("Usage: surc codegen --verify <platform> <deploy.toml> <existing.yml>");
+        std::process::exit(1);
+    }
+    let Some(generator) = codegen::find_generator(&args[0]) else {
+        return Err(format!("Unknown platform: {}", args[0]).into());
+    };
+    let deploy = parse_deploy_file_from_path(Path::new(&args[1]))?;
+    let existing = std::fs::read_to_string(&args[2])?;
+
+    let Some(embedded) = codegen::embedded_source_hash(&existing) else {
+        return Err(format!(
+            "{} has no Source-Hash header; regenerate it with codegen --provenance",
+            args[2]
+        )
+        .into());
+    };
+    let expected = codegen::source_hash(&deploy);
+    if embedded != expected {
+        return Err(format!(
+            "{} is stale: generated from {}, but {} is now {}",
+            args[2], embedded, args[1], expected
+        )
+        .into());
+    }
+    let regenerated = generator.generate(&deploy);
+    if codegen::strip_provenance(&existing) != codegen::strip_provenance(&regenerated) {
+        return Err(format!(
+            "{} matches the IR hash but differs from a fresh generation (edited by hand?)",
+            args[2]
+        )
+        .into());
+    }
+
+    println!("{} {} is up to date with {}", markers::ok(), args[2], args[1]);
+    Ok(())
+}
+
+/// Load a project; with `profile`, print per-file parse times to stderr (`--profile`)
+fn load_project_with_profile(manifest_path: &Path, profile: bool) -> Result<ProjectAST, Box<dyn Error>> {
+    if !profile {
+        return load_project(manifest_path);
+    }
+
+    let start = Instant::now();
+    let (project, mut stats) = load_project_profiled(manifest_path)?;
+    let total = start.elapsed();
+
+    // Slowest files first, so a pathological file is easy to spot
+    stats.sort_by_key(|stat| std::cmp::Reverse(stat.parse_time));
+    let mut table = TextTable::new(&["FILE", "SECTIONS", "PARSE"])
+        .align_right(1)
+        .align_right(2);
+    for stat in &stats {
+        table.add_row(vec![
+            stat.path.display().to_string(),
+            stat.sections.to_string(),
+            format!("{:.2} ms", stat.parse_time.as_secs_f64() * 1000.0),
+        ]);
+    }
+    eprint!("{}", table.render());
+    eprintln!(
+        "Loaded {} file(s), {} section(s) in {:.2} ms",
+        stats.len(),
+        stats.iter().map(|s| s.sections).sum::<usize>(),
+        total.as_secs_f64() * 1000.0
+    );
+    eprintln!();
+
+    Ok(project)
+}
+
+/// Split a `-o/--output <path>` option out of the arguments
+fn extract_output_option(args: &[String]) -> Result<(Vec<String>, Option<String>), Box<dyn Error>> {
+    let mut rest = Vec::new();
+    let mut output_path = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-o" | "--output" => {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    return Err(format!("{} requires a path", args[i]).into());
+                }
+            }
+            _ => {
+                rest.push(args[i].clone());
+                i += 1;
+            }
         }
     }
 
+    Ok((rest, output_path))
+}
+
+/// Write a Mermaid diagram, optionally wrapped in a markdown fence (`--markdown`)
+fn write_mermaid(
+    exporter: &MermaidExporter,
+    diagram: &str,
+    markdown: bool,
+    output_path: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    if markdown {
+        write_output(&exporter.wrap_markdown(diagram), output_path)
+    } else {
+        write_output(diagram, output_path)
+    }
+}
+
+/// Write generated text to a file (creating parent dirs), or to stdout when no path is given
+fn write_output(content: &str, output_path: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let Some(path) = output_path else {
+        println!("{}", content);
+        return Ok(());
+    };
+
+    let path = Path::new(path);
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::write(path, format!("{}\n", content))?;
+    eprintln!("Wrote {}", path.display());
     Ok(())
 }
 
+/// "Module not found" error for exports, with the closest module name when
+/// the input looks like a typo.
+fn module_not_found(module_name: &str, project: &ProjectAST) -> String {
+    let names = project.files.iter().flat_map(|(_, file)| {
+        file.sections.iter().filter_map(|section| match section {
+            Section::Mod(m) => Some(m.name.as_str()),
+            _ => None,
+        })
+    });
+    match closest_match(module_name, names) {
+        Some(suggestion) => {
+            format!("Module '{}' not found; did you mean {}?", module_name, suggestion)
+        }
+        None => format!("Module '{}' not found", module_name),
+    }
+}
+
 fn run_inspect(module_name: &str, filename: &str) -> Result<(), Box<dyn Error>> {
     let file = File::open(filename)?;
     let parsed = parse_surv_file(file)?;
@@ -581,6 +1443,13 @@ fn run_inspect(module_name: &str, filename: &str) -> Result<(), Box<dyn Error>>
         Some(m) => m,
         None => {
             eprintln!("Error: Module 'mod.{}' not found in {}", module_name, filename);
+            let names = parsed.sections.iter().filter_map(|section| match section {
+                Section::Mod(m) => Some(m.name.as_str()),
+                _ => None,
+            });
+            if let Some(suggestion) = closest_match(module_name, names) {
+                eprintln!("Did you mean mod.{}?", suggestion);
+            }
             eprintln!();
             eprintln!("Available modules:");
             for section in &parsed.sections {
@@ -622,12 +1491,14 @@ fn run_inspect(module_name: &str, filename: &str) -> Result<(), Box<dyn Error>>
     // Print pipeline
     if !module.pipeline.is_empty() {
         println!("Pipeline ({} steps):", module.pipeline.len());
-        for (i, step) in module.pipeline.iter().enumerate() {
-            if i == 0 {
-                println!("  {}", step);
-            } else {
+        for (i, stage) in module.pipeline_stages().iter().enumerate() {
+            if i > 0 {
                 println!("    ↓");
-                println!("  {}", step);
+            }
+            if stage.len() == 1 {
+                println!("  {}", stage[0]);
+            } else {
+                println!("  {{ {} }}", stage.join(", "));
             }
         }
         println!();
@@ -677,17 +1548,19 @@ fn print_diff_impl_usage() {
     eprintln!("Options:");
     eprintln!("  --mod <module>    Filter to specific module (with reference closure)");
     eprintln!("  --lang <lang>     Language to check (ts, rust, both) [default: both]");
-    eprintln!("  --format <fmt>    Output format (text, json, md) [default: text]");
+    eprintln!("  --format <fmt>    Output format (text, table, json, md) [default: text]");
     eprintln!("  --strategy <str>  Analysis strategy (static, lsp) [default: static]");
+    eprintln!("  --watch           Re-run when the IR file or workspace sources change");
     eprintln!();
     eprintln!("Examples:");
     eprintln!("  surc diff-impl design.toml .");
     eprintln!("  surc diff-impl design.toml . --mod ui_workspace_pane");
     eprintln!("  surc diff-impl design.toml . --strategy lsp");
+    eprintln!("  surc diff-impl design.toml . --watch");
 }
 
 fn run_diff_impl(args: &[String]) -> Result<(), Box<dyn Error>> {
-    use survibe_parser_rs::diff_impl::{diff_impl, reporter};
+    use survibe_parser_rs::diff_impl::diff_impl;
 
     if args.len() < 2 {
         print_diff_impl_usage();
@@ -702,6 +1575,7 @@ fn run_diff_impl(args: &[String]) -> Result<(), Box<dyn Error>> {
     let mut language = "both";
     let mut format = "text";
     let mut strategy = "static";
+    let mut watch = false;
 
     let mut i = 2;
     while i < args.len() {
@@ -731,8 +1605,8 @@ fn run_diff_impl(args: &[String]) -> Result<(), Box<dyn Error>> {
             "--format" => {
                 if i + 1 < args.len() {
                     format = &args[i + 1];
-                    if !matches!(format, "text" | "json" | "md") {
-                        eprintln!("Error: --format must be 'text', 'json', or 'md'");
+                    if !matches!(format, "text" | "table" | "json" | "md") {
+                        eprintln!("Error: --format must be 'text', 'table', 'json', or 'md'");
                         std::process::exit(1);
                     }
                     i += 2;
@@ -754,6 +1628,10 @@ fn run_diff_impl(args: &[String]) -> Result<(), Box<dyn Error>> {
                     std::process::exit(1);
                 }
             }
+            "--watch" => {
+                watch = true;
+                i += 1;
+            }
             _ => {
                 eprintln!("Error: Unknown option: {}", args[i]);
                 print_diff_impl_usage();
@@ -762,17 +1640,14 @@ fn run_diff_impl(args: &[String]) -> Result<(), Box<dyn Error>> {
         }
     }
 
+    if watch {
+        return watch_diff_impl(ir_file, workspace_root, filter_mod, language, strategy, format);
+    }
+
     // Run diff-impl analysis
     let result = diff_impl(ir_file, workspace_root, filter_mod, language, strategy)?;
 
-    // Format and print output
-    let output = match format {
-        "json" => reporter::report_json(&result),
-        "md" => reporter::report_markdown(&result),
-        _ => reporter::report_text(&result),
-    };
-
-    println!("{}", output);
+    println!("{}", format_diff_report(&result, format));
 
     // Exit with non-zero code if issues detected
     if result.has_issues() {
@@ -781,3 +1656,210 @@ fn run_diff_impl(args: &[String]) -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+fn format_diff_report(result: &survibe_parser_rs::diff_impl::DiffResult, format: &str) -> String {
+    use survibe_parser_rs::diff_impl::reporter;
+
+    match format {
+        "json" => reporter::report_json(result),
+        "md" => reporter::report_markdown(result),
+        "table" => reporter::report_table(result),
+        _ => reporter::report_text(result),
+    }
+}
+
+/// Re-run diff-impl whenever the IR file or a workspace source file changes.
+///
+/// Changes are detected by polling modification times, so no platform-specific
+/// file notification support is required. Analysis errors (e.g. an IR file that
+/// is mid-edit and fails to parse) are reported and the watch continues.
+fn watch_diff_impl(
+    ir_file: &Path,
+    workspace_root: &Path,
+    filter_mod: Option<&str>,
+    language: &str,
+    strategy: &str,
+    format: &str,
+) -> Result<(), Box<dyn Error>> {
+    use survibe_parser_rs::diff_impl::diff_impl;
+    use std::time::Duration;
+
+    let mut last_snapshot = None;
+
+    loop {
+        let snapshot = watch_snapshot(ir_file, workspace_root, language);
+
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            if last_snapshot.is_some() {
+                println!();
+                println!("--- Change detected, re-running diff-impl ---");
+                println!();
+            }
+
+            match diff_impl(ir_file, workspace_root, filter_mod, language, strategy) {
+                Ok(result) => println!("{}", format_diff_report(&result, format)),
+                Err(err) => eprintln!("Error: {}", err),
+            }
+
+            eprintln!(
+                "Watching {} and {} for changes (Ctrl+C to stop)...",
+                ir_file.display(),
+                workspace_root.display()
+            );
+            last_snapshot = Some(snapshot);
+        }
+
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
+/// Collect modification times for the IR file and the workspace sources relevant
+/// to `language`. Build output and dependency directories are skipped.
+fn watch_snapshot(
+    ir_file: &Path,
+    workspace_root: &Path,
+    language: &str,
+) -> Vec<(std::path::PathBuf, std::time::SystemTime)> {
+    let extensions: &[&str] = match language {
+        "rust" => &["rs"],
+        "ts" => &["ts", "tsx"],
+        _ => &["rs", "ts", "tsx"],
+    };
+
+    let mut snapshot = Vec::new();
+
+    if let Ok(modified) = std::fs::metadata(ir_file).and_then(|m| m.modified()) {
+        snapshot.push((ir_file.to_path_buf(), modified));
+    }
+
+    let walker = walkdir::WalkDir::new(workspace_root)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir()
+                && matches!(name.as_ref(), "target" | "node_modules" | ".git"))
+        });
+
+    for entry in walker.filter_map(Result::ok) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let is_source = entry
+            .path()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| extensions.contains(&ext))
+            .unwrap_or(false);
+        if !is_source {
+            continue;
+        }
+        if let Some(modified) = entry.metadata().ok().and_then(|m| m.modified().ok()) {
+            snapshot.push((entry.path().to_path_buf(), modified));
+        }
+    }
+
+    snapshot.sort();
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn strips_the_ascii_flag_before_dispatch() {
+        let mut plain = args(&["surc", "check", "--no-color", "api.toml", "--ascii"]);
+        assert!(take_ascii_flag(&mut plain));
+        assert_eq!(plain, args(&["surc", "check", "api.toml"]));
+
+        let mut unicode = args(&["surc", "check", "api.toml"]);
+        assert!(!take_ascii_flag(&mut unicode));
+        assert_eq!(unicode, args(&["surc", "check", "api.toml"]));
+    }
+
+    #[test]
+    fn extracts_output_option_anywhere_in_the_arguments() {
+        let (rest, output) =
+            extract_output_option(&args(&["mermaid", "-o", "out.mmd", "api.toml"])).unwrap();
+        assert_eq!(rest, args(&["mermaid", "api.toml"]));
+        assert_eq!(output.as_deref(), Some("out.mmd"));
+
+        let (rest, output) = extract_output_option(&args(&["api.toml", "--output", "x"])).unwrap();
+        assert_eq!(rest, args(&["api.toml"]));
+        assert_eq!(output.as_deref(), Some("x"));
+
+        let (rest, output) = extract_output_option(&args(&["api.toml"])).unwrap();
+        assert_eq!(rest, args(&["api.toml"]));
+        assert_eq!(output, None);
+
+        assert!(extract_output_option(&args(&["api.toml", "-o"])).is_err());
+    }
+
+    #[test]
+    fn validate_first_refuses_errors_but_not_warnings() {
+        let broken = survibe_parser_rs::parse_deploy_str(
+            "[deploy.job.deploy]\nrequires = [\"job.missing\"]\nruns = [\"kubectl apply\"]\n",
+        )
+        .unwrap();
+        let err = refuse_invalid_deploy("deploy.toml", &broken).unwrap_err();
+        assert!(err.to_string().starts_with("deploy.toml has "), "{}", err);
+        assert!(err.to_string().ends_with("error(s); not generating"), "{}", err);
+
+        // A job without runs only warns
+        let warned =
+            survibe_parser_rs::parse_deploy_str("[deploy.job.build]\nruns = []\n").unwrap();
+        assert!(check_deploy_file(&warned).iter().any(|d| d.severity == "warning"));
+        assert!(refuse_invalid_deploy("deploy.toml", &warned).is_ok());
+    }
+
+    #[test]
+    fn writes_output_into_missing_directories() {
+        let dir = std::env::temp_dir().join(format!("surc-output-{}", std::process::id()));
+        let path = dir.join("nested").join("out.mmd");
+
+        write_output("graph TD", Some(path.to_str().unwrap())).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "graph TD\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Package a changed and requires b's module without depending on it; b
+    /// is unchanged and has the same problem with c.
+    #[test]
+    fn changed_since_keeps_diagnostics_of_affected_files_only() {
+        let manifest: survibe_parser_rs::Manifest = toml::from_str(
+            "[project]\nname = \"demo\"\n\n[paths]\nir_root = \".\"\n\n\
+             [packages.a]\nroot = \"a\"\n\n[packages.b]\nroot = \"b\"\n\n\
+             [packages.c]\nroot = \"c\"\n",
+        )
+        .unwrap();
+        let file = |path: &str, text: &str| {
+            (PathBuf::from(path), survibe_parser_rs::parse_surv_str(text).unwrap())
+        };
+        let project = ProjectAST::from_files(vec![
+            file(
+                "a/alpha.toml",
+                "package = \"a\"\nrequire = [\"mod.beta\"]\n\n[mod.alpha]\npurpose = \"x\"\n",
+            ),
+            file(
+                "b/beta.toml",
+                "package = \"b\"\nrequire = [\"mod.gamma\"]\n\n[mod.beta]\npurpose = \"x\"\n",
+            ),
+            file("c/gamma.toml", "package = \"c\"\n\n[mod.gamma]\npurpose = \"x\"\n"),
+        ]);
+        let (symbols, _) = build_symbol_table(&project);
+        let changed = BTreeSet::from([PathBuf::from("a/alpha.toml")]);
+        let affected = reverse_dependency_closure(&project, &symbols, &changed);
+
+        let mut diags = check_package_dependencies(&project, &manifest);
+        assert_eq!(diags.len(), 2);
+        retain_in_files(&mut diags, &project, &affected);
+
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].location.starts_with("a/alpha.toml"));
+    }
+}
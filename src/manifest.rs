@@ -12,6 +12,9 @@ pub struct Manifest {
 #[derive(Debug, Deserialize)]
 pub struct ProjectSection {
     pub name: String,
+    /// Disable the project-wide fallback when resolving unqualified references.
+    #[serde(default)]
+    pub strict_namespaces: bool,
 }
 
 #[derive(Debug, Deserialize)]
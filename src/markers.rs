@@ -0,0 +1,53 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Switch to plain ASCII markers when asked (`--no-color` / `--ascii`) or
+/// when stdout is not a terminal, e.g. redirected to a CI log.
+pub fn init(requested: bool) {
+    ASCII.store(requested || !std::io::stdout().is_terminal(), Ordering::Relaxed);
+}
+
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+pub fn ok() -> &'static str {
+    pick("✓", "[OK]")
+}
+
+pub fn err() -> &'static str {
+    pick("✗", "[ERR]")
+}
+
+pub fn warn() -> &'static str {
+    pick("⚠", "[WARN]")
+}
+
+pub fn info() -> &'static str {
+    pick("ℹ", "[INFO]")
+}
+
+fn pick(unicode: &'static str, plain: &'static str) -> &'static str {
+    if ascii() {
+        plain
+    } else {
+        unicode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switches_between_unicode_and_ascii_markers() {
+        init(true);
+        assert!(ascii());
+        assert_eq!([ok(), err(), warn(), info()], ["[OK]", "[ERR]", "[WARN]", "[INFO]"]);
+
+        ASCII.store(false, Ordering::Relaxed);
+        assert_eq!([ok(), err(), warn(), info()], ["✓", "✗", "⚠", "ℹ"]);
+    }
+}
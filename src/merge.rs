@@ -0,0 +1,188 @@
+use crate::ast::{RequireDecl, Section, StatusSection, SurvFile};
+use crate::diagnostic::Diagnostic;
+use crate::project::ProjectAST;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Flatten every file of a project into one `SurvFile` (the inverse of `split`).
+///
+/// Identical symbols defined in several files are kept once; same-named
+/// symbols with different definitions are reported as `MergeConflict`.
+/// Requires on modules that are part of the merge are dropped, the rest kept.
+/// Package, namespace and imports do not survive flattening.
+pub fn merge_project(project: &ProjectAST) -> (SurvFile, Vec<Diagnostic>) {
+    let mut diags = Vec::new();
+    let mut meta = None;
+    let mut status: Option<StatusSection> = None;
+    // symbol id -> (defining file, section)
+    let mut symbols: BTreeMap<String, (&Path, &Section)> = BTreeMap::new();
+    let mut order = Vec::new();
+
+    for (path, file) in &project.files {
+        for section in &file.sections {
+            let id = match section {
+                Section::Meta(m) => {
+                    meta.get_or_insert_with(|| m.clone());
+                    continue;
+                }
+                Section::Status(s) => {
+                    merge_status(&mut status, s);
+                    continue;
+                }
+                Section::Schema(s) => format!("schema.{}", s.name),
+                Section::Func(f) => format!("func.{}", f.name),
+                Section::Mod(m) => format!("mod.{}", m.name),
+            };
+
+            match symbols.get(&id) {
+                None => {
+                    symbols.insert(id.clone(), (path.as_path(), section));
+                    order.push(id);
+                }
+                Some((first_path, first)) => {
+                    if !same_definition(first, section) {
+                        diags.push(Diagnostic::new(
+                            "error",
+                            "MergeConflict",
+                            format!(
+                                "{} is defined differently in {} and {}",
+                                id,
+                                first_path.display(),
+                                path.display()
+                            ),
+                            path.display().to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let merged_mods: BTreeSet<&str> = order
+        .iter()
+        .filter(|id| id.starts_with("mod."))
+        .map(String::as_str)
+        .collect();
+    let mut requires = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (_, file) in &project.files {
+        for req in &file.requires {
+            if !merged_mods.contains(req.target.as_str()) && seen.insert(req.target.clone()) {
+                requires.push(RequireDecl {
+                    target: req.target.clone(),
+                });
+            }
+        }
+    }
+
+    let mut sections: Vec<Section> = meta.into_iter().map(Section::Meta).collect();
+    // Keep the parser's section order: schemas, funcs, then mods
+    for prefix in ["schema.", "func.", "mod."] {
+        sections.extend(
+            order
+                .iter()
+                .filter(|id| id.starts_with(prefix))
+                .map(|id| symbols[id].1.clone()),
+        );
+    }
+    sections.extend(status.map(Section::Status));
+
+    let merged = SurvFile {
+        requires,
+        sections,
+        ..Default::default()
+    };
+    (merged, diags)
+}
+
+fn same_definition(a: &Section, b: &Section) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// Union of module statuses; the first file to mention a module wins, and the
+/// latest `updated_at` is kept.
+fn merge_status(merged: &mut Option<StatusSection>, status: &StatusSection) {
+    let Some(existing) = merged else {
+        *merged = Some(status.clone());
+        return;
+    };
+    if status.updated_at > existing.updated_at {
+        existing.updated_at = status.updated_at.clone();
+    }
+    for (name, module) in &status.modules {
+        existing
+            .modules
+            .entry(name.clone())
+            .or_insert_with(|| module.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_surv_str;
+    use std::path::PathBuf;
+
+    fn file(path: &str, text: &str) -> (PathBuf, SurvFile) {
+        (PathBuf::from(path), parse_surv_str(text).expect("parse"))
+    }
+
+    #[test]
+    fn merges_files_and_reports_conflicts() {
+        let project = ProjectAST::from_files(vec![
+            file(
+                "users.toml",
+                r#"
+package = "users"
+require = ["mod.auth", "mod.billing"]
+
+[schema.user]
+kind = "node"
+
+[mod.users]
+purpose = "Users"
+schemas = ["schema.user"]
+"#,
+            ),
+            file(
+                "auth.toml",
+                r#"
+package = "auth"
+
+[schema.user]
+kind = "node"
+
+[schema.session]
+kind = "node"
+
+[mod.auth]
+purpose = "Auth"
+"#,
+            ),
+            file(
+                "legacy.toml",
+                r#"
+[schema.session]
+kind = "edge"
+"#,
+            ),
+        ]);
+
+        let (merged, diags) = merge_project(&project);
+
+        let schemas = merged
+            .sections
+            .iter()
+            .filter(|s| matches!(s, Section::Schema(_)))
+            .count();
+        assert_eq!(schemas, 2);
+        assert_eq!(merged.package, None);
+
+        let requires: Vec<_> = merged.requires.iter().map(|r| r.target.as_str()).collect();
+        assert_eq!(requires, vec!["mod.billing"]);
+
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "MergeConflict");
+        assert!(diags[0].message.contains("schema.session"));
+    }
+}
@@ -0,0 +1,52 @@
+use survibe_parser_rs::load_project;
+use survibe_parser_rs::merge::merge_project;
+use survibe_parser_rs::writer::render_surv_file;
+use std::error::Error;
+use std::path::Path;
+
+use crate::{extract_output_option, markers, write_output};
+
+pub fn run_merge(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (args, output_path) = extract_output_option(args)?;
+
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        print_merge_usage();
+        if args.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(other) = args.get(1) {
+        return Err(format!("Unknown option: {}", other).into());
+    }
+
+    let project = load_project(Path::new(&args[0]))?;
+    let (merged, diags) = merge_project(&project);
+
+    if !diags.is_empty() {
+        for diag in &diags {
+            eprintln!("{} [{}] {}", markers::err(), diag.kind, diag.message);
+            eprintln!("  at {}\n", diag.location);
+        }
+        return Err(format!("{} merge conflict(s); nothing written", diags.len()).into());
+    }
+
+    let mut content = render_surv_file(&merged);
+    if content.ends_with('\n') {
+        content.pop();
+    }
+    write_output(&content, output_path.as_deref())
+}
+
+fn print_merge_usage() {
+    eprintln!("Usage: surc merge <surv.toml> [-o <file>]");
+    eprintln!();
+    eprintln!("Combine every IR file of a project into a single Surv IR file (inverse of split).");
+    eprintln!("Identical shared symbols are kept once; conflicting definitions are errors.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  -o, --output <path>  Write the merged file instead of printing it");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc merge surv.toml -o combined.toml");
+}
@@ -43,28 +43,28 @@ pub fn assign_packages_to_files(
                         package: declared.clone(),
                     });
                 } else {
-                    diagnostics.push(Diagnostic {
-                        severity: "error".into(),
-                        kind: "E_PACKAGE_ROOT_MISMATCH".into(),
-                        message: format!(
+                    diagnostics.push(Diagnostic::new(
+                        "error",
+                        "E_PACKAGE_ROOT_MISMATCH",
+                        format!(
                             "file {} is not inside the root of package '{}'",
                             path.display(),
                             declared
                         ),
-                        location: path.display().to_string(),
-                    });
+                        path.display().to_string(),
+                    ));
                 }
             } else {
-                diagnostics.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "E_PACKAGE_UNKNOWN".into(),
-                    message: format!(
+                diagnostics.push(Diagnostic::new(
+                    "error",
+                    "E_PACKAGE_UNKNOWN",
+                    format!(
                         "file {} declares unknown package '{}'",
                         path.display(),
                         declared
                     ),
-                    location: path.display().to_string(),
-                });
+                    path.display().to_string(),
+                ));
             }
             continue;
         }
@@ -76,29 +76,29 @@ pub fn assign_packages_to_files(
             .collect();
 
         match matching.len() {
-            0 => diagnostics.push(Diagnostic {
-                severity: "error".into(),
-                kind: "E_PACKAGE_UNASSIGNED".into(),
-                message: format!(
+            0 => diagnostics.push(Diagnostic::new(
+                "error",
+                "E_PACKAGE_UNASSIGNED",
+                format!(
                     "file {} does not fall under any package root and has no package header",
                     path.display()
                 ),
-                location: path.display().to_string(),
-            }),
+                path.display().to_string(),
+            )),
             1 => assignments.push(PackageAssignment {
                 file_path: path.clone(),
                 package: matching[0].clone(),
             }),
-            _ => diagnostics.push(Diagnostic {
-                severity: "error".into(),
-                kind: "E_PACKAGE_AMBIGUOUS".into(),
-                message: format!(
+            _ => diagnostics.push(Diagnostic::new(
+                "error",
+                "E_PACKAGE_AMBIGUOUS",
+                format!(
                     "file {} matches multiple package roots: {}",
                     path.display(),
                     matching.join(", ")
                 ),
-                location: path.display().to_string(),
-            }),
+                path.display().to_string(),
+            )),
         }
     }
 
@@ -117,11 +117,10 @@ fn resolve_package_root(project_root: &Path, pkg: &PackageSection) -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
 
     fn file(path: &str, text: &str) -> (PathBuf, SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -140,6 +139,7 @@ mod tests {
         Manifest {
             project: crate::manifest::ProjectSection {
                 name: "test".into(),
+                strict_namespaces: false,
             },
             paths: crate::manifest::PathsSection {
                 ir_root: "ir".into(),
@@ -1,7 +1,8 @@
 use crate::ast::{
-    FuncSection, ImportDecl, MetaSection, ModSection, ModuleStatus, RequireDecl, SchemaSection,
+    FuncSection, ImportDecl, FIELD_ANNOTATIONS, MetaSection, ModSection, ModuleStatus, RequireDecl, SchemaSection,
     Section, StatusSection, SurvFile,
 };
+use crate::diagnostic::Diagnostic;
 use crate::simple_toml::{parse_toml, TomlTable, TomlValue};
 use std::collections::BTreeMap;
 use std::fmt;
@@ -9,6 +10,25 @@ use std::fs::File;
 use std::io::{self, BufReader, Read};
 use std::path::Path;
 
+pub(crate) const TOP_LEVEL_KEYS: &[&str] = &[
+    "package", "namespace", "import", "require", "requires", "meta", "schema", "func", "mod",
+    "status",
+];
+pub(crate) const META_KEYS: &[&str] = &["name", "version", "description"];
+pub(crate) const SCHEMA_KEYS: &[&str] = &[
+    "kind", "role", "type", "from", "to", "base", "label", "fields", "field", "over",
+    "deprecated", "deprecated_note", "impl.bind", "impl.lang", "impl.path",
+];
+pub(crate) const FIELD_KEYS: &[&str] = &["type", "unique", "indexed"];
+pub(crate) const FUNC_KEYS: &[&str] = &[
+    "intent", "input", "output", "design_notes", "impl.bind", "impl.lang", "impl.path",
+];
+pub(crate) const MOD_KEYS: &[&str] = &[
+    "purpose", "kind", "schemas", "funcs", "pipeline", "owners", "label", "boundary",
+];
+pub(crate) const STATUS_KEYS: &[&str] = &["updated_at", "mod"];
+pub(crate) const MODULE_STATUS_KEYS: &[&str] = &["state", "coverage", "notes", "updated_at"];
+
 #[derive(Debug)]
 pub enum ParseError {
     Io(io::Error),
@@ -41,12 +61,28 @@ impl std::error::Error for ParseError {
 
 pub fn parse_surv_file<R: Read>(reader: R) -> Result<SurvFile, ParseError> {
     let raw = parse_toml(BufReader::new(reader))?;
+    build_surv_file(&raw)
+}
+
+/// Parse a Surv IR file and also report keys the parser does not recognise
+/// as `UnknownKey` warnings, and a file using both `require` and `requires`
+/// as `MixedRequireSpelling`.
+pub fn parse_surv_file_with_unknown_keys<R: Read>(
+    reader: R,
+) -> Result<(SurvFile, Vec<Diagnostic>), ParseError> {
+    let raw = parse_toml(BufReader::new(reader))?;
+    let file = build_surv_file(&raw)?;
+    let mut diags = collect_unknown_keys(&raw);
+    diags.extend(check_require_spelling(&raw));
+    Ok((file, diags))
+}
 
-    let package = parse_optional_header_string(&raw, "package")?;
-    let namespace = parse_optional_header_string(&raw, "namespace")?;
-    let imports = parse_imports(&raw)?;
-    let requires = parse_requires(&raw)?;
-    let sections = parse_sections(&raw)?;
+fn build_surv_file(raw: &TomlTable) -> Result<SurvFile, ParseError> {
+    let package = parse_optional_header_string(raw, "package")?;
+    let namespace = parse_optional_header_string(raw, "namespace")?;
+    let imports = parse_imports(raw)?;
+    let requires = parse_requires(raw)?;
+    let sections = parse_sections(raw)?;
 
     Ok(SurvFile {
         package,
@@ -57,6 +93,56 @@ pub fn parse_surv_file<R: Read>(reader: R) -> Result<SurvFile, ParseError> {
     })
 }
 
+/// Parse a Surv IR document held in memory.
+pub fn parse_surv_str(input: &str) -> Result<SurvFile, ParseError> {
+    parse_surv_file(input.as_bytes())
+}
+
+/// Parse a Surv IR document keeping sections in the order they are declared,
+/// rather than grouped by kind as `parse_surv_str` returns them.
+pub fn parse_surv_str_in_source_order(input: &str) -> Result<SurvFile, ParseError> {
+    let mut file = parse_surv_str(input)?;
+    sort_sections_by_source(&mut file, input);
+    Ok(file)
+}
+
+/// Reorder `file.sections` by the position of their headers in `source`.
+///
+/// `[status.mod.x]` counts as the `[status]` section and sub-tables such as
+/// `[schema.user.fields]` as their parent. Sections without a header keep
+/// their relative order after the rest.
+pub fn sort_sections_by_source(file: &mut SurvFile, source: &str) {
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+    for line in source.lines() {
+        let line = line.trim();
+        if line.starts_with("[[") {
+            continue;
+        }
+        let Some(header) = line.strip_prefix('[').and_then(|rest| rest.split(']').next()) else {
+            continue;
+        };
+        let parts: Vec<&str> = header.split('.').map(str::trim).collect();
+        let id = match parts.as_slice() {
+            ["schema" | "func" | "mod", name, ..] => format!("{}.{}", parts[0], name),
+            [kind, ..] => kind.to_string(),
+            [] => continue,
+        };
+        let next = positions.len();
+        positions.entry(id).or_insert(next);
+    }
+
+    file.sections.sort_by_key(|section| {
+        let id = match section {
+            Section::Meta(_) => "meta".to_string(),
+            Section::Schema(s) => format!("schema.{}", s.name),
+            Section::Func(f) => format!("func.{}", f.name),
+            Section::Mod(m) => format!("mod.{}", m.name),
+            Section::Status(_) => "status".to_string(),
+        };
+        positions.get(&id).copied().unwrap_or(usize::MAX)
+    });
+}
+
 pub fn parse_surv_ir<R: Read>(reader: R) -> Result<SurvFile, ParseError> {
     parse_surv_file(reader)
 }
@@ -66,6 +152,76 @@ pub fn parse_file(path: &Path) -> Result<SurvFile, ParseError> {
     parse_surv_file(file)
 }
 
+fn collect_unknown_keys(raw: &TomlTable) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    report_unknown(raw, TOP_LEVEL_KEYS, "", &mut diags);
+
+    if let Some(meta) = get_table(raw, "meta") {
+        report_unknown(meta, META_KEYS, "meta.", &mut diags);
+    }
+    for (section, known) in [("schema", SCHEMA_KEYS), ("func", FUNC_KEYS), ("mod", MOD_KEYS)] {
+        if let Some(table) = get_table(raw, section) {
+            for (name, value) in table {
+                if let TomlValue::Table(entry) = value {
+                    report_unknown(entry, known, &format!("{section}.{name}."), &mut diags);
+                }
+            }
+        }
+    }
+    for (name, schema) in get_table(raw, "schema").into_iter().flatten() {
+        if let Some(fields) = schema.as_table().and_then(|schema| get_table(schema, "field")) {
+            for (field, value) in fields {
+                if let TomlValue::Table(entry) = value {
+                    let prefix = format!("schema.{name}.field.{field}.");
+                    report_unknown(entry, FIELD_KEYS, &prefix, &mut diags);
+                }
+            }
+        }
+    }
+    if let Some(status) = get_table(raw, "status") {
+        report_unknown(status, STATUS_KEYS, "status.", &mut diags);
+        if let Some(modules) = get_table(status, "mod") {
+            for (name, value) in modules {
+                if let TomlValue::Table(entry) = value {
+                    let prefix = format!("status.mod.{name}.");
+                    report_unknown(entry, MODULE_STATUS_KEYS, &prefix, &mut diags);
+                }
+            }
+        }
+    }
+
+    diags
+}
+
+/// `parse_requires` merges both spellings, but a file should stick to one.
+fn check_require_spelling(raw: &TomlTable) -> Option<Diagnostic> {
+    if !(raw.contains_key("require") && raw.contains_key("requires")) {
+        return None;
+    }
+    Some(
+        Diagnostic::new(
+            "warning",
+            "MixedRequireSpelling",
+            "File declares both 'require' and 'requires'; their entries are merged",
+            "requires",
+        )
+        .with_suggestion("move the entries into a single 'require' list".to_string()),
+    )
+}
+
+fn report_unknown(table: &TomlTable, known: &[&str], prefix: &str, diags: &mut Vec<Diagnostic>) {
+    for key in table.keys() {
+        if !known.contains(&key.as_str()) {
+            diags.push(Diagnostic::new(
+                "warning",
+                "UnknownKey",
+                format!("Unknown key '{}'", key),
+                format!("{prefix}{key}"),
+            ));
+        }
+    }
+}
+
 fn parse_optional_header_string(raw: &TomlTable, key: &str) -> Result<Option<String>, ParseError> {
     match raw.get(key) {
         None => Ok(None),
@@ -201,6 +357,19 @@ fn parse_schema_section(name: &str, table: &TomlTable) -> SchemaSection {
             }
         }
     }
+    // Structured form: [schema.user.field.email] type = "string", unique = true.
+    // Annotations are folded into the `fields` string; an inline type wins.
+    if let Some(TomlValue::Table(field_table)) = table.get("field") {
+        for (k, v) in field_table {
+            if let TomlValue::Table(entry) = v {
+                let mut value = fields.remove(k).unwrap_or_else(|| get_string(entry, "type"));
+                for annotation in FIELD_ANNOTATIONS.iter().filter(|a| get_bool(entry, a)) {
+                    value.push_str(&format!(" @{}", annotation));
+                }
+                fields.insert(k.clone(), value);
+            }
+        }
+    }
 
     SchemaSection {
         name: name.to_string(),
@@ -213,6 +382,8 @@ fn parse_schema_section(name: &str, table: &TomlTable) -> SchemaSection {
         label: get_string(table, "label"),
         fields,
         over: parse_string_set(table, "over"),
+        deprecated: get_bool(table, "deprecated"),
+        deprecated_note: get_string(table, "deprecated_note"),
         impl_bind: get_optional_string(table, "impl.bind"),
         impl_lang: get_optional_string(table, "impl.lang"),
         impl_path: get_optional_string(table, "impl.path"),
@@ -233,12 +404,16 @@ fn parse_func_section(name: &str, table: &TomlTable) -> FuncSection {
 }
 
 fn parse_mod_section(name: &str, table: &TomlTable) -> ModSection {
+    let pipeline_chains = parse_pipeline(table, "pipeline");
     ModSection {
         name: name.to_string(),
         purpose: get_string(table, "purpose"),
+        kind: get_string(table, "kind"),
         schemas: parse_string_set(table, "schemas"),
         funcs: parse_string_set(table, "funcs"),
-        pipeline: parse_pipeline(table, "pipeline"),
+        pipeline: pipeline_chains.concat().concat(),
+        pipeline_chains,
+        owners: parse_string_set(table, "owners"),
     }
 }
 
@@ -250,6 +425,14 @@ fn get_string(table: &TomlTable, key: &str) -> String {
         .to_string()
 }
 
+fn get_bool(table: &TomlTable, key: &str) -> bool {
+    match table.get(key) {
+        Some(TomlValue::Bool(b)) => *b,
+        Some(TomlValue::String(s)) => s.trim() == "true",
+        _ => false,
+    }
+}
+
 fn get_optional_string(table: &TomlTable, key: &str) -> Option<String> {
     table
         .get(key)
@@ -270,27 +453,35 @@ fn parse_string_set(table: &TomlTable, key: &str) -> Vec<String> {
     }
 }
 
-fn parse_pipeline(table: &TomlTable, key: &str) -> Vec<String> {
-    match table.get(key) {
-        Some(TomlValue::Table(map)) => {
-            let mut result = Vec::new();
-            for chain in map.keys() {
-                result.extend(parse_pipeline_chain(chain));
+/// Parse a pipeline into chains of stages. Each entry written with `->` is a
+/// chain of its own; runs of single-step entries (`["func.a", "func.b"]`)
+/// form one chain together.
+fn parse_pipeline(table: &TomlTable, key: &str) -> Vec<Vec<Vec<String>>> {
+    let entries: Vec<&str> = match table.get(key) {
+        Some(TomlValue::Table(map)) => map.keys().map(String::as_str).collect(),
+        Some(TomlValue::Array(items)) => items.iter().filter_map(TomlValue::as_str).collect(),
+        Some(TomlValue::String(s)) => vec![s.as_str()],
+        _ => Vec::new(),
+    };
+
+    let mut chains: Vec<Vec<Vec<String>>> = Vec::new();
+    let mut in_step_run = false;
+    for entry in entries {
+        let stages = parse_pipeline_stages(entry);
+        match stages.len() {
+            0 => {}
+            1 if in_step_run => chains.last_mut().unwrap().extend(stages),
+            1 => {
+                chains.push(stages);
+                in_step_run = true;
             }
-            result
-        }
-        Some(TomlValue::Array(items)) => {
-            let mut result = Vec::new();
-            for item in items {
-                if let Some(s) = item.as_str() {
-                    result.extend(parse_pipeline_chain(s));
-                }
+            _ => {
+                chains.push(stages);
+                in_step_run = false;
             }
-            result
         }
-        Some(TomlValue::String(s)) => parse_pipeline_chain(s),
-        _ => Vec::new(),
     }
+    chains
 }
 
 fn parse_inline_brace_set(input: &str) -> Vec<String> {
@@ -311,28 +502,35 @@ fn parse_inline_brace_set(input: &str) -> Vec<String> {
         .collect()
 }
 
-fn parse_pipeline_chain(input: &str) -> Vec<String> {
+/// Parse a chain such as `func.a -> { func.b, func.c } -> func.d` into stages.
+/// A braced group is a set of parallel branches that all follow the previous
+/// stage and all feed the next one.
+fn parse_pipeline_stages(input: &str) -> Vec<Vec<String>> {
     let mut s = input.trim();
-    if let Some(stripped) = s.strip_prefix('{') {
-        s = stripped;
-    }
-    if let Some(stripped) = s.strip_suffix('}') {
-        s = stripped;
+    // Strip braces wrapping the whole chain, but not a leading branch group
+    if s.starts_with('{') && s.find('}') == Some(s.len() - 1) {
+        s = &s[1..s.len() - 1];
     }
     s = s.trim();
     if s.is_empty() {
         return Vec::new();
     }
     s.split("->")
-        .map(|part| part.trim().to_string())
-        .filter(|step| !step.is_empty())
+        .map(|part| {
+            let part = part.trim();
+            if part.starts_with('{') {
+                parse_inline_brace_set(part)
+            } else {
+                vec![part.to_string()]
+            }
+        })
+        .filter(|stage| stage.iter().any(|step| !step.is_empty()))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
 
     const SAMPLE_IR: &str = r#"
 namespace = "example.user"
@@ -383,15 +581,90 @@ schemas = ["schema.user", "schema.create_user_req", "schema.users_snapshot"]
 funcs   = ["func.create_user", "func.save_user", "func.get_user"]
 "#;
 
+    #[test]
+    fn preserves_section_source_order_on_request() {
+        let ir = r#"
+[mod.api]
+purpose = "API"
+
+[func.create]
+intent = "Create"
+
+[schema.user]
+kind = "node"
+
+[schema.user.fields]
+id = "string"
+
+[meta]
+name = "ordered"
+"#;
+        let ids = |file: &SurvFile| -> Vec<String> {
+            file.sections
+                .iter()
+                .map(|section| match section {
+                    Section::Meta(_) => "meta".to_string(),
+                    Section::Schema(s) => format!("schema.{}", s.name),
+                    Section::Func(f) => format!("func.{}", f.name),
+                    Section::Mod(m) => format!("mod.{}", m.name),
+                    Section::Status(_) => "status".to_string(),
+                })
+                .collect()
+        };
+
+        let grouped = parse_surv_str(ir).unwrap();
+        assert_eq!(ids(&grouped), ["meta", "schema.user", "func.create", "mod.api"]);
+
+        let ordered = parse_surv_str_in_source_order(ir).unwrap();
+        assert_eq!(ids(&ordered), ["mod.api", "func.create", "schema.user", "meta"]);
+    }
+
     #[test]
     fn parses_sample_ir() {
-        let file = parse_surv_file(Cursor::new(SAMPLE_IR)).expect("parse");
+        let file = parse_surv_str(SAMPLE_IR).expect("parse");
         assert_eq!(file.namespace.as_deref(), Some("example.user"));
         assert_eq!(file.imports.len(), 1);
         assert_eq!(file.requires.len(), 1);
         assert_eq!(file.sections.len(), 8);
     }
 
+    #[test]
+    fn reports_unknown_keys() {
+        let input = r#"
+pacakge = "users"
+
+[schema.user]
+kind = "node"
+typ = "User"
+
+[status.mod.api]
+state = "done"
+progress = "1.0"
+"#;
+        let (_, diags) = parse_surv_file_with_unknown_keys(input.as_bytes()).expect("parse");
+        let locations: Vec<_> = diags.iter().map(|d| d.location.as_str()).collect();
+        assert_eq!(locations.len(), 3);
+        assert!(locations.contains(&"pacakge"));
+        assert!(locations.contains(&"schema.user.typ"));
+        assert!(locations.contains(&"status.mod.api.progress"));
+
+        let (_, clean) = parse_surv_file_with_unknown_keys(SAMPLE_IR.as_bytes()).expect("parse");
+        assert!(clean.is_empty(), "unexpected: {:?}", clean);
+    }
+
+    #[test]
+    fn warns_when_both_require_spellings_are_used() {
+        let input = r#"
+require = ["mod.auth"]
+requires = ["mod.storage"]
+"#;
+        let (file, diags) = parse_surv_file_with_unknown_keys(input.as_bytes()).expect("parse");
+        assert_eq!(file.requires.len(), 2);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "MixedRequireSpelling");
+        assert_eq!(diags[0].severity, "warning");
+    }
+
     #[test]
     fn parses_inline_brace_set() {
         let cases = vec![
@@ -432,9 +705,55 @@ funcs   = ["func.create_user", "func.save_user", "func.get_user"]
         ];
 
         for (input, expected) in cases {
-            assert_eq!(parse_pipeline_chain(input), expected);
+            assert_eq!(parse_pipeline_stages(input).concat(), expected);
         }
     }
+
+    #[test]
+    fn parses_pipeline_branches() {
+        let stages = parse_pipeline_stages("func.a -> { func.b, func.c } -> func.d");
+        assert_eq!(
+            stages,
+            vec![
+                vec!["func.a".to_string()],
+                vec!["func.b".to_string(), "func.c".to_string()],
+                vec!["func.d".to_string()],
+            ]
+        );
+
+        let leading = parse_pipeline_stages("{ func.a, func.b } -> func.c");
+        assert_eq!(leading.len(), 2);
+        assert_eq!(leading[0].len(), 2);
+    }
+
+    #[test]
+    fn keeps_pipeline_chains_separate() {
+        let file = parse_surv_str(
+            r#"
+[mod.single]
+pipeline = ["func.a", "func.b", "func.c"]
+
+[mod.chains]
+pipeline = ["func.a -> func.b", "func.c -> func.d", "func.e"]
+"#,
+        )
+        .unwrap();
+        let module = |name: &str| {
+            file.sections
+                .iter()
+                .find_map(|section| match section {
+                    Section::Mod(m) if m.name == name => Some(m),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        assert_eq!(module("single").pipeline_chains.len(), 1);
+        let chains = &module("chains").pipeline_chains;
+        assert_eq!(chains.len(), 3);
+        assert_eq!(chains[1].concat(), vec!["func.c", "func.d"]);
+        assert_eq!(module("chains").pipeline_stages().len(), 5);
+    }
 }
 
 fn parse_status_section(table: &TomlTable) -> StatusSection {
@@ -448,6 +767,7 @@ fn parse_status_section(table: &TomlTable) -> StatusSection {
                 let state = get_string(mod_status_table, "state");
                 let coverage = get_f64(mod_status_table, "coverage");
                 let notes = get_string(mod_status_table, "notes");
+                let updated_at = get_string(mod_status_table, "updated_at");
 
                 modules.insert(
                     module_name.clone(),
@@ -455,6 +775,7 @@ fn parse_status_section(table: &TomlTable) -> StatusSection {
                         state,
                         coverage,
                         notes,
+                        updated_at,
                     },
                 );
             }
@@ -1,5 +1,5 @@
 use crate::ast::{Section, SurvFile};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -44,6 +44,15 @@ impl ProjectAST {
         ProjectAST { files, mods }
     }
 
+    /// The project with only the files in `keep`. `mods` stays complete, so
+    /// requires into the files left out still resolve.
+    pub fn restricted_to(&self, keep: &BTreeSet<PathBuf>) -> ProjectAST {
+        ProjectAST {
+            files: self.files.iter().filter(|(path, _)| keep.contains(path)).cloned().collect(),
+            mods: self.mods.clone(),
+        }
+    }
+
     pub fn collect_normalized_requires(&self) -> Vec<NormalizedRequire> {
         let mut deps = Vec::new();
 
@@ -82,12 +91,11 @@ impl ProjectAST {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::parse_surv_file;
+    use crate::parser::parse_surv_str;
     use std::collections::HashSet;
-    use std::io::Cursor;
 
     fn file(path: &str, text: &str) -> (PathBuf, SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -139,4 +147,33 @@ funcs = []
         assert_eq!(dep.from_mod, "mod.alpha");
         assert_eq!(dep.to_mod, "mod.shared");
     }
+
+    #[test]
+    fn restricted_project_still_resolves_requires() {
+        let files = vec![
+            file(
+                "a.toml",
+                r#"
+require = ["mod.shared"]
+
+[mod.alpha]
+purpose = "test"
+"#,
+            ),
+            file(
+                "b.toml",
+                r#"
+[mod.shared]
+purpose = "test"
+"#,
+            ),
+        ];
+
+        let project = ProjectAST::from_files(files);
+        let restricted = project.restricted_to(&BTreeSet::from([PathBuf::from("a.toml")]));
+        assert_eq!(restricted.files.len(), 1);
+        assert_eq!(restricted.files[0].0, PathBuf::from("a.toml"));
+        let diags = crate::project_checker::check_project(&restricted);
+        assert!(diags.iter().all(|d| d.kind != "UnresolvedRequire"), "{:?}", diags);
+    }
 }
@@ -1,12 +1,157 @@
+use crate::ast::Section;
+use crate::checker::duplicate_func_notes;
 use crate::diagnostic::Diagnostic;
+use crate::manifest::Manifest;
 use crate::project::{NormalizedRequire, ProjectAST};
-use std::collections::HashMap;
+use crate::symbol::{build_symbol_table, SymbolEntry, SymbolKind};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 pub fn check_project(project: &ProjectAST) -> Vec<Diagnostic> {
     let normalized = project.collect_normalized_requires();
     ProjectChecker::new(project, normalized).check()
 }
 
+/// Opt-in lint (`project-check --lint-duplicates`): funcs in any file of the
+/// project with the same input and output schemas, labelled `path: func.x`.
+pub fn check_duplicate_funcs_in_project(project: &ProjectAST) -> Vec<Diagnostic> {
+    let funcs = project.files.iter().flat_map(|(path, file)| {
+        file.sections.iter().filter_map(move |section| match section {
+            Section::Func(func) => Some((format!("{}: func.{}", path.display(), func.name), func)),
+            _ => None,
+        })
+    });
+    duplicate_func_notes(funcs)
+}
+
+/// Report manifest packages that list themselves in `depends`.
+pub fn check_self_dependent_packages(manifest: &Manifest) -> Vec<Diagnostic> {
+    let mut names: Vec<&String> = manifest.packages.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter(|name| manifest.packages[*name].depends.contains(name))
+        .map(|name| {
+            Diagnostic::new(
+                "error",
+                "SelfDependentPackage",
+                format!("Package '{}' lists itself in depends", name),
+                format!("packages.{}.depends", name),
+            )
+            .with_suggestion(format!(
+                "remove \"{}\" from packages.{}.depends",
+                name, name
+            ))
+        })
+        .collect()
+}
+
+/// Report imports and module requires that reach into a package the
+/// importing package does not list in its manifest `depends`.
+pub fn check_package_dependencies(project: &ProjectAST, manifest: &Manifest) -> Vec<Diagnostic> {
+    let file_packages: HashMap<_, _> = project
+        .files
+        .iter()
+        .filter_map(|(path, file)| file.package.as_deref().map(|pkg| (path, pkg)))
+        .collect();
+    let mod_packages: HashMap<&str, &str> = project
+        .mods
+        .iter()
+        .filter_map(|(id, mod_ref)| {
+            file_packages
+                .get(&mod_ref.file)
+                .map(|pkg| (id.as_str(), *pkg))
+        })
+        .collect();
+
+    // (file, package, target package) already reported
+    let mut reported = BTreeSet::new();
+    let mut diagnostics = Vec::new();
+    let mut report = |path: &std::path::Path, package: &str, target: &str, via: String| {
+        if package == target || !manifest.packages.contains_key(target) {
+            return;
+        }
+        let Some(pkg) = manifest.packages.get(package) else {
+            return;
+        };
+        if pkg.depends.iter().any(|dep| dep == target) {
+            return;
+        }
+        if !reported.insert((path.to_path_buf(), target.to_string())) {
+            return;
+        }
+        diagnostics.push(
+            Diagnostic::new(
+                "error",
+                "UndeclaredPackageDependency",
+                format!(
+                    "Package '{}' uses package '{}' ({}) but does not list it in depends",
+                    package, target, via
+                ),
+                path.display().to_string(),
+            )
+            .with_package(target.to_string()),
+        );
+    };
+
+    for (path, file) in &project.files {
+        let Some(package) = file.package.as_deref() else {
+            continue;
+        };
+        for import in &file.imports {
+            let target = import.target.split_whitespace().next().unwrap_or("");
+            report(path, package, target, format!("import {}", import.target));
+        }
+    }
+
+    for edge in project.collect_normalized_requires() {
+        let (Some(from_pkg), Some(to_pkg)) = (
+            mod_packages.get(edge.from_mod.as_str()),
+            mod_packages.get(edge.to_mod.as_str()),
+        ) else {
+            continue;
+        };
+        report(
+            &edge.file,
+            from_pkg,
+            to_pkg,
+            format!("{} requires {}", edge.from_mod, edge.to_mod),
+        );
+    }
+
+    diagnostics
+}
+
+/// Report manifest packages that no loaded file declares a module for,
+/// which usually means a mistyped `root`.
+pub fn check_empty_packages(project: &ProjectAST, manifest: &Manifest) -> Vec<Diagnostic> {
+    let packages_with_mods: BTreeSet<&str> = project
+        .files
+        .iter()
+        .filter(|(_, file)| {
+            file.sections
+                .iter()
+                .any(|section| matches!(section, crate::ast::Section::Mod(_)))
+        })
+        .filter_map(|(_, file)| file.package.as_deref())
+        .collect();
+
+    let mut names: Vec<&String> = manifest.packages.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter(|name| !packages_with_mods.contains(name.as_str()))
+        .map(|name| {
+            let root = &manifest.packages[name].root;
+            Diagnostic::new(
+                "warning",
+                "EmptyPackage",
+                format!("Package '{}' (root '{}') contains no modules", name, root),
+                root.clone(),
+            )
+        })
+        .collect()
+}
+
 struct ProjectChecker<'a> {
     project: &'a ProjectAST,
     normalized: Vec<NormalizedRequire>,
@@ -25,9 +170,116 @@ impl<'a> ProjectChecker<'a> {
     fn check(mut self) -> Vec<Diagnostic> {
         self.check_requires();
         self.check_cycles();
+        self.check_namespaces();
+        self.check_unused_symbols();
         self.diagnostics
     }
 
+    /// Unused schemas and funcs across every file, so a symbol defined in one
+    /// file and referenced only from another is not reported. References are
+    /// matched by local name, whatever package prefix they carry.
+    fn check_unused_symbols(&mut self) {
+        let mut used_schemas = BTreeSet::new();
+        let mut used_funcs = BTreeSet::new();
+        for (_, file) in &self.project.files {
+            for section in &file.sections {
+                match section {
+                    Section::Func(func) => {
+                        let schemas = func.input.iter().chain(&func.output);
+                        used_schemas.extend(schemas.map(|r| local_name(r)));
+                    }
+                    Section::Schema(schema) => {
+                        let links = [&schema.from, &schema.to].into_iter().chain(&schema.over);
+                        used_schemas.extend(links.map(|r| local_name(r)));
+                    }
+                    Section::Mod(module) => {
+                        used_schemas.extend(module.schemas.iter().map(|r| local_name(r)));
+                        let funcs = module.funcs.iter().chain(&module.pipeline);
+                        used_funcs.extend(funcs.map(|r| local_name(r)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let (symbols, _) = build_symbol_table(self.project);
+        for entry in &symbols.entries {
+            let local = entry.local_name.as_str();
+            let diag = match entry.kind {
+                SymbolKind::Schema if !used_schemas.contains(local) => Diagnostic {
+                    kind: "ProjectUnusedSchema".into(),
+                    ..unused_symbol(entry)
+                },
+                SymbolKind::Func if !used_funcs.contains(local) => Diagnostic {
+                    kind: "ProjectUnusedFunc".into(),
+                    ..unused_symbol(entry)
+                },
+                _ => continue,
+            };
+            self.diagnostics.push(diag);
+        }
+    }
+
+    fn check_namespaces(&mut self) {
+        // namespace -> package -> first file declaring it
+        let mut owners: BTreeMap<&str, BTreeMap<Option<&str>, String>> = BTreeMap::new();
+
+        for (path, file) in &self.project.files {
+            let Some(namespace) = file.namespace.as_deref() else {
+                continue;
+            };
+            let location = path.display().to_string();
+
+            if !is_valid_namespace(namespace) {
+                self.diagnostics.push(Diagnostic::new(
+                    "error",
+                    "InvalidNamespace",
+                    format!(
+                        "Namespace '{}' is malformed (expected dot-separated segments of [a-z0-9_])",
+                        namespace
+                    ),
+                    location.clone(),
+                ));
+            }
+
+            owners
+                .entry(namespace)
+                .or_default()
+                .entry(file.package.as_deref())
+                .or_insert(location);
+        }
+
+        for (namespace, packages) in owners {
+            if packages.len() < 2 {
+                continue;
+            }
+            let claimed_by: Vec<String> = packages
+                .iter()
+                .map(|(package, file)| format!("{} ({})", package.unwrap_or("<no package>"), file))
+                .collect();
+            self.diagnostics.push(
+                Diagnostic::new(
+                    "warning",
+                    "DuplicateNamespace",
+                    format!(
+                        "Namespace '{}' is declared in more than one package: {}",
+                        namespace,
+                        claimed_by.join(", ")
+                    ),
+                    packages.values().nth(1).cloned().unwrap_or_default(),
+                )
+                .with_package(
+                    packages
+                        .keys()
+                        .next()
+                        .copied()
+                        .flatten()
+                        .map(str::to_string),
+                ),
+            );
+        }
+    }
+
     fn check_requires(&mut self) {
         for edge in &self.normalized {
             if !self.project.mods.contains_key(&edge.to_mod) {
@@ -35,12 +287,12 @@ impl<'a> ProjectChecker<'a> {
                     "Module '{}' (required from '{}') does not exist",
                     edge.to_mod, edge.from_mod
                 );
-                self.diagnostics.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "UnresolvedRequire".into(),
-                    message: msg,
-                    location: edge.file.display().to_string(),
-                });
+                self.diagnostics.push(Diagnostic::new(
+                    "error",
+                    "UnresolvedRequire",
+                    msg,
+                    edge.file.display().to_string(),
+                ));
             }
         }
     }
@@ -123,26 +375,47 @@ impl<'a> ProjectChecker<'a> {
                     })
                     .next()
                     .unwrap_or_else(|| "require graph".into());
-                self.diagnostics.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "RequireCycle".into(),
-                    message: msg,
-                    location: file,
-                });
+                self.diagnostics
+                    .push(Diagnostic::new("error", "RequireCycle", msg, file));
             }
         }
     }
 }
 
+fn unused_symbol(entry: &SymbolEntry) -> Diagnostic {
+    let id = format!("{}.{}", entry.kind.as_str(), entry.local_name);
+    Diagnostic::new(
+        "warning",
+        String::new(),
+        format!("{} is not referenced anywhere in the project", id),
+        format!("{}: {}", entry.file.display(), id),
+    )
+}
+
+/// `schema.user`, `backend.schema.user` and fully-qualified names all end in
+/// the local name.
+fn local_name(reference: &str) -> &str {
+    reference.trim().rsplit('.').next().unwrap_or_default()
+}
+
+/// Namespaces are dot-separated segments of lowercase letters, digits and `_`
+fn is_valid_namespace(namespace: &str) -> bool {
+    namespace.split('.').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
     use std::path::PathBuf;
 
     fn file(path: &str, text: &str) -> (PathBuf, crate::ast::SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -167,6 +440,46 @@ funcs = []
             .any(|d| d.kind == "UnresolvedRequire" && d.location.contains("a.toml")));
     }
 
+    #[test]
+    fn reports_unused_symbols_across_files() {
+        let files = vec![
+            file(
+                "schemas.toml",
+                r#"
+[schema.user]
+kind = "node"
+
+[schema.orphan]
+kind = "node"
+
+[func.lookup_user]
+intent = "Find a user"
+output = ["schema.user"]
+
+[func.unwired]
+intent = "Nothing calls this"
+"#,
+            ),
+            file(
+                "api.toml",
+                r#"
+[mod.users]
+purpose = "Users"
+pipeline = ["func.lookup_user"]
+"#,
+            ),
+        ];
+
+        let project = ProjectAST::from_files(files);
+        let diags = check_project(&project);
+        let unused: Vec<&str> = diags
+            .iter()
+            .filter(|d| d.kind.starts_with("ProjectUnused"))
+            .map(|d| d.location.as_str())
+            .collect();
+        assert_eq!(unused, vec!["schemas.toml: schema.orphan", "schemas.toml: func.unwired"]);
+    }
+
     #[test]
     fn detects_require_cycles() {
         let files = vec![
@@ -198,4 +511,209 @@ funcs = []
         let diags = check_project(&project);
         assert!(diags.iter().any(|d| d.kind == "RequireCycle"));
     }
+
+    #[test]
+    fn validates_namespace_format_and_uniqueness() {
+        let files = vec![
+            file(
+                "backend/api.toml",
+                r#"
+package = "backend"
+namespace = "app.api"
+"#,
+            ),
+            file(
+                "frontend/api.toml",
+                r#"
+package = "frontend"
+namespace = "app.api"
+"#,
+            ),
+            file(
+                "backend/users.toml",
+                r#"
+package = "backend"
+namespace = "app.api"
+"#,
+            ),
+            file(
+                "bad.toml",
+                r#"
+package = "backend"
+namespace = "App Users"
+"#,
+            ),
+        ];
+
+        let project = ProjectAST::from_files(files);
+        let diags = check_project(&project);
+
+        let invalid: Vec<_> = diags.iter().filter(|d| d.kind == "InvalidNamespace").collect();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].location, "bad.toml");
+
+        let duplicate: Vec<_> = diags.iter().filter(|d| d.kind == "DuplicateNamespace").collect();
+        assert_eq!(duplicate.len(), 1);
+        assert!(duplicate[0].message.contains("backend") && duplicate[0].message.contains("frontend"));
+
+        assert!(is_valid_namespace("app.users_v2"));
+        assert!(!is_valid_namespace("app..users"));
+    }
+
+    #[test]
+    fn reports_undeclared_package_dependencies() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[paths]
+ir_root = "."
+
+[packages.backend]
+root = "backend"
+depends = ["common"]
+
+[packages.common]
+root = "common"
+
+[packages.frontend]
+root = "frontend"
+"#,
+        )
+        .unwrap();
+
+        let files = vec![
+            file(
+                "common/auth.toml",
+                r#"
+package = "common"
+
+[mod.auth]
+purpose = "test"
+"#,
+            ),
+            file(
+                "backend/api.toml",
+                r#"
+package = "backend"
+import = ["common"]
+require = ["mod.auth"]
+
+[mod.api]
+purpose = "test"
+"#,
+            ),
+            file(
+                "frontend/ui.toml",
+                r#"
+package = "frontend"
+require = ["mod.api"]
+
+[mod.ui]
+purpose = "test"
+"#,
+            ),
+        ];
+
+        let project = ProjectAST::from_files(files);
+        let diags = check_package_dependencies(&project, &manifest);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "UndeclaredPackageDependency");
+        assert_eq!(diags[0].package.as_deref(), Some("backend"));
+        assert_eq!(diags[0].location, "frontend/ui.toml");
+    }
+
+    #[test]
+    fn reports_packages_without_modules() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[paths]
+ir_root = "."
+
+[packages.backend]
+root = "backend"
+
+[packages.shared]
+root = "shraed"
+"#,
+        )
+        .unwrap();
+
+        let files = vec![file(
+            "backend/api.toml",
+            r#"
+package = "backend"
+
+[mod.api]
+purpose = "test"
+"#,
+        )];
+
+        let project = ProjectAST::from_files(files);
+        let diags = check_empty_packages(&project, &manifest);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "EmptyPackage");
+        assert!(diags[0].message.contains("'shared'"));
+        assert_eq!(diags[0].location, "shraed");
+    }
+
+    #[test]
+    fn reports_self_dependent_packages() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[paths]
+ir_root = "."
+
+[packages.backend]
+root = "backend"
+depends = ["common", "backend"]
+
+[packages.common]
+root = "common"
+"#,
+        )
+        .unwrap();
+
+        let diags = check_self_dependent_packages(&manifest);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "SelfDependentPackage");
+        assert_eq!(diags[0].location, "packages.backend.depends");
+    }
+
+    #[test]
+    fn notes_duplicate_funcs_across_files() {
+        let files = vec![
+            file(
+                "a.toml",
+                r#"
+[func.load_user]
+intent = "test"
+input = ["schema.user_id"]
+output = ["schema.user"]
+"#,
+            ),
+            file(
+                "b.toml",
+                r#"
+[func.fetch_user]
+intent = "test"
+input = ["users.schema.user_id"]
+output = ["users.schema.user"]
+"#,
+            ),
+        ];
+
+        let project = ProjectAST::from_files(files);
+        let diags = check_duplicate_funcs_in_project(&project);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "PossibleDuplicateFunc");
+        assert_eq!(diags[0].location, "b.toml: func.fetch_user");
+    }
 }
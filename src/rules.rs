@@ -0,0 +1,371 @@
+use serde::Serialize;
+
+/// Metadata for one diagnostic kind the crate can emit.
+#[derive(Debug, Clone, Serialize)]
+pub struct Rule {
+    pub kind: &'static str,
+    /// Severity the checker reports it with (`error`, `warning`, `info`)
+    pub severity: &'static str,
+    /// Which checker emits it: `surv`, `parse`, `project`, `merge`, `deploy`
+    pub checker: &'static str,
+    pub description: &'static str,
+}
+
+const fn rule(
+    kind: &'static str,
+    severity: &'static str,
+    checker: &'static str,
+    description: &'static str,
+) -> Rule {
+    Rule {
+        kind,
+        severity,
+        checker,
+        description,
+    }
+}
+
+/// Every diagnostic kind, grouped by checker.
+pub const RULES: &[Rule] = &[
+    // Surv IR (`surc check`)
+    rule(
+        "UndefinedSchema", "error", "surv",
+        "A func input/output names a schema that is not defined",
+    ),
+    rule("UndefinedSchemaInMod", "error", "surv", "A mod lists a schema that is not defined"),
+    rule("UndefinedFuncInMod", "error", "surv", "A mod lists a func that is not defined"),
+    rule(
+        "UndefinedFuncInPipeline", "error", "surv",
+        "A pipeline step names a func that is not defined",
+    ),
+    rule(
+        "UndefinedSchemaInBoundary", "error", "surv",
+        "A boundary lists a schema that is not defined",
+    ),
+    rule(
+        "UndefinedSchemaInEdgeFrom", "error", "surv",
+        "An edge schema's `from` is not a defined schema",
+    ),
+    rule(
+        "UndefinedSchemaInEdgeTo", "error", "surv",
+        "An edge schema's `to` is not a defined schema",
+    ),
+    rule(
+        "IncompleteEdgeSchema", "error", "surv",
+        "An edge schema is missing `from` or `to`",
+    ),
+    rule("PipelineStepIsNotFunc", "error", "surv", "A pipeline step is not a `func.*` reference"),
+    rule("PipelineCycle", "error", "surv", "A pipeline visits the same func more than once"),
+    rule(
+        "PipelineTypeMismatch", "warning", "surv",
+        "Adjacent pipeline steps share no schema between output and input",
+    ),
+    rule(
+        "PipelineDataFlowBroken", "warning", "surv",
+        "A step needs a schema no earlier step or the mod provides",
+    ),
+    rule(
+        "PipelineOrderViolatesDataFlow", "warning", "surv",
+        "A step is listed before the step that produces its input",
+    ),
+    rule(
+        "PipelineUsesUndeclaredSchema", "warning", "surv",
+        "A pipeline func uses a schema the mod does not list",
+    ),
+    rule(
+        "DeadPipelineOutput", "warning", "surv",
+        "A pipeline step outputs a schema no later step consumes",
+    ),
+    rule("DeprecatedSchemaUsed", "warning", "surv", "A func or mod references a deprecated schema"),
+    rule(
+        "UnknownFieldAnnotation", "warning", "surv",
+        "A schema field has an annotation other than @unique or @indexed",
+    ),
+    rule(
+        "DuplicateFieldAnnotation", "warning", "surv",
+        "A schema field repeats the same annotation",
+    ),
+    rule("UnusedSchema", "warning", "surv", "A schema is defined but never referenced"),
+    rule(
+        "StaleStatusEntry", "warning", "surv",
+        "A [status.mod.x] entry has no matching [mod.x]",
+    ),
+    rule("UnusedFunc", "warning", "surv", "A func is not referenced by any mod"),
+    rule("MissingImplBinding", "warning", "surv", "A func has no `impl` binding (--require-impl)"),
+    rule(
+        "ImplLangMismatch", "warning", "surv",
+        "An impl binding's file extension disagrees with its lang (--impl-lang)",
+    ),
+    rule(
+        "InvalidVersion", "warning", "surv",
+        "`meta.version` is not a semantic version (--check-version)",
+    ),
+    rule("ModuleWithoutOwner", "warning", "surv", "A mod has no owners (--require-owners)"),
+    rule(
+        "ModuleWithoutPipeline", "info", "surv",
+        "A mod has funcs but no pipeline (off with --no-lint-pipeline)",
+    ),
+    rule(
+        "SuspiciousPipelineOrder", "info", "surv",
+        "A step's intent suggests another position in the pipeline (--lint-intent)",
+    ),
+    rule(
+        "OverlappingBoundaries", "info", "surv",
+        "A schema belongs to more than one boundary (--lint-boundaries)",
+    ),
+    rule(
+        "PossibleDuplicateFunc", "info", "surv",
+        "Funcs share the same input and output schemas (--lint-duplicates)",
+    ),
+    // Parsing
+    rule(
+        "UnknownKey", "warning", "parse",
+        "A section has a key the format does not define (fails with --strict)",
+    ),
+    rule(
+        "MixedRequireSpelling", "warning", "parse",
+        "A file declares both `require` and `requires` (they are merged)",
+    ),
+    // Projects (`surc project-check`)
+    rule("UnresolvedRequire", "error", "project", "A mod requires a module that does not exist"),
+    rule("RequireCycle", "error", "project", "Module requires form a cycle"),
+    rule(
+        "UndeclaredPackageDependency", "error", "project",
+        "A cross-package require is missing from `depends` (project-check --cross-package-only)",
+    ),
+    rule(
+        "InvalidNamespace", "error", "project",
+        "A namespace is not dot-separated segments of [a-z0-9_]",
+    ),
+    rule(
+        "DuplicateNamespace", "warning", "project",
+        "The same namespace is declared by more than one package",
+    ),
+    rule("EmptyPackage", "warning", "project", "A package root contains no modules"),
+    rule("SelfDependentPackage", "error", "project", "A package lists itself in depends"),
+    rule(
+        "ProjectUnusedSchema", "warning", "project",
+        "A schema is referenced by no file in the project",
+    ),
+    rule(
+        "ProjectUnusedFunc", "warning", "project",
+        "A func is referenced by no mod in the project",
+    ),
+    rule(
+        "E_PACKAGE_UNKNOWN", "error", "project",
+        "A file declares a package the manifest does not define",
+    ),
+    rule(
+        "E_PACKAGE_ROOT_MISMATCH", "error", "project",
+        "A file is outside the root of the package it declares",
+    ),
+    rule(
+        "E_PACKAGE_UNASSIGNED", "error", "project",
+        "A file is under no package root and has no package header",
+    ),
+    rule("E_PACKAGE_AMBIGUOUS", "error", "project", "A file matches several package roots"),
+    rule(
+        "E_IMPORT_UNKNOWN_PACKAGE", "error", "project",
+        "An import names a package the manifest does not define",
+    ),
+    rule("E_IMPORT_SYNTAX", "error", "project", "An import is not `package` or `package as alias`"),
+    rule("E_UNDEFINED_SCHEMA", "error", "project", "A schema reference resolves to no symbol"),
+    rule("E_UNDEFINED_FUNC", "error", "project", "A func reference resolves to no symbol"),
+    rule("E_UNDEFINED_MOD", "error", "project", "A mod reference resolves to no symbol"),
+    rule(
+        "E_UNDEFINED_PREFIX", "error", "project",
+        "A qualified reference uses an unknown package or alias",
+    ),
+    rule("W_AMBIGUOUS_NAME", "warning", "project", "A name matches symbols in several packages"),
+    rule("W_SHADOWED_IMPORT", "warning", "project", "A local symbol shadows an imported one"),
+    rule(
+        "UnresolvedReference", "warning", "project",
+        "A reference is left as-is when rewriting to fully-qualified names",
+    ),
+    // Merging (`surc merge`)
+    rule("MergeConflict", "error", "merge", "Two files define the same symbol differently"),
+    // Deploy IR (`surc deploy-check`)
+    rule(
+        "DuplicateJobAcrossIncludes", "error", "deploy",
+        "A job is defined in more than one included file",
+    ),
+    rule(
+        "JobNameCollisionAfterSanitization", "warning", "deploy",
+        "Job names collide once sanitized for CI output",
+    ),
+    rule("UndefinedJobReference", "error", "deploy", "`requires` names a job that is not defined"),
+    rule(
+        "UndefinedTargetReference", "error", "deploy",
+        "A job or rollback names a target that is not defined",
+    ),
+    rule("UndefinedSecretReference", "error", "deploy", "A job uses a secret that is not defined"),
+    rule(
+        "UndefinedPermReference", "error", "deploy",
+        "A job uses a permission that is not defined",
+    ),
+    rule(
+        "UndefinedArtifactReference", "warning", "deploy",
+        "A job produces an artifact that is not defined",
+    ),
+    rule(
+        "UndeclaredJobStage", "error", "deploy",
+        "A job's stage is not declared",
+    ),
+    rule("RequiresLaterStage", "error", "deploy", "A job requires a job in a later stage"),
+    rule("DeployCycle", "error", "deploy", "Job requirements form a cycle"),
+    rule("NoEntryPoint", "error", "deploy", "Every job has dependencies, so no job can start"),
+    rule("UnreachableJob", "warning", "deploy", "A job is not reachable from any entry point"),
+    rule("EmptyJobRuns", "warning", "deploy", "A job has no runs commands"),
+    rule("EmptyMatrixAxis", "error", "deploy", "A matrix axis has no values"),
+    rule(
+        "EnvironmentInstanceNameClash", "error", "deploy",
+        "A job generated from environments takes the name of another job",
+    ),
+    rule(
+        "InvalidJobCondition", "error", "deploy",
+        "A job's when is not always, manual, on_tag or on_branch(<name>)",
+    ),
+    rule(
+        "SecretScopeViolation", "error", "deploy",
+        "A job uses a secret outside the secret's scope",
+    ),
+    rule(
+        "UnscopedSecretInProduction", "warning", "deploy",
+        "A production job uses a secret with no scope",
+    ),
+    rule(
+        "PermScopeViolation", "error", "deploy",
+        "A job uses a permission on a target outside its scope",
+    ),
+    rule(
+        "MissingProdGate", "error", "deploy",
+        "Production jobs exist but no [deploy.gate] is defined",
+    ),
+    rule(
+        "ProdJobWithoutApproval", "error", "deploy",
+        "A production job's target is not in the gate approval list",
+    ),
+    rule(
+        "MissingHealthCheck", "error", "deploy",
+        "The release strategy requires a health_check that is missing",
+    ),
+    rule(
+        "MissingProdRollback", "error", "deploy",
+        "Production jobs exist but no [deploy.rollback] is defined",
+    ),
+    rule(
+        "ProdJobWithoutRollbackCoverage", "error", "deploy",
+        "A production job's target is not covered by any rollback",
+    ),
+    rule(
+        "ReleaseWithoutStrategy", "warning", "deploy",
+        "A job has release side effects but no [deploy.release]",
+    ),
+    rule(
+        "UnknownRollbackStrategy", "warning", "deploy",
+        "A rollback uses an unrecognized strategy",
+    ),
+    rule(
+        "RollbackReferencesUndefinedJob", "error", "deploy",
+        "A rollback names a job that is not defined",
+    ),
+    rule(
+        "EmptyNotifyChannels", "error", "deploy",
+        "[deploy.notify] lists no channels",
+    ),
+    rule(
+        "UnknownNotifyEvent", "warning", "deploy",
+        "[deploy.notify].on names an event other than success or failure",
+    ),
+    rule(
+        "DbMigrationWithoutTarget", "error", "deploy",
+        "A db_migration job has no uses_target",
+    ),
+    rule(
+        "DbMigrationWithoutApproval", "error", "deploy",
+        "A db_migration job's target is not in the approval list",
+    ),
+    rule(
+        "ConflictingTargetKindsForDomain", "error", "deploy",
+        "Targets of different kinds share a domain",
+    ),
+];
+
+/// Look up a diagnostic kind.
+pub fn find_rule(kind: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.kind == kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    const SOURCES: &[&str] = &[
+        include_str!("checker.rs"),
+        include_str!("parser.rs"),
+        include_str!("project_checker.rs"),
+        include_str!("package.rs"),
+        include_str!("imports.rs"),
+        include_str!("symbol.rs"),
+        include_str!("merge.rs"),
+        include_str!("deploy/checker.rs"),
+    ];
+
+    /// String literals passed as a diagnostic kind, plus `E_*`/`W_*` codes
+    /// built elsewhere. Test modules are skipped.
+    fn emitted_kinds() -> BTreeSet<&'static str> {
+        let mut kinds = BTreeSet::new();
+        for code in SOURCES.iter().flat_map(|source| non_test_code(source)) {
+            for (pos, _) in code.match_indices("kind: \"") {
+                insert_literal(&mut kinds, &code[pos + "kind: \"".len()..]);
+            }
+            // Diagnostic::new(severity, "Kind", ...)
+            for (pos, _) in code.match_indices("Diagnostic::new(") {
+                let args = &code[pos + "Diagnostic::new(".len()..];
+                if let Some(kind) = args.split_once(',').map(|(_, rest)| rest.trim_start()) {
+                    if let Some(literal) = kind.strip_prefix('"') {
+                        insert_literal(&mut kinds, literal);
+                    }
+                }
+            }
+            for prefix in ["\"E_", "\"W_"] {
+                for (pos, _) in code.match_indices(prefix) {
+                    insert_literal(&mut kinds, &code[pos + 1..]);
+                }
+            }
+        }
+        kinds
+    }
+
+    /// The source split around `#[cfg(test)] mod tests { ... }` blocks, which
+    /// end at the next unindented `}`.
+    fn non_test_code(source: &'static str) -> Vec<&'static str> {
+        let mut parts = source.split("#[cfg(test)]");
+        let mut code: Vec<&str> = parts.next().into_iter().collect();
+        code.extend(parts.filter_map(|part| part.split_once("\n}\n").map(|(_, rest)| rest)));
+        code
+    }
+
+    fn insert_literal(kinds: &mut BTreeSet<&'static str>, rest: &'static str) {
+        if let Some(end) = rest.find('"') {
+            kinds.insert(&rest[..end]);
+        }
+    }
+
+    #[test]
+    fn registry_covers_every_emitted_kind() {
+        let kinds = emitted_kinds();
+        assert!(kinds.contains("PipelineCycle"));
+        assert!(kinds.contains("E_UNDEFINED_MOD"));
+        for kind in &kinds {
+            assert!(find_rule(kind).is_some(), "{} is missing from RULES", kind);
+        }
+        for rule in RULES {
+            assert!(kinds.contains(rule.kind), "{} is never emitted", rule.kind);
+            assert!(["error", "warning", "info"].contains(&rule.severity));
+        }
+        let unique: BTreeSet<&str> = RULES.iter().map(|rule| rule.kind).collect();
+        assert_eq!(unique.len(), RULES.len());
+    }
+}
@@ -0,0 +1,69 @@
+use survibe_parser_rs::rules::RULES;
+use survibe_parser_rs::text_table::TextTable;
+use std::error::Error;
+
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub fn run_rules(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut format = OutputFormat::Text;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_rules_usage();
+                return Ok(());
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    format = match args[i + 1].as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        other => return Err(format!("Unknown format: {}", other).into()),
+                    };
+                    i += 2;
+                } else {
+                    return Err("--format requires a value (text, json)".into());
+                }
+            }
+            other => {
+                return Err(format!("Unknown option: {}", other).into());
+            }
+        }
+    }
+
+    match format {
+        OutputFormat::Text => {
+            let mut table = TextTable::new(&["KIND", "SEVERITY", "CHECKER", "DESCRIPTION"]);
+            for rule in RULES {
+                table.add_row(vec![
+                    rule.kind.to_string(),
+                    rule.severity.to_string(),
+                    rule.checker.to_string(),
+                    rule.description.to_string(),
+                ]);
+            }
+            print!("{}", table.render());
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(RULES)?),
+    }
+
+    Ok(())
+}
+
+fn print_rules_usage() {
+    eprintln!("Usage: surc rules [--format text|json]");
+    eprintln!();
+    eprintln!("List every diagnostic kind the checkers can report, with its default");
+    eprintln!("severity, the checker that emits it, and a one-line description.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --format <format>    Output format (text, json) [default: text]");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc rules");
+    eprintln!("  surc rules --format json");
+}
@@ -0,0 +1,59 @@
+/// A vetted example file that `surc init` can write.
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub name: &'static str,
+    /// File name written when no output path is given
+    pub file_name: &'static str,
+    pub description: &'static str,
+    pub contents: &'static str,
+}
+
+/// Every sample, the default scaffold first.
+pub const SAMPLES: &[Sample] = &[
+    Sample {
+        name: "minimal",
+        file_name: "main.toml",
+        description: "One schema pair, func and mod to start from",
+        contents: include_str!("../examples/samples/minimal.toml"),
+    },
+    Sample {
+        name: "crud",
+        file_name: "user_crud.toml",
+        description: "User CRUD HTTP API using schemas, boundaries, funcs and pipeline chains",
+        contents: include_str!("../examples/samples/user_crud.toml"),
+    },
+    Sample {
+        name: "deploy",
+        file_name: "deploy.toml",
+        description: "Staged web app deploy IR with targets, secrets, gates and rollback",
+        contents: include_str!("../examples/samples/deploy.toml"),
+    },
+];
+
+pub fn find_sample(name: &str) -> Option<&'static Sample> {
+    SAMPLES.iter().find(|sample| sample.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::check_surv_file;
+    use crate::deploy::{check_deploy_file, parse_deploy_str};
+    use crate::parser::parse_surv_str;
+
+    #[test]
+    fn surv_samples_pass_check() {
+        for name in ["minimal", "crud"] {
+            let file = parse_surv_str(find_sample(name).unwrap().contents).expect(name);
+            let diags = check_surv_file(&file);
+            assert!(diags.is_empty(), "{}: {:?}", name, diags);
+        }
+    }
+
+    #[test]
+    fn deploy_sample_passes_deploy_check() {
+        let deploy = parse_deploy_str(find_sample("deploy").unwrap().contents).expect("parse");
+        let diags = check_deploy_file(&deploy);
+        assert!(diags.is_empty(), "{:?}", diags);
+    }
+}
@@ -0,0 +1,244 @@
+use serde_json::{json, Value};
+
+/// Schema kinds understood by the checker and exporters.
+pub const SCHEMA_KINDS: &[&str] = &["node", "edge", "boundary", "space"];
+
+/// Implementation states accepted by `[status.mod.*]` entries.
+pub const STATUS_STATES: &[&str] = &["todo", "skeleton", "partial", "done", "blocked"];
+
+/// Languages accepted by `impl.lang`.
+pub const IMPL_LANGS: &[&str] = &["ts", "rust", "either"];
+
+/// Build a JSON Schema (draft 2020-12) document describing the structure of a
+/// Surv IR file as it appears after a TOML-to-JSON conversion.
+///
+/// Dotted keys such as `impl.bind` are described in their nested form
+/// (`impl = { bind = ... }`), which is what standard TOML converters produce.
+pub fn surv_ir_json_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "https://github.com/otaku46/Surv-IR/schema/surv-ir-v1.1.json",
+        "title": "Surv IR file",
+        "description": "Structure of a Surv IR v1.1 design file (schemas, funcs, mods, status).",
+        "type": "object",
+        "properties": {
+            "package": {
+                "type": "string",
+                "description": "Package this file belongs to (must match a package in surv.toml)"
+            },
+            "namespace": {
+                "type": "string",
+                "description": "Namespace used to qualify symbols defined in this file"
+            },
+            "import": {
+                "type": "array",
+                "description": "Imported packages, optionally aliased (\"users as u\")",
+                "items": { "type": "string" }
+            },
+            "require": { "$ref": "#/$defs/requireList" },
+            "requires": { "$ref": "#/$defs/requireList" },
+            "meta": { "$ref": "#/$defs/meta" },
+            "schema": {
+                "type": "object",
+                "description": "Schema definitions keyed by name ([schema.<name>])",
+                "additionalProperties": { "$ref": "#/$defs/schema" }
+            },
+            "func": {
+                "type": "object",
+                "description": "Function definitions keyed by name ([func.<name>])",
+                "additionalProperties": { "$ref": "#/$defs/func" }
+            },
+            "mod": {
+                "type": "object",
+                "description": "Module definitions keyed by name ([mod.<name>])",
+                "additionalProperties": { "$ref": "#/$defs/mod" }
+            },
+            "status": { "$ref": "#/$defs/status" }
+        },
+        "$defs": {
+            "requireList": {
+                "type": "array",
+                "description": "Modules required by the modules in this file",
+                "items": { "type": "string", "pattern": "^mod\\." }
+            },
+            "ref": {
+                "type": "string",
+                "description": "Reference to another symbol, e.g. schema.user or func.create_user"
+            },
+            "refSet": {
+                "description": "Array of references, or an inline brace set string",
+                "oneOf": [
+                    { "type": "array", "items": { "$ref": "#/$defs/ref" } },
+                    { "type": "string" }
+                ]
+            },
+            "impl": {
+                "type": "object",
+                "description": "Implementation binding metadata used by diff-impl",
+                "properties": {
+                    "bind": { "type": "string" },
+                    "lang": { "type": "string", "enum": IMPL_LANGS },
+                    "path": { "type": "string" }
+                }
+            },
+            "meta": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "version": { "type": "string" },
+                    "description": { "type": "string" }
+                }
+            },
+            "schema": {
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string", "enum": SCHEMA_KINDS },
+                    "role": { "type": "string" },
+                    "type": { "type": "string" },
+                    "from": { "$ref": "#/$defs/ref" },
+                    "to": { "$ref": "#/$defs/ref" },
+                    "base": { "$ref": "#/$defs/ref" },
+                    "label": { "type": "string" },
+                    "fields": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    },
+                    "field": {
+                        "type": "object",
+                        "description": "Structured fields ([schema.<name>.field.<field>])",
+                        "additionalProperties": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "type": "string" },
+                                "unique": { "type": "boolean" },
+                                "indexed": { "type": "boolean" }
+                            }
+                        }
+                    },
+                    "over": { "$ref": "#/$defs/refSet" },
+                    "deprecated": { "type": "boolean" },
+                    "deprecated_note": { "type": "string" },
+                    "impl": { "$ref": "#/$defs/impl" }
+                }
+            },
+            "func": {
+                "type": "object",
+                "properties": {
+                    "intent": { "type": "string" },
+                    "input": { "$ref": "#/$defs/refSet" },
+                    "output": { "$ref": "#/$defs/refSet" },
+                    "design_notes": { "type": "string" },
+                    "impl": { "$ref": "#/$defs/impl" }
+                }
+            },
+            "mod": {
+                "type": "object",
+                "properties": {
+                    "purpose": { "type": "string" },
+                    "kind": {
+                        "description": "Optional hint; \"http\" marks the module for export openapi",
+                        "type": "string"
+                    },
+                    "schemas": { "$ref": "#/$defs/refSet" },
+                    "funcs": { "$ref": "#/$defs/refSet" },
+                    "pipeline": {
+                        "description": "Pipeline chain(s): \"func.a -> func.b\", an array of chains, or a table keyed by chain",
+                        "oneOf": [
+                            { "type": "string" },
+                            { "type": "array", "items": { "type": "string" } },
+                            { "type": "object" }
+                        ]
+                    },
+                    "owners": { "type": "array", "items": { "type": "string" } },
+                    "label": { "type": "string" },
+                    "boundary": {
+                        "type": "object",
+                        "description": "External endpoints by channel, e.g. { http = [\"GET /todos\"] }",
+                        "additionalProperties": { "type": "array", "items": { "type": "string" } }
+                    }
+                }
+            },
+            "status": {
+                "type": "object",
+                "properties": {
+                    "updated_at": { "type": "string" },
+                    "mod": {
+                        "type": "object",
+                        "description": "Per-module implementation status ([status.mod.<name>])",
+                        "additionalProperties": {
+                            "type": "object",
+                            "properties": {
+                                "state": { "type": "string", "enum": STATUS_STATES },
+                                "coverage": { "type": "number", "minimum": 0.0, "maximum": 1.0 },
+                                "notes": { "type": "string" },
+                                "updated_at": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_top_level_tables_and_enums() {
+        let schema = surv_ir_json_schema();
+
+        for key in ["meta", "schema", "func", "mod", "status"] {
+            assert!(schema["properties"].get(key).is_some(), "missing {key}");
+        }
+
+        let kinds = &schema["$defs"]["schema"]["properties"]["kind"]["enum"];
+        assert_eq!(kinds.as_array().map(Vec::len), Some(SCHEMA_KINDS.len()));
+
+        let state = &schema["$defs"]["status"]["properties"]["mod"]["additionalProperties"]
+            ["properties"]["state"]["enum"];
+        assert!(state.as_array().unwrap().iter().any(|s| s == "done"));
+    }
+
+    /// Every key the parser accepts is described, and nothing else
+    #[test]
+    fn matches_the_keys_the_parser_accepts() {
+        use crate::parser::{
+            FIELD_KEYS, FUNC_KEYS, META_KEYS, MODULE_STATUS_KEYS, MOD_KEYS, SCHEMA_KEYS,
+            STATUS_KEYS, TOP_LEVEL_KEYS,
+        };
+        fn keys(properties: &Value) -> Vec<String> {
+            let mut keys: Vec<String> = properties.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        }
+        fn expected(accepted: &[&str]) -> Vec<String> {
+            // `impl.bind` and friends are described as one nested `impl` table
+            let mut keys: Vec<String> = accepted
+                .iter()
+                .map(|key| key.split('.').next().unwrap().to_string())
+                .collect();
+            keys.sort();
+            keys.dedup();
+            keys
+        }
+
+        let schema = surv_ir_json_schema();
+        let defs = &schema["$defs"];
+        let module_status = &defs["status"]["properties"]["mod"]["additionalProperties"];
+        let field = &defs["schema"]["properties"]["field"]["additionalProperties"];
+        for (properties, accepted) in [
+            (&schema["properties"], TOP_LEVEL_KEYS),
+            (&defs["meta"]["properties"], META_KEYS),
+            (&defs["schema"]["properties"], SCHEMA_KEYS),
+            (&field["properties"], FIELD_KEYS),
+            (&defs["func"]["properties"], FUNC_KEYS),
+            (&defs["mod"]["properties"], MOD_KEYS),
+            (&defs["status"]["properties"], STATUS_KEYS),
+            (&module_status["properties"], MODULE_STATUS_KEYS),
+        ] {
+            assert_eq!(keys(properties), expected(accepted));
+        }
+    }
+}
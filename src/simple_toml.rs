@@ -8,7 +8,6 @@ pub enum TomlValue {
     String(String),
     Array(Vec<TomlValue>),
     Table(TomlTable),
-    #[allow(dead_code)]
     Bool(bool),
 }
 
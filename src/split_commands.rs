@@ -6,7 +6,10 @@ use std::error::Error;
 use std::fs;
 use std::path::PathBuf;
 
-use survibe_parser_rs::{parse_surv_file, SurvFile, Section, SchemaSection, FuncSection, ModSection};
+use survibe_parser_rs::{parse_surv_str, SurvFile, Section, SchemaSection, FuncSection, ModSection};
+use survibe_parser_rs::writer::pipeline_entries;
+
+use crate::markers;
 
 #[derive(Debug)]
 pub struct SplitConfig {
@@ -49,6 +52,14 @@ pub struct SplitContext {
 pub struct DependencyClosure {
     pub schemas: HashSet<String>,
     pub funcs: HashSet<String>,
+    /// Edge from/to chains that lead back to a schema already being expanded
+    pub cycles: Vec<Vec<String>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    InProgress,
+    Done,
 }
 
 pub fn run_split(args: &[String]) -> Result<(), Box<dyn Error>> {
@@ -64,7 +75,7 @@ pub fn run_split(args: &[String]) -> Result<(), Box<dyn Error>> {
 
     // 1. Parse input IR
     let input_content = fs::read_to_string(input_path)?;
-    let input_ast = parse_surv_file(input_content.as_bytes())?;
+    let input_ast = parse_surv_str(&input_content)?;
 
     // 2. Parse split config
     let config = parse_split_config(&config_path)?;
@@ -101,7 +112,7 @@ pub fn run_split(args: &[String]) -> Result<(), Box<dyn Error>> {
         println!("  (project-check not yet integrated)");
     }
 
-    println!("\n✓ Split completed successfully");
+    println!("\n{} Split completed successfully", markers::ok());
     Ok(())
 }
 
@@ -272,6 +283,15 @@ fn execute_split(ctx: &mut SplitContext) -> Result<(), Box<dyn Error>> {
             // Compute dependency closure for this module
             let closure = compute_closure(&ctx.input_ast, &mod_assignment.mod_name)?;
 
+            for cycle in &closure.cycles {
+                let path: Vec<String> = cycle.iter().map(|s| format!("schema.{}", s)).collect();
+                ctx.warnings.push(format!(
+                    "W_CLOSURE_CYCLE: edge references form a cycle while expanding {}: {}",
+                    mod_assignment.mod_name,
+                    path.join(" -> ")
+                ));
+            }
+
             // Track symbol usage
             for schema in &closure.schemas {
                 symbol_usage.entry(schema.clone())
@@ -294,7 +314,12 @@ fn execute_split(ctx: &mut SplitContext) -> Result<(), Box<dyn Error>> {
 
             // Write file
             fs::write(&output_path, content)?;
-            println!("  ✓ Created {:?} ({})", output_path, mod_assignment.mod_name);
+            println!(
+                "  {} Created {:?} ({})",
+                markers::ok(),
+                output_path,
+                mod_assignment.mod_name
+            );
         }
     }
 
@@ -331,6 +356,7 @@ fn compute_closure(ast: &SurvFile, mod_name: &str) -> Result<DependencyClosure,
     let mut closure = DependencyClosure {
         schemas: HashSet::new(),
         funcs: HashSet::new(),
+        cycles: Vec::new(),
     };
 
     // Collect direct references from module
@@ -358,16 +384,13 @@ fn compute_closure(ast: &SurvFile, mod_name: &str) -> Result<DependencyClosure,
         expand_func_closure(ast, func_name, &mut closure);
     }
 
-    // Expand closure: schemas -> schemas (recursive references)
-    let mut changed = true;
-    while changed {
-        changed = false;
-        let current_schemas: Vec<_> = closure.schemas.iter().cloned().collect();
-        for schema_name in &current_schemas {
-            if expand_schema_closure(ast, schema_name, &mut closure) {
-                changed = true;
-            }
-        }
+    // Expand closure: schemas -> schemas (edge from/to), tracking visits so
+    // self-referential edges are reported instead of expanded again
+    let mut seeds: Vec<String> = closure.schemas.iter().cloned().collect();
+    seeds.sort();
+    let mut visits = HashMap::new();
+    for seed in &seeds {
+        expand_schema_closure(ast, seed, &mut closure, &mut visits);
     }
 
     Ok(closure)
@@ -397,36 +420,65 @@ fn expand_func_closure(ast: &SurvFile, func_name: &str, closure: &mut Dependency
     }
 }
 
-fn expand_schema_closure(ast: &SurvFile, schema_name: &str, closure: &mut DependencyClosure) -> bool {
-    let mut added = false;
-
-    for sec in &ast.sections {
-        if let Section::Schema(schema_sec) = sec {
-            if schema_sec.name == schema_name {
-                // Handle edge schemas (from/to)
-                if schema_sec.kind == "edge" {
-                    if !schema_sec.from.is_empty() {
-                        if let Some(from_name) = schema_sec.from.strip_prefix("schema.") {
-                            if closure.schemas.insert(from_name.to_string()) {
-                                added = true;
-                            }
-                        }
-                    }
-
-                    if !schema_sec.to.is_empty() {
-                        if let Some(to_name) = schema_sec.to.strip_prefix("schema.") {
-                            if closure.schemas.insert(to_name.to_string()) {
-                                added = true;
-                            }
-                        }
-                    }
-                }
-                break;
+/// Depth-first walk over edge `from`/`to` references starting at `root`. An
+/// explicit stack keeps deep chains off the call stack; reaching a schema
+/// that is still on the stack records the cycle.
+fn expand_schema_closure(
+    ast: &SurvFile,
+    root: &str,
+    closure: &mut DependencyClosure,
+    visits: &mut HashMap<String, Visit>,
+) {
+    if visits.contains_key(root) {
+        return;
+    }
+    visits.insert(root.to_string(), Visit::InProgress);
+    let mut stack = vec![(root.to_string(), edge_links(ast, root), 0)];
+
+    while let Some((_, links, next)) = stack.last_mut() {
+        let Some(link) = links.get(*next).cloned() else {
+            let (name, _, _) = stack.pop().unwrap();
+            visits.insert(name, Visit::Done);
+            continue;
+        };
+        *next += 1;
+
+        match visits.get(&link) {
+            Some(Visit::Done) => {}
+            Some(Visit::InProgress) => {
+                let start = stack.iter().position(|(name, _, _)| *name == link).unwrap_or(0);
+                let mut cycle: Vec<String> =
+                    stack[start..].iter().map(|(name, _, _)| name.clone()).collect();
+                cycle.push(link);
+                closure.cycles.push(cycle);
+            }
+            None => {
+                closure.schemas.insert(link.clone());
+                visits.insert(link.clone(), Visit::InProgress);
+                let links = edge_links(ast, &link);
+                stack.push((link, links, 0));
             }
         }
     }
+}
 
-    added
+/// Schemas an edge schema points at through `from` and `to`.
+fn edge_links(ast: &SurvFile, schema_name: &str) -> Vec<String> {
+    ast.sections
+        .iter()
+        .find_map(|sec| match sec {
+            Section::Schema(schema_sec) if schema_sec.name == schema_name => Some(schema_sec),
+            _ => None,
+        })
+        .filter(|schema_sec| schema_sec.kind == "edge")
+        .map(|schema_sec| {
+            [&schema_sec.from, &schema_sec.to]
+                .into_iter()
+                .filter_map(|reference| reference.strip_prefix("schema."))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 fn generate_file_content(
@@ -521,8 +573,9 @@ fn generate_file_content(
         if !mod_sec.funcs.is_empty() {
             output.push_str(&format!("funcs = {:?}\n", mod_sec.funcs));
         }
-        if !mod_sec.pipeline.is_empty() {
-            output.push_str(&format!("pipeline = {:?}\n", mod_sec.pipeline));
+        let pipeline = pipeline_entries(mod_sec);
+        if !pipeline.is_empty() {
+            output.push_str(&format!("pipeline = {:?}\n", pipeline));
         }
         output.push('\n');
     }
@@ -560,7 +613,7 @@ fn generate_manifest(ctx: &SplitContext) -> Result<(), Box<dyn Error>> {
     }
 
     fs::write(&manifest_path, output)?;
-    println!("  ✓ Created {:?}", manifest_path);
+    println!("  {} Created {:?}", markers::ok(), manifest_path);
 
     Ok(())
 }
@@ -0,0 +1,228 @@
+use crate::ast::{SchemaSection, SurvFile};
+use crate::checker::FileIndex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// How a schema is used across the funcs and mods of one file.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaUsage {
+    pub schema: String,
+    /// Funcs taking the schema as input (fan-in)
+    pub consumers: Vec<String>,
+    /// Funcs producing the schema as output (fan-out)
+    pub producers: Vec<String>,
+    /// Mods listing the schema in `schemas`
+    pub modules: Vec<String>,
+}
+
+impl SchemaUsage {
+    pub fn fan_in(&self) -> usize {
+        self.consumers.len()
+    }
+
+    pub fn fan_out(&self) -> usize {
+        self.producers.len()
+    }
+}
+
+/// Schema usage for every schema defined in `file`, busiest (highest fan-in) first.
+pub fn schema_usage(file: &SurvFile) -> Vec<SchemaUsage> {
+    let index = FileIndex::new(file);
+
+    let mut usage: Vec<SchemaUsage> = index
+        .schemas
+        .keys()
+        .map(|schema| SchemaUsage {
+            schema: schema.clone(),
+            consumers: index
+                .funcs
+                .iter()
+                .filter(|(_, func)| func.input.contains(schema))
+                .map(|(id, _)| id.clone())
+                .collect(),
+            producers: index
+                .funcs
+                .iter()
+                .filter(|(_, func)| func.output.contains(schema))
+                .map(|(id, _)| id.clone())
+                .collect(),
+            modules: index
+                .mods
+                .iter()
+                .filter(|(_, module)| module.schemas.contains(schema))
+                .map(|(id, _)| id.clone())
+                .collect(),
+        })
+        .collect();
+
+    usage.sort_by(|a, b| {
+        b.fan_in()
+            .cmp(&a.fan_in())
+            .then(b.fan_out().cmp(&a.fan_out()))
+            .then_with(|| a.schema.cmp(&b.schema))
+    });
+    usage
+}
+
+/// The longest chain of schema references (`from`, `to`, `base`, `over`)
+/// starting at one schema.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaDepth {
+    pub schema: String,
+    /// References followed along `path`; 0 for a schema referencing nothing
+    pub depth: usize,
+    /// `schema` followed by the schemas it transitively depends on
+    pub path: Vec<String>,
+}
+
+/// Dependency depth of every schema defined in `file`, deepest first.
+///
+/// References to undefined schemas are skipped, and so is any reference
+/// that closes a cycle, so the depth stays finite.
+pub fn schema_depths(file: &SurvFile) -> Vec<SchemaDepth> {
+    let index = FileIndex::new(file);
+    let mut longest: BTreeMap<&String, Vec<String>> = BTreeMap::new();
+    for id in index.schemas.keys() {
+        let mut stack = Vec::new();
+        longest_path(id, &index.schemas, &mut stack, &mut longest);
+    }
+
+    let mut depths: Vec<SchemaDepth> = longest
+        .into_iter()
+        .map(|(id, path)| SchemaDepth {
+            schema: id.clone(),
+            depth: path.len() - 1,
+            path,
+        })
+        .collect();
+    depths.sort_by(|a, b| b.depth.cmp(&a.depth).then_with(|| a.schema.cmp(&b.schema)));
+    depths
+}
+
+fn schema_references(schema: &SchemaSection) -> impl Iterator<Item = &String> {
+    [&schema.from, &schema.to, &schema.base]
+        .into_iter()
+        .chain(&schema.over)
+        .filter(|reference| !reference.is_empty())
+}
+
+fn longest_path<'a>(
+    id: &'a String,
+    schemas: &'a BTreeMap<String, &'a SchemaSection>,
+    stack: &mut Vec<&'a String>,
+    longest: &mut BTreeMap<&'a String, Vec<String>>,
+) -> Vec<String> {
+    if let Some(path) = longest.get(id) {
+        return path.clone();
+    }
+    stack.push(id);
+    let mut deepest: Vec<String> = Vec::new();
+    for reference in schema_references(schemas[id]) {
+        let Some((reference, _)) = schemas.get_key_value(reference) else {
+            continue;
+        };
+        if stack.contains(&reference) {
+            continue;
+        }
+        let path = longest_path(reference, schemas, stack, longest);
+        if path.len() > deepest.len() {
+            deepest = path;
+        }
+    }
+    stack.pop();
+
+    let mut path = vec![id.clone()];
+    path.extend(deepest);
+    longest.insert(id, path.clone());
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_surv_str;
+
+    #[test]
+    fn counts_fan_in_and_fan_out_per_schema() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.order]
+kind = "node"
+
+[schema.report]
+kind = "node"
+
+[func.create_order]
+intent = "test"
+input = ["schema.user"]
+output = ["schema.order"]
+
+[func.bill]
+intent = "test"
+input = ["schema.user", "schema.order"]
+output = ["schema.order"]
+
+[mod.shop]
+purpose = "test"
+schemas = ["schema.user", "schema.order"]
+funcs = ["func.create_order", "func.bill"]
+"#;
+        let file = parse_surv_str(ir).unwrap();
+        let usage = schema_usage(&file);
+
+        assert_eq!(usage[0].schema, "schema.user");
+        assert_eq!(usage[0].fan_in(), 2);
+        assert_eq!(usage[0].fan_out(), 0);
+        assert_eq!(usage[1].schema, "schema.order");
+        assert_eq!(usage[1].producers, vec!["func.bill", "func.create_order"]);
+        assert_eq!(usage[1].modules, vec!["mod.shop"]);
+        assert_eq!(usage[2].schema, "schema.report");
+        assert_eq!(usage[2].fan_in(), 0);
+    }
+
+    #[test]
+    fn finds_the_deepest_schema_reference_chain() {
+        let ir = r#"
+[schema.user]
+kind = "node"
+
+[schema.admin]
+kind = "node"
+base = "schema.user"
+
+[schema.group]
+kind = "node"
+
+[schema.membership]
+kind = "edge"
+from = "schema.admin"
+to = "schema.group"
+
+[schema.members]
+kind = "boundary"
+over = ["schema.membership", "schema.unknown"]
+
+[schema.loop_a]
+kind = "node"
+base = "schema.loop_b"
+
+[schema.loop_b]
+kind = "node"
+base = "schema.loop_a"
+"#;
+        let file = parse_surv_str(ir).unwrap();
+        let depths = schema_depths(&file);
+
+        assert_eq!(depths[0].schema, "schema.members");
+        assert_eq!(depths[0].depth, 3);
+        assert_eq!(
+            depths[0].path,
+            vec!["schema.members", "schema.membership", "schema.admin", "schema.user"]
+        );
+        let depth = |id: &str| depths.iter().find(|d| d.schema == id).unwrap().depth;
+        assert_eq!(depth("schema.user"), 0);
+        assert_eq!(depth("schema.loop_a"), 1);
+    }
+}
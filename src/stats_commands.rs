@@ -0,0 +1,138 @@
+use survibe_parser_rs::parse_surv_file;
+use survibe_parser_rs::stats::{schema_depths, schema_usage, SchemaDepth, SchemaUsage};
+use survibe_parser_rs::text_table::TextTable;
+use std::error::Error;
+use std::fs::File;
+
+enum Report {
+    SchemaUsage,
+    SchemaDepth,
+}
+
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub fn run_stats(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        print_stats_usage();
+        if args.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let filename = &args[0];
+    let mut report = Report::SchemaUsage;
+    let mut format = OutputFormat::Text;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--schema-usage" => {
+                report = Report::SchemaUsage;
+                i += 1;
+            }
+            "--schema-depth" => {
+                report = Report::SchemaDepth;
+                i += 1;
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    format = match args[i + 1].as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        other => return Err(format!("Unknown format: {}", other).into()),
+                    };
+                    i += 2;
+                } else {
+                    return Err("--format requires a value (text, json)".into());
+                }
+            }
+            other => {
+                return Err(format!("Unknown option: {}", other).into());
+            }
+        }
+    }
+
+    let file = parse_surv_file(File::open(filename)?)?;
+
+    match report {
+        Report::SchemaUsage => {
+            let usage = schema_usage(&file);
+            match format {
+                OutputFormat::Text => print_schema_usage(filename, &usage),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&usage)?),
+            }
+        }
+        Report::SchemaDepth => {
+            let depths = schema_depths(&file);
+            match format {
+                OutputFormat::Text => print_schema_depths(filename, &depths),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&depths)?),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_schema_usage(filename: &str, usage: &[SchemaUsage]) {
+    if usage.is_empty() {
+        println!("No schemas found in {}", filename);
+        return;
+    }
+
+    println!("Schema usage in {}:", filename);
+    println!();
+
+    let mut table = TextTable::new(&["SCHEMA", "FAN-IN", "FAN-OUT", "MODULES"])
+        .align_right(1)
+        .align_right(2)
+        .indent(2);
+    for entry in usage {
+        table.add_row(vec![
+            entry.schema.clone(),
+            entry.fan_in().to_string(),
+            entry.fan_out().to_string(),
+            entry.modules.join(", "),
+        ]);
+    }
+    print!("{}", table.render());
+}
+
+fn print_schema_depths(filename: &str, depths: &[SchemaDepth]) {
+    let Some(deepest) = depths.first() else {
+        println!("No schemas found in {}", filename);
+        return;
+    };
+
+    println!("Schema dependency depth in {}: {}", filename, deepest.depth);
+    println!("  {}", deepest.path.join(" -> "));
+    println!();
+
+    let mut table = TextTable::new(&["SCHEMA", "DEPTH"]).align_right(1).indent(2);
+    for entry in depths {
+        table.add_row(vec![entry.schema.clone(), entry.depth.to_string()]);
+    }
+    print!("{}", table.render());
+}
+
+fn print_stats_usage() {
+    eprintln!("Usage: surc stats <file> [options]");
+    eprintln!();
+    eprintln!("Report usage statistics for a Surv IR file.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --schema-usage       Funcs consuming (fan-in) and producing (fan-out) each schema,");
+    eprintln!("                       and the modules referencing it [default]");
+    eprintln!("  --schema-depth       Longest chain of schema references (from, to, base, over),");
+    eprintln!("                       with the deepest path");
+    eprintln!("  --format <format>    Output format (text, json) [default: text]");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc stats api.toml --schema-usage");
+    eprintln!("  surc stats api.toml --schema-usage --format json");
+    eprintln!("  surc stats api.toml --schema-depth");
+}
@@ -1,9 +1,15 @@
-use survibe_parser_rs::ast::Section;
+use survibe_parser_rs::ast::{ModSection, ModuleStatus, Section};
 use survibe_parser_rs::parser::parse_surv_file;
+use survibe_parser_rs::text_table::{csv_row, TextTable};
 use std::error::Error;
 use std::fs::{self, File};
 use std::io::Write as IoWrite;
 
+use crate::markers;
+
+/// Notes longer than this many terminal columns are truncated in `status list`.
+const NOTES_MAX_WIDTH: usize = 48;
+
 pub fn run_status(args: &[String]) -> Result<(), Box<dyn Error>> {
     if args.is_empty() {
         print_status_usage();
@@ -20,10 +26,11 @@ pub fn run_status(args: &[String]) -> Result<(), Box<dyn Error>> {
         }
         "sync" => {
             if args.len() < 2 {
-                eprintln!("Usage: surc status sync <file.toml>");
+                eprintln!("Usage: surc status sync <file.toml> [--prune]");
                 std::process::exit(1);
             }
-            run_status_sync(&args[1])
+            let prune = args[2..].iter().any(|a| a == "--prune");
+            run_status_sync(&args[1], prune)
         }
         "set" => {
             if args.len() < 3 {
@@ -34,10 +41,10 @@ pub fn run_status(args: &[String]) -> Result<(), Box<dyn Error>> {
         }
         "list" => {
             if args.len() < 2 {
-                eprintln!("Usage: surc status list <file.toml>");
+                eprintln!("Usage: surc status list <file.toml> [--sort <name|state|coverage>] [--desc] [--format text|csv] [--since <date>]");
                 std::process::exit(1);
             }
-            run_status_list(&args[1])
+            run_status_list(&args[1], &args[2..])
         }
         "show" => {
             if args.len() < 3 {
@@ -65,19 +72,25 @@ fn print_status_usage() {
     eprintln!("      Initialize [status] section if not present");
     eprintln!("      Lists all modules with state = 'todo'");
     eprintln!();
-    eprintln!("  sync <file.toml>");
+    eprintln!("  sync <file.toml> [--prune]");
     eprintln!("      Sync status section with current modules");
     eprintln!("      Adds missing modules with state = 'todo'");
+    eprintln!("      --prune also removes entries whose module no longer exists");
     eprintln!();
     eprintln!("  set <module> <file.toml> [options]");
-    eprintln!("      Update status for a specific module");
+    eprintln!("      Update status for a specific module and stamp its updated_at");
     eprintln!("      Options:");
     eprintln!("        --state <state>      Set state (todo, skeleton, partial, done, blocked)");
     eprintln!("        --coverage <0.0-1.0> Set coverage (0.0 to 1.0)");
     eprintln!("        --notes <text>       Set notes");
     eprintln!();
-    eprintln!("  list <file.toml>");
+    eprintln!("  list <file.toml> [options]");
     eprintln!("      List all modules with their status");
+    eprintln!("      Options:");
+    eprintln!("        --sort <field>       Sort by name, state, or coverage");
+    eprintln!("        --desc               Sort in descending order");
+    eprintln!("        --format <format>    Output format (text, csv) [default: text]");
+    eprintln!("        --since <YYYY-MM-DD> Only modules whose status was updated on or after the date");
     eprintln!();
     eprintln!("  show <module> <file.toml>");
     eprintln!("      Show detailed status for a specific module");
@@ -85,9 +98,13 @@ fn print_status_usage() {
     eprintln!("Examples:");
     eprintln!("  surc status init examples/todo_api.toml");
     eprintln!("  surc status sync examples/todo_api.toml");
+    eprintln!("  surc status sync examples/todo_api.toml --prune");
     eprintln!("  surc status set mod.book_api api.toml --state partial");
     eprintln!("  surc status set mod.book_api api.toml --coverage 0.6 --notes \"create/get done\"");
     eprintln!("  surc status list examples/todo_api.toml");
+    eprintln!("  surc status list examples/todo_api.toml --sort coverage");
+    eprintln!("  surc status list examples/todo_api.toml --format csv > status.csv");
+    eprintln!("  surc status list examples/todo_api.toml --since 2024-01-01");
     eprintln!("  surc status show mod.todo_api examples/todo_api.toml");
 }
 
@@ -145,7 +162,7 @@ fn run_status_init(filename: &str) -> Result<(), Box<dyn Error>> {
 
     output.write_all(status_text.as_bytes())?;
 
-    println!("✓ Initialized status section in {}", filename);
+    println!("{} Initialized status section in {}", markers::ok(), filename);
     println!("  Modules added: {}", modules.len());
     for module in modules {
         println!("    - mod.{} (todo)", module);
@@ -257,8 +274,10 @@ fn run_status_set(
             .to_string();
     }
 
-    // Update timestamp
+    // Stamp the module, then the file-level timestamp
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    new_content = stamp_module_updated_at(&new_content, module_name, &today)?;
+
     let timestamp_pattern = r#"(\[status\][^\[]*updated_at = )"[^"]*""#;
     let re = regex::Regex::new(timestamp_pattern)?;
     new_content = re
@@ -267,12 +286,100 @@ fn run_status_set(
 
     fs::write(filename, new_content)?;
 
-    println!("✓ Updated status for mod.{}", module_name);
+    println!("{} Updated status for mod.{}", markers::ok(), module_name);
 
     Ok(())
 }
 
-fn run_status_list(filename: &str) -> Result<(), Box<dyn Error>> {
+/// Set `updated_at` in `[status.mod.<name>]`, adding it after the header when
+/// the module has never been stamped.
+fn stamp_module_updated_at(
+    content: &str,
+    module_name: &str,
+    date: &str,
+) -> Result<String, Box<dyn Error>> {
+    let name = regex::escape(module_name);
+    let existing = regex::Regex::new(&format!(
+        r#"(\[status\.mod\.{}\][^\[]*updated_at = )"[^"]*""#,
+        name
+    ))?;
+    if existing.is_match(content) {
+        return Ok(existing
+            .replace(content, format!("${{1}}\"{}\"", date))
+            .to_string());
+    }
+
+    let header = regex::Regex::new(&format!(r"(\[status\.mod\.{}\][^\n]*\n)", name))?;
+    Ok(header
+        .replace(content, format!("${{1}}updated_at = \"{}\"\n", date))
+        .to_string())
+}
+
+fn run_status_list(filename: &str, args: &[String]) -> Result<(), Box<dyn Error>> {
+    // Parse options
+    let mut sort: Option<String> = None;
+    let mut desc = false;
+    let mut csv = false;
+    let mut since: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--sort" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].as_str() {
+                        "name" | "state" | "coverage" => sort = Some(args[i + 1].clone()),
+                        other => {
+                            eprintln!("Error: unknown sort field '{}' (expected name, state, or coverage)", other);
+                            std::process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --sort requires a value");
+                    std::process::exit(1);
+                }
+            }
+            "--desc" => {
+                desc = true;
+                i += 1;
+            }
+            "--since" => {
+                if i + 1 < args.len() {
+                    if chrono::NaiveDate::parse_from_str(&args[i + 1], "%Y-%m-%d").is_err() {
+                        eprintln!("Error: --since expects a date (YYYY-MM-DD), got '{}'", args[i + 1]);
+                        std::process::exit(1);
+                    }
+                    since = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --since requires a date");
+                    std::process::exit(1);
+                }
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].as_str() {
+                        "text" => csv = false,
+                        "csv" => csv = true,
+                        other => {
+                            eprintln!("Error: unknown format '{}' (expected text or csv)", other);
+                            std::process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --format requires a value");
+                    std::process::exit(1);
+                }
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                std::process::exit(1);
+            }
+        }
+    }
+
     let file = File::open(filename)?;
     let parsed = parse_surv_file(file)?;
 
@@ -284,59 +391,87 @@ fn run_status_list(filename: &str) -> Result<(), Box<dyn Error>> {
         }
     });
 
-    let modules: Vec<_> = parsed
+    let mut modules: Vec<_> = parsed
         .sections
         .iter()
         .filter_map(|s| {
             if let Section::Mod(m) = s {
-                Some(m)
+                Some((m, status.and_then(|st| st.modules.get(&m.name))))
             } else {
                 None
             }
         })
         .collect();
 
-    if modules.is_empty() {
-        println!("No modules found in {}", filename);
+    if let Some(since) = &since {
+        let file_date = status.map(|st| st.updated_at.as_str()).unwrap_or("");
+        modules.retain(|(_, ms)| ms.is_some_and(|ms| updated_since(ms, file_date, since)));
+    }
+
+    if modules.is_empty() && !csv {
+        match &since {
+            Some(since) => println!("No modules updated since {} in {}", since, filename),
+            None => println!("No modules found in {}", filename),
+        }
         return Ok(());
     }
 
-    println!("Modules in {}:", filename);
-    println!();
+    sort_status_rows(&mut modules, sort.as_deref(), desc);
 
-    for module in modules {
-        let module_status = status.and_then(|s| s.modules.get(&module.name));
-
-        print!("  mod.{:<20}", module.name);
-
-        if let Some(ms) = module_status {
-            let state_display = match ms.state.as_str() {
-                "done" => "✓ done",
-                "partial" => "◐ partial",
-                "skeleton" => "◯ skeleton",
-                "blocked" => "✗ blocked",
-                "todo" => "☐ todo",
-                _ => &ms.state,
+    if csv {
+        println!("module,state,coverage,notes");
+        for (module, module_status) in modules {
+            let (state, coverage, notes) = match module_status {
+                Some(ms) => (ms.state.clone(), ms.coverage.to_string(), ms.notes.clone()),
+                None => (String::new(), String::new(), String::new()),
             };
+            println!(
+                "{}",
+                csv_row(&[format!("mod.{}", module.name), state, coverage, notes])
+            );
+        }
+        return Ok(());
+    }
 
-            print!(" {:<12}", state_display);
-
-            if ms.coverage > 0.0 {
-                print!(" {:>3.0}%", ms.coverage * 100.0);
-            } else {
-                print!("     ");
-            }
+    println!("Modules in {}:", filename);
+    println!();
 
-            if !ms.notes.is_empty() {
-                print!("  {}", ms.notes);
+    let mut table = TextTable::new(&["MODULE", "STATE", "COVERAGE", "NOTES"])
+        .align_right(2)
+        .max_width(3, NOTES_MAX_WIDTH)
+        .indent(2);
+
+    for (module, module_status) in modules {
+        let row = match module_status {
+            Some(ms) => {
+                let state_display = match ms.state.as_str() {
+                    _ if markers::ascii() => ms.state.clone(),
+                    "done" => "✓ done".to_string(),
+                    "partial" => "◐ partial".to_string(),
+                    "skeleton" => "◯ skeleton".to_string(),
+                    "blocked" => "✗ blocked".to_string(),
+                    "todo" => "☐ todo".to_string(),
+                    other => other.to_string(),
+                };
+                let coverage = if ms.coverage > 0.0 {
+                    format!("{:.0}%", ms.coverage * 100.0)
+                } else {
+                    String::new()
+                };
+                vec![
+                    format!("mod.{}", module.name),
+                    state_display,
+                    coverage,
+                    ms.notes.clone(),
+                ]
             }
-        } else {
-            print!(" (no status)");
-        }
-
-        println!();
+            None => vec![format!("mod.{}", module.name), "(no status)".to_string()],
+        };
+        table.add_row(row);
     }
 
+    print!("{}", table.render());
+
     if let Some(status_section) = status {
         if !status_section.updated_at.is_empty() {
             println!();
@@ -347,6 +482,51 @@ fn run_status_list(filename: &str) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// `status list --since`: whether the module's status was updated on or
+/// after `since`. Modules never stamped fall back to the file-level date.
+fn updated_since(module_status: &ModuleStatus, file_date: &str, since: &str) -> bool {
+    let date = if module_status.updated_at.is_empty() {
+        file_date
+    } else {
+        &module_status.updated_at
+    };
+    !date.is_empty() && date >= since
+}
+
+/// Order `status list` rows by `--sort` (name, state or coverage), reversed
+/// with `--desc`. Without a sort field rows keep their file order.
+fn sort_status_rows(
+    rows: &mut [(&ModSection, Option<&ModuleStatus>)],
+    sort: Option<&str>,
+    desc: bool,
+) {
+    match sort {
+        Some("name") => rows.sort_by(|a, b| a.0.name.cmp(&b.0.name)),
+        Some("state") => rows.sort_by_key(|(_, ms)| ms.map(|ms| state_rank(&ms.state)).unwrap_or(0)),
+        Some("coverage") => rows.sort_by(|a, b| {
+            let ca = a.1.map(|ms| ms.coverage).unwrap_or(0.0);
+            let cb = b.1.map(|ms| ms.coverage).unwrap_or(0.0);
+            ca.total_cmp(&cb)
+        }),
+        _ => {}
+    }
+    if desc {
+        rows.reverse();
+    }
+}
+
+/// Order states from least to most complete; unknown states sort first
+fn state_rank(state: &str) -> u8 {
+    match state {
+        "blocked" => 1,
+        "todo" => 2,
+        "skeleton" => 3,
+        "partial" => 4,
+        "done" => 5,
+        _ => 0,
+    }
+}
+
 fn run_status_show(module_name: &str, filename: &str) -> Result<(), Box<dyn Error>> {
     let file = File::open(filename)?;
     let parsed = parse_surv_file(file)?;
@@ -410,7 +590,7 @@ fn run_status_show(module_name: &str, filename: &str) -> Result<(), Box<dyn Erro
 
     Ok(())
 }
-fn run_status_sync(filename: &str) -> Result<(), Box<dyn Error>> {
+fn run_status_sync(filename: &str, prune: bool) -> Result<(), Box<dyn Error>> {
     let content = fs::read_to_string(filename)?;
     let file = File::open(filename)?;
     let parsed = parse_surv_file(file)?;
@@ -456,27 +636,37 @@ fn run_status_sync(filename: &str) -> Result<(), Box<dyn Error>> {
         .cloned()
         .collect();
 
-    if missing_modules.is_empty() {
-        println!("✓ All modules already have status entries");
+    // Status entries left behind by renamed or removed modules
+    let stale_modules: Vec<String> = status_section
+        .modules
+        .keys()
+        .filter(|module_name| !all_modules.contains(*module_name))
+        .cloned()
+        .collect();
+
+    if missing_modules.is_empty() && (stale_modules.is_empty() || !prune) {
+        println!("{} All modules already have status entries", markers::ok());
+        print_stale_hint(&stale_modules, prune);
         return Ok(());
     }
 
+    let mut new_content = content;
+    if prune {
+        for module in &stale_modules {
+            new_content = remove_module_status(&new_content, module)?;
+        }
+    }
+
     // Generate status entries for missing modules
-    let mut new_status_text = String::new();
     for module in &missing_modules {
-        new_status_text.push_str(&format!("\n[status.mod.{}]\n", module));
-        new_status_text.push_str("state = \"todo\"\n");
-        new_status_text.push_str("coverage = 0.0\n");
-        new_status_text.push_str("notes = \"\"\n");
+        new_content.push_str(&format!("\n[status.mod.{}]\n", module));
+        new_content.push_str("state = \"todo\"\n");
+        new_content.push_str("coverage = 0.0\n");
+        new_content.push_str("notes = \"\"\n");
     }
 
-    // Append to file
-    let mut output = fs::OpenOptions::new().append(true).open(filename)?;
-    output.write_all(new_status_text.as_bytes())?;
-
     // Update timestamp
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let new_content = fs::read_to_string(filename)?;
     let timestamp_pattern = r#"(\[status\][^\[]*updated_at = )"[^"]*""#;
     let re = regex::Regex::new(timestamp_pattern)?;
     let updated_content = re
@@ -484,11 +674,140 @@ fn run_status_sync(filename: &str) -> Result<(), Box<dyn Error>> {
         .to_string();
     fs::write(filename, updated_content)?;
 
-    println!("✓ Synced status section in {}", filename);
+    println!("{} Synced status section in {}", markers::ok(), filename);
     println!("  Added modules: {}", missing_modules.len());
     for module in missing_modules {
         println!("    - mod.{} (todo)", module);
     }
+    if prune {
+        println!("  Removed stale entries: {}", stale_modules.len());
+        for module in &stale_modules {
+            println!("    - mod.{}", module);
+        }
+    }
+    print_stale_hint(&stale_modules, prune);
 
     Ok(())
 }
+
+fn print_stale_hint(stale_modules: &[String], prune: bool) {
+    if prune || stale_modules.is_empty() {
+        return;
+    }
+    println!(
+        "{} {} status entr{} without a module: {} (rerun with --prune to remove)",
+        markers::warn(),
+        stale_modules.len(),
+        if stale_modules.len() == 1 { "y" } else { "ies" },
+        stale_modules.iter().map(|m| format!("mod.{}", m)).collect::<Vec<_>>().join(", ")
+    );
+}
+
+/// Remove the `[status.mod.<name>]` table: its header and every line up to
+/// the next table header.
+fn remove_module_status(content: &str, module_name: &str) -> Result<String, Box<dyn Error>> {
+    let pattern = format!(
+        r"(?m)^\[status\.mod\.{}\][^\n]*\n(?:[^\[\n][^\n]*\n?|\n)*",
+        regex::escape(module_name)
+    );
+    let re = regex::Regex::new(&pattern)?;
+    Ok(re.replace(content, "").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_only_the_named_status_table() {
+        let content = "[mod.kept]\npurpose = \"x\"\n\n[status]\nupdated_at = \"2026-01-01\"\n\n\
+[status.mod.gone]\nstate = \"todo\"\ncoverage = 0.0\n\n[status.mod.kept]\nstate = \"done\"\n";
+
+        let pruned = remove_module_status(content, "gone").unwrap();
+
+        assert!(!pruned.contains("[status.mod.gone]"));
+        assert!(!pruned.contains("state = \"todo\""));
+        assert!(pruned.contains("[status.mod.kept]\nstate = \"done\"\n"));
+        assert!(pruned.contains("[status]\nupdated_at = \"2026-01-01\"\n"));
+    }
+
+    fn module(name: &str) -> ModSection {
+        ModSection {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn status(state: &str, coverage: f64) -> ModuleStatus {
+        ModuleStatus {
+            state: state.to_string(),
+            coverage,
+            ..Default::default()
+        }
+    }
+
+    fn names(rows: &[(&ModSection, Option<&ModuleStatus>)]) -> Vec<String> {
+        rows.iter().map(|(m, _)| m.name.clone()).collect()
+    }
+
+    #[test]
+    fn state_rank_orders_from_least_to_most_complete() {
+        let states = ["done", "partial", "skeleton", "todo", "blocked", "custom"];
+        let mut sorted = states.to_vec();
+        sorted.sort_by_key(|state| state_rank(state));
+        assert_eq!(sorted, ["custom", "blocked", "todo", "skeleton", "partial", "done"]);
+    }
+
+    #[test]
+    fn sorts_status_rows_by_field_and_direction() {
+        let (api, core, web) = (module("api"), module("core"), module("web"));
+        let (done, todo) = (status("done", 0.9), status("todo", 0.1));
+        let rows = [(&web, Some(&todo)), (&api, Some(&done)), (&core, None)];
+
+        let mut by_name = rows;
+        sort_status_rows(&mut by_name, Some("name"), false);
+        assert_eq!(names(&by_name), ["api", "core", "web"]);
+
+        // Modules without a status entry rank with unknown states, first
+        let mut by_state = rows;
+        sort_status_rows(&mut by_state, Some("state"), false);
+        assert_eq!(names(&by_state), ["core", "web", "api"]);
+
+        let mut by_coverage = rows;
+        sort_status_rows(&mut by_coverage, Some("coverage"), true);
+        assert_eq!(names(&by_coverage), ["api", "web", "core"]);
+
+        let mut unsorted = rows;
+        sort_status_rows(&mut unsorted, None, false);
+        assert_eq!(names(&unsorted), ["web", "api", "core"]);
+    }
+
+    #[test]
+    fn filters_modules_updated_since_a_date() {
+        let stamped = ModuleStatus {
+            updated_at: "2026-03-10".to_string(),
+            ..status("done", 1.0)
+        };
+        assert!(updated_since(&stamped, "2026-01-01", "2026-03-10"));
+        assert!(!updated_since(&stamped, "2026-12-01", "2026-03-11"));
+
+        // Unstamped modules use the file-level date, and are left out without one
+        let unstamped = status("todo", 0.0);
+        assert!(updated_since(&unstamped, "2026-04-01", "2026-03-10"));
+        assert!(!updated_since(&unstamped, "2026-01-01", "2026-03-10"));
+        assert!(!updated_since(&unstamped, "", "2026-03-10"));
+    }
+
+    #[test]
+    fn stamps_module_updated_at() {
+        let content = "[status.mod.a]\nstate = \"todo\"\n\n[status.mod.b]\nstate = \"done\"\n";
+
+        let stamped = stamp_module_updated_at(content, "a", "2026-03-10").unwrap();
+        assert!(stamped.contains("[status.mod.a]\nupdated_at = \"2026-03-10\"\n"));
+        assert!(!stamped.contains("[status.mod.b]\nupdated_at"));
+
+        let restamped = stamp_module_updated_at(&stamped, "a", "2026-04-01").unwrap();
+        assert!(restamped.contains("updated_at = \"2026-04-01\""));
+        assert_eq!(restamped.matches("updated_at").count(), 1);
+    }
+}
@@ -1,17 +1,28 @@
 use crate::ast::{Section, SurvFile};
 use crate::diagnostic::Diagnostic;
 use crate::imports::{FileImportContext, ImportEntry};
+use crate::manifest::Manifest;
 use crate::project::ProjectAST;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SymbolKind {
     Schema,
     Func,
     Mod,
 }
 
+impl SymbolKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Schema => "schema",
+            SymbolKind::Func => "func",
+            SymbolKind::Mod => "mod",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SymbolEntry {
     pub kind: SymbolKind,
@@ -137,17 +148,17 @@ fn report_duplicates(
         entry.local_name.clone(),
     );
     if let Some(existing) = defined.get(&key) {
-        diags.push(Diagnostic {
-            severity: "warning".into(),
-            kind: "W_AMBIGUOUS_NAME".into(),
-            message: format!(
+        diags.push(Diagnostic::new(
+            "warning",
+            "W_AMBIGUOUS_NAME",
+            format!(
                 "Symbol '{}' is defined in both {} and {}",
                 entry.fq_name,
                 existing.display(),
                 current_path.display()
             ),
-            location: current_path.display().to_string(),
-        });
+            current_path.display().to_string(),
+        ));
     } else {
         defined.insert(key, current_path.to_path_buf());
     }
@@ -182,17 +193,20 @@ fn emit_undefined(
     context: &str,
     diags: &mut Vec<Diagnostic>,
 ) {
-    let code = match kind {
+    diags.push(Diagnostic::new(
+        "error",
+        undefined_code(kind),
+        format!("Reference '{}' is undefined", reference),
+        format!("{}: {}", path.display(), context),
+    ));
+}
+
+fn undefined_code(kind: SymbolKind) -> &'static str {
+    match kind {
         SymbolKind::Schema => "E_UNDEFINED_SCHEMA",
         SymbolKind::Func => "E_UNDEFINED_FUNC",
         SymbolKind::Mod => "E_UNDEFINED_MOD",
-    };
-    diags.push(Diagnostic {
-        severity: "error".into(),
-        kind: code.into(),
-        message: format!("Reference '{}' is undefined", reference),
-        location: format!("{}: {}", path.display(), context),
-    });
+    }
 }
 
 fn emit_ambiguous(
@@ -205,32 +219,49 @@ fn emit_ambiguous(
 ) {
     let code = "W_AMBIGUOUS_NAME";
     let candidates: Vec<String> = matches.iter().map(|entry| entry.fq_name.clone()).collect();
-    let kind_str = match kind {
-        SymbolKind::Schema => "schema",
-        SymbolKind::Func => "func",
-        SymbolKind::Mod => "mod",
-    };
-    diags.push(Diagnostic {
-        severity: "warning".into(),
-        kind: code.into(),
-        message: format!(
+    let kind_str = kind.as_str();
+    diags.push(Diagnostic::new(
+        "warning",
+        code,
+        format!(
             "Ambiguous {kind_str} reference '{}'; candidates: {}",
             reference,
             candidates.join(", ")
         ),
-        location: format!("{}: {}", path.display(), context),
-    });
+        format!("{}: {}", path.display(), context),
+    ));
+}
+
+fn emit_shadowed(
+    reference: &str,
+    local: &SymbolEntry,
+    shadowed: Vec<&SymbolEntry>,
+    path: &Path,
+    context: &str,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let hidden: Vec<String> = shadowed.iter().map(|entry| entry.fq_name.clone()).collect();
+    diags.push(Diagnostic::new(
+        "warning",
+        "W_SHADOWED_IMPORT",
+        format!(
+            "Reference '{}' resolves to local {} and shadows imported {}",
+            reference,
+            local.fq_name,
+            hidden.join(", ")
+        ),
+        format!("{}: {}", path.display(), context),
+    ));
 }
 
 #[cfg(test)]
 mod symbol_tests {
     use super::*;
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
     use std::path::PathBuf;
 
     fn file(path: &str, text: &str) -> (PathBuf, crate::ast::SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -337,6 +368,18 @@ pub fn resolve_names_with_packages(
     files: &[(PathBuf, SurvFile)],
     symbols: &SymbolTable,
     import_contexts: &[FileImportContext],
+) -> Vec<Diagnostic> {
+    resolve_names_with_options(files, symbols, import_contexts, false)
+}
+
+/// Like `resolve_names_with_packages`. With `strict_namespaces`, an unqualified
+/// reference must resolve in the file's own package or an import; there is no
+/// project-wide fallback.
+pub fn resolve_names_with_options(
+    files: &[(PathBuf, SurvFile)],
+    symbols: &SymbolTable,
+    import_contexts: &[FileImportContext],
+    strict_namespaces: bool,
 ) -> Vec<Diagnostic> {
     let ctx_map: HashMap<_, _> = import_contexts
         .iter()
@@ -356,9 +399,9 @@ pub fn resolve_names_with_packages(
                             symbols,
                             SymbolKind::Schema,
                             schema,
-                            path,
                             ctx,
                             &format!("func.{}.input({})", func.name, schema),
+                            strict_namespaces,
                             &mut diags,
                         );
                     }
@@ -367,9 +410,9 @@ pub fn resolve_names_with_packages(
                             symbols,
                             SymbolKind::Schema,
                             schema,
-                            path,
                             ctx,
                             &format!("func.{}.output({})", func.name, schema),
+                            strict_namespaces,
                             &mut diags,
                         );
                     }
@@ -381,9 +424,9 @@ pub fn resolve_names_with_packages(
                                 symbols,
                                 SymbolKind::Schema,
                                 &schema.from,
-                                path,
                                 ctx,
                                 &format!("schema.{}.from({})", schema.name, schema.from),
+                                strict_namespaces,
                                 &mut diags,
                             );
                         }
@@ -392,9 +435,9 @@ pub fn resolve_names_with_packages(
                                 symbols,
                                 SymbolKind::Schema,
                                 &schema.to,
-                                path,
                                 ctx,
                                 &format!("schema.{}.to({})", schema.name, schema.to),
+                                strict_namespaces,
                                 &mut diags,
                             );
                         }
@@ -405,9 +448,9 @@ pub fn resolve_names_with_packages(
                                 symbols,
                                 SymbolKind::Schema,
                                 over,
-                                path,
                                 ctx,
                                 &format!("schema.{}.over({})", schema.name, over),
+                                strict_namespaces,
                                 &mut diags,
                             );
                         }
@@ -420,9 +463,9 @@ pub fn resolve_names_with_packages(
                             symbols,
                             SymbolKind::Schema,
                             s,
-                            path,
                             ctx,
                             &format!("mod.{}.schemas({})", module.name, s),
+                            strict_namespaces,
                             &mut diags,
                         );
                     }
@@ -431,9 +474,9 @@ pub fn resolve_names_with_packages(
                             symbols,
                             SymbolKind::Func,
                             func,
-                            path,
                             ctx,
                             &format!("mod.{}.funcs({})", module.name, func),
+                            strict_namespaces,
                             &mut diags,
                         );
                     }
@@ -442,9 +485,9 @@ pub fn resolve_names_with_packages(
                             symbols,
                             SymbolKind::Func,
                             step,
-                            path,
                             ctx,
                             &format!("mod.{}.pipeline({})", module.name, step),
+                            strict_namespaces,
                             &mut diags,
                         );
                     }
@@ -462,14 +505,15 @@ fn resolve_reference(
     symbols: &SymbolTable,
     kind: SymbolKind,
     reference: &str,
-    path: &Path,
     ctx: &FileImportContext,
     context: &str,
+    strict_namespaces: bool,
     diags: &mut Vec<Diagnostic>,
 ) {
     if reference.is_empty() {
         return;
     }
+    let path = ctx.file_path.as_path();
 
     let reference = reference.trim();
     let (prefix, base) = split_reference(reference);
@@ -477,14 +521,15 @@ fn resolve_reference(
     match prefix {
         Some(prefix) if prefix != "schema" && prefix != "func" => {
             let Some(package) = resolve_prefix(prefix, ctx) else {
-                diags.push(Diagnostic {
-                    severity: "error".into(),
-                    kind: "E_UNDEFINED_PREFIX".into(),
-                    message: format!("Unknown reference prefix '{}'", prefix),
-                    location: format!("{}: {}", path.display(), context),
-                });
+                diags.push(Diagnostic::new(
+                    "error",
+                    "E_UNDEFINED_PREFIX",
+                    format!("Unknown reference prefix '{}'", prefix),
+                    format!("{}: {}", path.display(), context),
+                ));
                 return;
             };
+            let first_new = diags.len();
             resolve_package_reference(
                 symbols,
                 kind,
@@ -495,6 +540,11 @@ fn resolve_reference(
                 context,
                 diags,
             );
+            if package != ctx.self_package {
+                for diag in &mut diags[first_new..] {
+                    diag.package = Some(package.clone());
+                }
+            }
         }
         _ => {
             let local = if prefix.is_some() {
@@ -509,7 +559,12 @@ fn resolve_reference(
             if resolve_import_packages(symbols, kind, ctx, local, reference, path, context, diags) {
                 return;
             }
-            resolve_global_reference(symbols, kind, local, reference, path, context, diags);
+            if strict_namespaces {
+                let elsewhere = lookup_any_namespace(symbols, kind, local);
+                emit_not_visible(kind, elsewhere, ctx, reference, context, diags);
+            } else {
+                resolve_global_reference(symbols, kind, local, reference, path, context, diags);
+            }
         }
     }
 }
@@ -533,7 +588,20 @@ fn resolve_self_package(
     );
     match matches.len() {
         0 => false,
-        1 => true,
+        1 => {
+            let shadowed: Vec<_> = ctx
+                .imports
+                .iter()
+                .filter(|import| import.package != ctx.self_package)
+                .flat_map(|import| {
+                    lookup_in_package(symbols, kind, &import.package, None, local_name)
+                })
+                .collect();
+            if !shadowed.is_empty() {
+                emit_shadowed(reference, matches[0], shadowed, path, context, diags);
+            }
+            true
+        }
         _ => {
             emit_ambiguous(kind, reference, matches, path, context, diags);
             true
@@ -606,6 +674,155 @@ fn resolve_global_reference(
     }
 }
 
+/// Strict-namespace miss: the reference is undefined from this file's point of
+/// view, even if another package defines the name.
+fn emit_not_visible(
+    kind: SymbolKind,
+    elsewhere: Vec<&SymbolEntry>,
+    ctx: &FileImportContext,
+    reference: &str,
+    context: &str,
+    diags: &mut Vec<Diagnostic>,
+) {
+    if elsewhere.is_empty() {
+        emit_undefined(kind, reference, &ctx.file_path, context, diags);
+        return;
+    }
+
+    let candidates: Vec<String> = elsewhere
+        .iter()
+        .map(|entry| entry.fq_name.clone())
+        .collect();
+    diags.push(
+        Diagnostic::new(
+            "error",
+            undefined_code(kind),
+            format!(
+            "Reference '{}' is not visible from package '{}' (strict_namespaces); defined as {}",
+            reference,
+            ctx.self_package,
+            candidates.join(", ")
+        ),
+            format!("{}: {}", ctx.file_path.display(), context),
+        )
+        .with_package(match elsewhere.as_slice() {
+            [entry] => Some(entry.package.clone()),
+            _ => None,
+        })
+        .with_suggestion(match elsewhere.as_slice() {
+            [entry] => Some(format!("add \"{}\" to this file's imports", entry.package)),
+            _ => None,
+        }),
+    );
+}
+
+/// Copy of the project's files with every schema/func reference rewritten to
+/// its `fq_name`. References that are undefined or ambiguous are left as-is and
+/// reported as `UnresolvedReference` warnings.
+pub fn resolve_to_fq_names(
+    project: &ProjectAST,
+    symbols: &SymbolTable,
+) -> (Vec<(PathBuf, SurvFile)>, Vec<Diagnostic>) {
+    let contexts = project_import_contexts(project);
+    let mut diags = Vec::new();
+    let mut files = Vec::new();
+
+    for ((path, file), ctx) in project.files.iter().zip(&contexts) {
+        let mut file = file.clone();
+        let mut rewrite = |kind: SymbolKind, reference: &mut String| {
+            if reference.is_empty() {
+                return;
+            }
+            let matches = lookup_reference(symbols, kind, reference.trim(), ctx);
+            match matches.as_slice() {
+                [entry] => *reference = entry.fq_name.clone(),
+                [] => diags.push(unresolved_note(path, reference, "undefined")),
+                _ => {
+                    let candidates: Vec<&str> =
+                        matches.iter().map(|e| e.fq_name.as_str()).collect();
+                    let reason = format!("ambiguous: {}", candidates.join(", "));
+                    diags.push(unresolved_note(path, reference, &reason));
+                }
+            }
+        };
+
+        for section in &mut file.sections {
+            match section {
+                Section::Func(func) => {
+                    for schema in func.input.iter_mut().chain(func.output.iter_mut()) {
+                        rewrite(SymbolKind::Schema, schema);
+                    }
+                }
+                Section::Schema(schema) => {
+                    rewrite(SymbolKind::Schema, &mut schema.from);
+                    rewrite(SymbolKind::Schema, &mut schema.to);
+                    for over in &mut schema.over {
+                        rewrite(SymbolKind::Schema, over);
+                    }
+                }
+                Section::Mod(module) => {
+                    for schema in &mut module.schemas {
+                        rewrite(SymbolKind::Schema, schema);
+                    }
+                    for func in module.funcs.iter_mut() {
+                        rewrite(SymbolKind::Func, func);
+                    }
+                    for step in module.pipeline_chains.iter_mut().flatten().flatten() {
+                        rewrite(SymbolKind::Func, step);
+                    }
+                    module.pipeline = module.pipeline_stages().concat();
+                }
+                Section::Meta(_) | Section::Status(_) => {}
+            }
+        }
+        files.push((path.clone(), file));
+    }
+
+    (files, diags)
+}
+
+/// Candidates for a reference, in the same order `resolve_reference` checks:
+/// explicit package prefix, own package, imports, then any namespace.
+fn lookup_reference<'a>(
+    symbols: &'a SymbolTable,
+    kind: SymbolKind,
+    reference: &str,
+    ctx: &FileImportContext,
+) -> Vec<&'a SymbolEntry> {
+    let (prefix, base) = split_reference(reference);
+    if let Some(prefix) = prefix.filter(|p| *p != "schema" && *p != "func") {
+        let Some(package) = resolve_prefix(prefix, ctx) else {
+            return Vec::new();
+        };
+        return lookup_in_package(symbols, kind, &package, None, extract_local_name(base));
+    }
+
+    let local = extract_local_name(reference);
+    let namespace = ctx.namespace.as_deref();
+    let own = lookup_in_package(symbols, kind, &ctx.self_package, namespace, local);
+    if !own.is_empty() {
+        return own;
+    }
+    let imported: Vec<_> = ctx
+        .imports
+        .iter()
+        .flat_map(|import| lookup_in_package(symbols, kind, &import.package, None, local))
+        .collect();
+    if !imported.is_empty() {
+        return imported;
+    }
+    lookup_any_namespace(symbols, kind, local)
+}
+
+fn unresolved_note(path: &Path, reference: &str, reason: &str) -> Diagnostic {
+    Diagnostic::new(
+        "warning",
+        "UnresolvedReference",
+        format!("Reference '{}' left as-is ({})", reference, reason),
+        path.display().to_string(),
+    )
+}
+
 fn resolve_prefix(prefix: &str, ctx: &FileImportContext) -> Option<String> {
     if prefix == ctx.self_package {
         return Some(prefix.to_string());
@@ -673,7 +890,25 @@ pub fn resolve_schema_and_func_references(
     project: &ProjectAST,
     symbols: &SymbolTable,
 ) -> Vec<Diagnostic> {
-    let contexts: Vec<FileImportContext> = project
+    resolve_names_with_packages(&project.files, symbols, &project_import_contexts(project))
+}
+
+/// Resolve references honoring manifest options such as `[project] strict_namespaces`.
+pub fn resolve_project_references(
+    project: &ProjectAST,
+    symbols: &SymbolTable,
+    manifest: &Manifest,
+) -> Vec<Diagnostic> {
+    resolve_names_with_options(
+        &project.files,
+        symbols,
+        &project_import_contexts(project),
+        manifest.project.strict_namespaces,
+    )
+}
+
+fn project_import_contexts(project: &ProjectAST) -> Vec<FileImportContext> {
+    project
         .files
         .iter()
         .map(|(path, file)| FileImportContext {
@@ -689,19 +924,85 @@ pub fn resolve_schema_and_func_references(
                 })
                 .collect(),
         })
+        .collect()
+}
+
+/// `changed` plus every file that references a symbol defined in one of
+/// them, transitively: the files whose checks can change when `changed` do.
+/// References match definitions by kind and local name, whatever package
+/// prefix they carry.
+pub fn reverse_dependency_closure(
+    project: &ProjectAST,
+    symbols: &SymbolTable,
+    changed: &BTreeSet<PathBuf>,
+) -> BTreeSet<PathBuf> {
+    let references: Vec<(&PathBuf, BTreeSet<(SymbolKind, &str)>)> = project
+        .files
+        .iter()
+        .map(|(path, file)| (path, file_references(file)))
         .collect();
-    resolve_names_with_packages(&project.files, symbols, &contexts)
+
+    let mut affected = changed.clone();
+    let mut pending: Vec<PathBuf> = changed.iter().cloned().collect();
+    while let Some(path) = pending.pop() {
+        let defined: BTreeSet<(SymbolKind, &str)> = symbols
+            .entries
+            .iter()
+            .filter(|entry| entry.file == path)
+            .map(|entry| (entry.kind, entry.local_name.as_str()))
+            .collect();
+        for (dependent, refs) in &references {
+            if !affected.contains(*dependent) && !refs.is_disjoint(&defined) {
+                affected.insert((*dependent).clone());
+                pending.push((*dependent).clone());
+            }
+        }
+    }
+    affected
+}
+
+fn file_references(file: &SurvFile) -> BTreeSet<(SymbolKind, &str)> {
+    fn local(reference: &str) -> &str {
+        reference.trim().rsplit('.').next().unwrap_or_default()
+    }
+    let mut refs = BTreeSet::new();
+    for require in &file.requires {
+        refs.insert((SymbolKind::Mod, local(&require.target)));
+    }
+    for section in &file.sections {
+        match section {
+            Section::Func(func) => {
+                for schema in func.input.iter().chain(&func.output) {
+                    refs.insert((SymbolKind::Schema, local(schema)));
+                }
+            }
+            Section::Schema(schema) => {
+                for link in [&schema.from, &schema.to].into_iter().chain(&schema.over) {
+                    refs.insert((SymbolKind::Schema, local(link)));
+                }
+            }
+            Section::Mod(module) => {
+                for schema in &module.schemas {
+                    refs.insert((SymbolKind::Schema, local(schema)));
+                }
+                for func in module.funcs.iter().chain(&module.pipeline) {
+                    refs.insert((SymbolKind::Func, local(func)));
+                }
+            }
+            Section::Meta(_) | Section::Status(_) => {}
+        }
+    }
+    refs
 }
 
 #[cfg(test)]
 mod resolve_tests {
     use super::*;
-    use crate::parser::parse_surv_file;
-    use std::io::Cursor;
+    use crate::parser::parse_surv_str;
     use std::path::PathBuf;
 
     fn file(path: &str, text: &str) -> (PathBuf, crate::ast::SurvFile) {
-        let parsed = parse_surv_file(Cursor::new(text)).expect("parse");
+        let parsed = parse_surv_str(text).expect("parse");
         (PathBuf::from(path), parsed)
     }
 
@@ -816,4 +1117,189 @@ type = "User"
         assert!(packages.contains(&"users".to_string()));
         assert!(packages.contains(&"auth".to_string()));
     }
+
+    #[test]
+    fn local_definition_shadowing_import_warns() {
+        let files = vec![
+            file(
+                "pkg/users/a.toml",
+                r#"
+[schema.user]
+kind = "node"
+type = "User"
+"#,
+            ),
+            file(
+                "pkg/auth/a.toml",
+                r#"
+[schema.user]
+kind = "node"
+type = "AuthUser"
+
+[func.login]
+intent = "test"
+input = ["schema.user"]
+output = []
+"#,
+            ),
+        ];
+        let mut assignments = HashMap::new();
+        assignments.insert(PathBuf::from("pkg/users/a.toml"), "users".to_string());
+        assignments.insert(PathBuf::from("pkg/auth/a.toml"), "auth".to_string());
+
+        let (symbols, _) = build_symbol_table_with_packages(&files, &assignments);
+        let contexts = vec![FileImportContext {
+            file_path: PathBuf::from("pkg/auth/a.toml"),
+            self_package: "auth".into(),
+            namespace: None,
+            imports: vec![ImportEntry {
+                package: "users".into(),
+                alias: None,
+            }],
+        }];
+        let diags = resolve_names_with_packages(&files, &symbols, &contexts);
+        let shadow = diags
+            .iter()
+            .find(|d| d.kind == "W_SHADOWED_IMPORT")
+            .expect("shadow warning");
+        assert!(shadow.message.contains("pkg.auth."));
+        assert!(shadow.message.contains("pkg.users."));
+    }
+
+    #[test]
+    fn rewrites_references_to_fq_names() {
+        let project = ProjectAST::from_files(vec![
+            file(
+                "users.toml",
+                r#"
+package = "users"
+
+[schema.user]
+kind = "node"
+
+[func.load]
+intent = "test"
+output = ["schema.user", "schema.missing"]
+"#,
+            ),
+            file(
+                "api.toml",
+                r#"
+package = "api"
+import = ["users"]
+
+[mod.api]
+purpose = "test"
+schemas = ["schema.user"]
+pipeline = ["func.load"]
+"#,
+            ),
+        ]);
+        let (symbols, _) = build_symbol_table(&project);
+        let (files, diags) = resolve_to_fq_names(&project, &symbols);
+
+        let Section::Func(load) = &files[0].1.sections[1] else {
+            panic!("expected func");
+        };
+        assert_eq!(load.output, vec!["pkg.users.schema.global.user", "schema.missing"]);
+        let Section::Mod(api) = &files[1].1.sections[0] else {
+            panic!("expected mod");
+        };
+        assert_eq!(api.pipeline_stages(), vec![vec!["pkg.users.func.global.load"]]);
+
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, "UnresolvedReference");
+        assert!(diags[0].message.contains("schema.missing"));
+    }
+
+    #[test]
+    fn strict_namespaces_disables_global_fallback() {
+        let files = vec![
+            file(
+                "pkg/users/a.toml",
+                r#"
+[schema.user]
+kind = "node"
+"#,
+            ),
+            file(
+                "pkg/auth/a.toml",
+                r#"
+[func.login]
+intent = "test"
+input = ["schema.user"]
+"#,
+            ),
+        ];
+        let mut assignments = HashMap::new();
+        assignments.insert(PathBuf::from("pkg/users/a.toml"), "users".to_string());
+        assignments.insert(PathBuf::from("pkg/auth/a.toml"), "auth".to_string());
+
+        let (symbols, _) = build_symbol_table_with_packages(&files, &assignments);
+        let contexts = vec![FileImportContext {
+            file_path: PathBuf::from("pkg/auth/a.toml"),
+            self_package: "auth".into(),
+            namespace: None,
+            imports: vec![],
+        }];
+
+        let lenient = resolve_names_with_options(&files, &symbols, &contexts, false);
+        assert!(lenient.is_empty());
+
+        let strict = resolve_names_with_options(&files, &symbols, &contexts, true);
+        assert_eq!(strict.len(), 1);
+        assert_eq!(strict[0].kind, "E_UNDEFINED_SCHEMA");
+        assert!(strict[0].message.contains("not visible"));
+        assert_eq!(strict[0].package.as_deref(), Some("users"));
+    }
+
+    #[test]
+    fn reverse_dependency_closure_follows_references() {
+        let files = vec![
+            file(
+                "schemas.toml",
+                r#"
+[schema.user]
+kind = "node"
+"#,
+            ),
+            file(
+                "funcs.toml",
+                r#"
+[func.create]
+intent = "test"
+input = ["schema.user"]
+output = ["schema.user"]
+"#,
+            ),
+            file(
+                "mods.toml",
+                r#"
+[mod.users]
+purpose = "test"
+pipeline = ["func.create"]
+"#,
+            ),
+            file(
+                "other.toml",
+                r#"
+[schema.order]
+kind = "node"
+"#,
+            ),
+        ];
+        let project = ProjectAST::from_files(files);
+        let (symbols, _) = build_symbol_table(&project);
+
+        let changed = BTreeSet::from([PathBuf::from("schemas.toml")]);
+        let affected = reverse_dependency_closure(&project, &symbols, &changed);
+        let expected: BTreeSet<PathBuf> = ["schemas.toml", "funcs.toml", "mods.toml"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(affected, expected);
+
+        let changed = BTreeSet::from([PathBuf::from("mods.toml")]);
+        assert_eq!(reverse_dependency_closure(&project, &symbols, &changed), changed);
+    }
 }
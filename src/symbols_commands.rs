@@ -0,0 +1,275 @@
+use survibe_parser_rs::{build_symbol_table, load_project, SymbolEntry, SymbolKind, SymbolTable};
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::Path;
+
+struct SymbolsOptions {
+    kind: Option<SymbolKind>,
+    package: Option<String>,
+    format: OutputFormat,
+}
+
+enum OutputFormat {
+    Text,
+    Json,
+    /// One JSON object per line, for `jq`/`grep` pipelines
+    Ndjson,
+}
+
+pub fn run_symbols(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        print_symbols_usage();
+        if args.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let manifest_path = Path::new(&args[0]);
+    let options = parse_options(&args[1..])?;
+
+    let project = load_project(manifest_path)?;
+    let (table, _diags) = build_symbol_table(&project);
+
+    let entries = select_symbols(&table, &options);
+
+    match options.format {
+        OutputFormat::Text => print_text(&entries),
+        OutputFormat::Json => {
+            let items: Vec<_> = entries.iter().map(|entry| symbol_json(entry)).collect();
+            println!("{}", serde_json::to_string_pretty(&items)?);
+        }
+        OutputFormat::Ndjson => write_ndjson(&mut io::stdout().lock(), &entries)?,
+    }
+
+    Ok(())
+}
+
+/// Symbols matching `--kind` and `--package`, sorted by fully-qualified name
+fn select_symbols<'a>(table: &'a SymbolTable, options: &SymbolsOptions) -> Vec<&'a SymbolEntry> {
+    let mut entries: Vec<&SymbolEntry> = table
+        .entries
+        .iter()
+        .filter(|entry| options.kind.is_none_or(|kind| entry.kind == kind))
+        .filter(|entry| {
+            options
+                .package
+                .as_deref()
+                .is_none_or(|pkg| entry.package == pkg)
+        })
+        .collect();
+    entries.sort_by(|a, b| a.fq_name.cmp(&b.fq_name));
+    entries
+}
+
+fn parse_options(args: &[String]) -> Result<SymbolsOptions, Box<dyn Error>> {
+    let mut kind = None;
+    let mut package = None;
+    let mut format = OutputFormat::Text;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--kind" => {
+                if i + 1 < args.len() {
+                    kind = Some(match args[i + 1].as_str() {
+                        "schema" => SymbolKind::Schema,
+                        "func" => SymbolKind::Func,
+                        "mod" => SymbolKind::Mod,
+                        other => return Err(format!("Unknown symbol kind: {}", other).into()),
+                    });
+                    i += 2;
+                } else {
+                    return Err("--kind requires a value (schema, func, mod)".into());
+                }
+            }
+            "--package" => {
+                if i + 1 < args.len() {
+                    package = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    return Err("--package requires a package name".into());
+                }
+            }
+            "--format" => {
+                if i + 1 < args.len() {
+                    format = match args[i + 1].as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        "ndjson" => OutputFormat::Ndjson,
+                        other => return Err(format!("Unknown format: {}", other).into()),
+                    };
+                    i += 2;
+                } else {
+                    return Err("--format requires a value (text, json, ndjson)".into());
+                }
+            }
+            other => {
+                return Err(format!("Unknown option: {}", other).into());
+            }
+        }
+    }
+
+    Ok(SymbolsOptions {
+        kind,
+        package,
+        format,
+    })
+}
+
+fn print_text(entries: &[&SymbolEntry]) {
+    if entries.is_empty() {
+        println!("No symbols found");
+        return;
+    }
+
+    let width = entries.iter().map(|e| e.fq_name.len()).max().unwrap_or(0);
+    for entry in entries {
+        println!(
+            "{:<width$}  ({:<6})  {}",
+            entry.fq_name,
+            entry.kind.as_str(),
+            entry.file.display(),
+            width = width
+        );
+    }
+    println!();
+    println!("{} symbol(s)", entries.len());
+}
+
+/// One JSON object per line; stops quietly when the reader goes away (`| head`)
+fn write_ndjson(out: &mut impl Write, entries: &[&SymbolEntry]) -> io::Result<()> {
+    for entry in entries {
+        match writeln!(out, "{}", symbol_json(entry)) {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => break,
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+fn symbol_json(entry: &SymbolEntry) -> serde_json::Value {
+    serde_json::json!({
+        "fq_name": entry.fq_name,
+        "kind": entry.kind.as_str(),
+        "package": entry.package,
+        "namespace": entry.namespace,
+        "local_name": entry.local_name,
+        "file": entry.file.display().to_string(),
+    })
+}
+
+fn print_symbols_usage() {
+    eprintln!("Usage: surc symbols <surv.toml> [options]");
+    eprintln!();
+    eprintln!("List every symbol in the project with its fully-qualified name.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --kind <kind>        Only show symbols of this kind (schema, func, mod)");
+    eprintln!("  --package <name>     Only show symbols in this package");
+    eprintln!("  --format <format>    Output format (text, json, ndjson) [default: text]");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc symbols surv.toml");
+    eprintln!("  surc symbols surv.toml --kind schema --package users");
+    eprintln!("  surc symbols surv.toml --format json > symbols.json");
+    eprintln!("  surc symbols surv.toml --format ndjson | jq -r 'select(.kind == \"func\") | .fq_name'");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use survibe_parser_rs::{parse_surv_str, ProjectAST};
+    use std::path::PathBuf;
+
+    fn table() -> SymbolTable {
+        let file = |path: &str, text: &str| (PathBuf::from(path), parse_surv_str(text).unwrap());
+        let project = ProjectAST::from_files(vec![
+            file(
+                "users.toml",
+                "package = \"users\"\n\n[schema.user]\nkind = \"node\"\n\n\
+                 [func.load]\nintent = \"x\"\n",
+            ),
+            file("api.toml", "package = \"api\"\n\n[schema.request]\nkind = \"node\"\n"),
+        ]);
+        build_symbol_table(&project).0
+    }
+
+    fn names(args: &[&str]) -> Vec<String> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let options = parse_options(&args).unwrap();
+        let table = table();
+        select_symbols(&table, &options)
+            .iter()
+            .map(|entry| entry.fq_name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn filters_symbols_by_kind_and_package() {
+        assert_eq!(
+            names(&[]),
+            [
+                "pkg.api.schema.global.request",
+                "pkg.users.func.global.load",
+                "pkg.users.schema.global.user"
+            ]
+        );
+        assert_eq!(
+            names(&["--kind", "schema"]),
+            ["pkg.api.schema.global.request", "pkg.users.schema.global.user"]
+        );
+        assert_eq!(
+            names(&["--kind", "schema", "--package", "users"]),
+            ["pkg.users.schema.global.user"]
+        );
+        assert!(names(&["--package", "billing"]).is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert!(parse_options(&args(&["--kind", "table"])).is_err());
+        assert!(parse_options(&args(&["--package"])).is_err());
+        assert!(parse_options(&args(&["--verbose"])).is_err());
+    }
+
+    #[test]
+    fn writes_one_json_object_per_line() {
+        let table = table();
+        let entries: Vec<&SymbolEntry> = table.entries.iter().collect();
+        let mut out = Vec::new();
+
+        write_ndjson(&mut out, &entries).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), entries.len());
+        let user = lines
+            .iter()
+            .find(|line| line["fq_name"] == "pkg.users.schema.global.user")
+            .unwrap();
+        assert_eq!(user["kind"], "schema");
+        assert_eq!(user["package"], "users");
+    }
+
+    #[test]
+    fn ndjson_stops_quietly_on_a_closed_pipe() {
+        struct ClosedPipe;
+        impl Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let table = table();
+        let entries: Vec<&SymbolEntry> = table.entries.iter().collect();
+        assert!(write_ndjson(&mut ClosedPipe, &entries).is_ok());
+    }
+}
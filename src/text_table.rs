@@ -0,0 +1,227 @@
+/// Number of terminal columns a string occupies.
+///
+/// East Asian wide and fullwidth characters count as two columns and
+/// combining marks as zero, so Japanese notes line up with ASCII ones.
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Truncate `s` so it fits in `max_width` columns, ending with `…` when cut.
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut result = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = char_width(c);
+        if width + w > max_width - 1 {
+            break;
+        }
+        result.push(c);
+        width += w;
+    }
+    result.push('…');
+    result
+}
+
+fn char_width(c: char) -> usize {
+    let cp = c as u32;
+    if cp == 0 || is_zero_width(cp) {
+        0
+    } else if is_wide(cp) {
+        2
+    } else {
+        1
+    }
+}
+
+fn is_zero_width(cp: u32) -> bool {
+    matches!(
+        cp,
+        0x0300..=0x036F
+            | 0x200B..=0x200F
+            | 0x20D0..=0x20FF
+            | 0x3099..=0x309A
+            | 0xFE00..=0xFE0F
+            | 0xFE20..=0xFE2F
+    )
+}
+
+fn is_wide(cp: u32) -> bool {
+    matches!(
+        cp,
+        0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x1F300..=0x1F64F
+            | 0x1F900..=0x1F9FF
+            | 0x20000..=0x2FFFD
+            | 0x30000..=0x3FFFD
+    )
+}
+
+/// Join cells into one CSV line (RFC 4180), quoting cells that contain
+/// commas, quotes or line breaks.
+pub fn csv_row(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Plain-text table with a header row and width-aware column alignment.
+#[derive(Debug, Clone, Default)]
+pub struct TextTable {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    max_widths: Vec<Option<usize>>,
+    right_aligned: Vec<bool>,
+    indent: usize,
+}
+
+impl TextTable {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+            max_widths: vec![None; headers.len()],
+            right_aligned: vec![false; headers.len()],
+            indent: 0,
+        }
+    }
+
+    /// Limit a column to `width` columns; longer cells are truncated with `…`.
+    pub fn max_width(mut self, column: usize, width: usize) -> Self {
+        self.max_widths[column] = Some(width);
+        self
+    }
+
+    pub fn align_right(mut self, column: usize) -> Self {
+        self.right_aligned[column] = true;
+        self
+    }
+
+    /// Number of spaces printed before every line.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Add a row. Missing cells are rendered empty, extra cells are dropped.
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        let mut row: Vec<String> = cells.into_iter().take(self.headers.len()).collect();
+        row.resize(self.headers.len(), String::new());
+        for (cell, max) in row.iter_mut().zip(&self.max_widths) {
+            if let Some(max) = max {
+                *cell = truncate_to_width(cell, *max);
+            }
+        }
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|col| {
+                self.rows
+                    .iter()
+                    .map(|row| display_width(&row[col]))
+                    .chain(std::iter::once(display_width(&self.headers[col])))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut out = String::new();
+        self.render_line(&mut out, &self.headers, &widths);
+        let separator: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+        self.render_line(&mut out, &separator, &widths);
+        for row in &self.rows {
+            self.render_line(&mut out, row, &widths);
+        }
+        out
+    }
+
+    fn render_line(&self, out: &mut String, cells: &[String], widths: &[usize]) {
+        let mut line = " ".repeat(self.indent);
+        let last = cells.len().saturating_sub(1);
+        for (col, cell) in cells.iter().enumerate() {
+            let padding = " ".repeat(widths[col] - display_width(cell));
+            if self.right_aligned[col] {
+                line.push_str(&padding);
+                line.push_str(cell);
+            } else {
+                line.push_str(cell);
+                if col != last {
+                    line.push_str(&padding);
+                }
+            }
+            if col != last {
+                line.push_str("  ");
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_and_truncates_wide_characters() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("認証モジュール"), 14);
+        assert_eq!(display_width("e\u{301}"), 1);
+
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("abcdefgh", 5), "abcd…");
+        assert_eq!(truncate_to_width("日本語のメモ", 6), "日本…");
+        assert!(display_width(&truncate_to_width("日本語のメモ", 6)) <= 6);
+    }
+
+    #[test]
+    fn escapes_csv_cells() {
+        let row = csv_row(&[
+            "mod.users".to_string(),
+            "create, get done".to_string(),
+            "say \"hi\"".to_string(),
+        ]);
+        assert_eq!(row, "mod.users,\"create, get done\",\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn aligns_columns_by_display_width() {
+        let mut table = TextTable::new(&["MODULE", "NOTES"]).max_width(1, 8);
+        table.add_row(vec!["mod.認証".to_string(), "ok".to_string()]);
+        table.add_row(vec!["mod.users".to_string(), "a very long note".to_string()]);
+        let rendered = table.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "MODULE     NOTES");
+        assert_eq!(lines[2], "mod.認証   ok");
+        assert_eq!(lines[3], "mod.users  a very …");
+        let note_col = |line: &str| display_width(&line[..line.rfind("  ").unwrap()]);
+        assert_eq!(note_col(lines[2]), note_col(lines[3]));
+    }
+}
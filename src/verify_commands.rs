@@ -0,0 +1,59 @@
+use survibe_parser_rs::parse_surv_file;
+use survibe_parser_rs::writer::round_trip_difference;
+use std::error::Error;
+use std::fs::File;
+
+use crate::markers;
+
+pub fn run_verify(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        print_verify_usage();
+        if args.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(other) = args.iter().find(|a| a.starts_with("--")) {
+        return Err(format!("Unknown option: {}", other).into());
+    }
+
+    let mut failed = 0;
+    for path in args {
+        let file = parse_surv_file(File::open(path)?)?;
+        match round_trip_difference(&file) {
+            Ok(None) => println!("{} {}: round-trips cleanly", markers::ok(), path),
+            Ok(Some(difference)) => {
+                failed += 1;
+                println!(
+                    "{} {}: re-parsed AST differs at {}",
+                    markers::err(),
+                    path,
+                    difference.path
+                );
+                println!("    original: {}", difference.original);
+                println!("    reparsed: {}", difference.reparsed);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{} {}: emitted text does not parse: {}", markers::err(), path, e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} file(s) did not round-trip", failed, args.len()).into());
+    }
+    Ok(())
+}
+
+fn print_verify_usage() {
+    eprintln!("Usage: surc verify <file.toml>...");
+    eprintln!();
+    eprintln!("Parse each file, re-emit it with the canonical writer (the one merge");
+    eprintln!("uses), parse the emitted text again and compare the two ASTs. Reports");
+    eprintln!("the first difference per file and exits non-zero if any file diverges.");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  surc verify api.toml");
+    eprintln!("  surc verify examples/*.toml");
+}
@@ -0,0 +1,355 @@
+use crate::ast::{FuncSection, MetaSection, ModSection, SchemaSection, Section, StatusSection, SurvFile};
+use crate::parser::{parse_surv_str, ParseError};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Render a `SurvFile` back to Surv IR TOML text.
+///
+/// Sections are written in the order they appear in `file.sections`; empty
+/// fields are omitted so the output parses back to the same AST.
+pub fn render_surv_file(file: &SurvFile) -> String {
+    let mut output = String::new();
+
+    if let Some(package) = &file.package {
+        output.push_str(&format!("package = {}\n", quote(package)));
+    }
+    if let Some(namespace) = &file.namespace {
+        output.push_str(&format!("namespace = {}\n", quote(namespace)));
+    }
+    if !file.imports.is_empty() {
+        let imports: Vec<String> = file
+            .imports
+            .iter()
+            .map(|import| match &import.alias {
+                Some(alias) => format!("{} as {}", import.target, alias),
+                None => import.target.clone(),
+            })
+            .collect();
+        output.push_str(&format!("import = {}\n", array(&imports)));
+    }
+    if !file.requires.is_empty() {
+        let requires: Vec<String> = file.requires.iter().map(|r| r.target.clone()).collect();
+        output.push_str(&format!("require = {}\n", array(&requires)));
+    }
+
+    for section in &file.sections {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        match section {
+            Section::Meta(meta) => render_meta(&mut output, meta),
+            Section::Schema(schema) => render_schema(&mut output, schema),
+            Section::Func(func) => render_func(&mut output, func),
+            Section::Mod(module) => render_mod(&mut output, module),
+            Section::Status(status) => render_status(&mut output, status),
+        }
+    }
+
+    output
+}
+
+/// The first place where a file and its re-parsed rendering disagree.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundTripDifference {
+    /// Where the ASTs diverge, e.g. `schema.user.fields.id` or `imports[0].alias`
+    pub path: String,
+    pub original: Value,
+    pub reparsed: Value,
+}
+
+/// Render `file`, parse the text back and compare the two ASTs (`surc verify`).
+///
+/// Returns the first difference found, or `None` when nothing was lost.
+pub fn round_trip_difference(file: &SurvFile) -> Result<Option<RoundTripDifference>, ParseError> {
+    let reparsed = parse_surv_str(&render_surv_file(file))?;
+    Ok(first_difference(file, &reparsed))
+}
+
+fn first_difference(original: &SurvFile, reparsed: &SurvFile) -> Option<RoundTripDifference> {
+    let (a, b) = (to_value(original), to_value(reparsed));
+    for key in ["package", "namespace", "imports", "requires"] {
+        if let Some(difference) = value_difference(key.to_string(), &a[key], &b[key]) {
+            return Some(difference);
+        }
+    }
+
+    let ids = |file: &SurvFile| -> Vec<String> { file.sections.iter().map(section_id).collect() };
+    let (a_ids, b_ids) = (ids(original), ids(reparsed));
+    if a_ids != b_ids {
+        return Some(RoundTripDifference {
+            path: "sections".to_string(),
+            original: a_ids.into(),
+            reparsed: b_ids.into(),
+        });
+    }
+    original
+        .sections
+        .iter()
+        .zip(&reparsed.sections)
+        .find_map(|(a, b)| value_difference(section_id(a), &section_value(a), &section_value(b)))
+}
+
+fn value_difference(path: String, a: &Value, b: &Value) -> Option<RoundTripDifference> {
+    if a == b {
+        return None;
+    }
+    match (a, b) {
+        (Value::Object(a_map), Value::Object(b_map)) => {
+            let missing = b_map.keys().filter(|key| !a_map.contains_key(*key));
+            a_map.keys().chain(missing).find_map(|key| {
+                let (a, b) = (&a_map.get(key), &b_map.get(key));
+                let (a, b) = (a.unwrap_or(&Value::Null), b.unwrap_or(&Value::Null));
+                value_difference(format!("{}.{}", path, key), a, b)
+            })
+        }
+        (Value::Array(a_items), Value::Array(b_items)) if a_items.len() == b_items.len() => a_items
+            .iter()
+            .zip(b_items)
+            .enumerate()
+            .find_map(|(i, (a, b))| value_difference(format!("{}[{}]", path, i), a, b)),
+        _ => Some(RoundTripDifference {
+            path,
+            original: a.clone(),
+            reparsed: b.clone(),
+        }),
+    }
+}
+
+fn section_id(section: &Section) -> String {
+    match section {
+        Section::Meta(_) => "meta".to_string(),
+        Section::Schema(schema) => format!("schema.{}", schema.name),
+        Section::Func(func) => format!("func.{}", func.name),
+        Section::Mod(module) => format!("mod.{}", module.name),
+        Section::Status(_) => "status".to_string(),
+    }
+}
+
+fn section_value(section: &Section) -> Value {
+    match section {
+        Section::Meta(meta) => to_value(meta),
+        Section::Schema(schema) => to_value(schema),
+        Section::Func(func) => to_value(func),
+        Section::Mod(module) => to_value(module),
+        Section::Status(status) => to_value(status),
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn render_meta(output: &mut String, meta: &MetaSection) {
+    output.push_str("[meta]\n");
+    push_string(output, "name", &meta.name);
+    push_string(output, "version", &meta.version);
+    push_string(output, "description", &meta.description);
+}
+
+fn render_schema(output: &mut String, schema: &SchemaSection) {
+    output.push_str(&format!("[schema.{}]\n", schema.name));
+    push_string(output, "kind", &schema.kind);
+    push_string(output, "role", &schema.role);
+    push_string(output, "type", &schema.r#type);
+    push_string(output, "from", &schema.from);
+    push_string(output, "to", &schema.to);
+    push_string(output, "base", &schema.base);
+    push_string(output, "label", &schema.label);
+    push_array(output, "over", &schema.over);
+    if schema.deprecated {
+        output.push_str("deprecated = true\n");
+    }
+    push_string(output, "deprecated_note", &schema.deprecated_note);
+    if !schema.fields.is_empty() {
+        let fields: Vec<String> = schema
+            .fields
+            .iter()
+            .map(|(name, ty)| format!("{} = {}", name, quote(ty)))
+            .collect();
+        output.push_str(&format!("fields = {{ {} }}\n", fields.join(", ")));
+    }
+    push_impl(output, &schema.impl_bind, &schema.impl_lang, &schema.impl_path);
+}
+
+fn render_func(output: &mut String, func: &FuncSection) {
+    output.push_str(&format!("[func.{}]\n", func.name));
+    push_string(output, "intent", &func.intent);
+    push_array(output, "input", &func.input);
+    push_array(output, "output", &func.output);
+    push_string(output, "design_notes", &func.design_notes);
+    push_impl(output, &func.impl_bind, &func.impl_lang, &func.impl_path);
+}
+
+fn render_mod(output: &mut String, module: &ModSection) {
+    output.push_str(&format!("[mod.{}]\n", module.name));
+    push_string(output, "purpose", &module.purpose);
+    push_string(output, "kind", &module.kind);
+    push_array(output, "schemas", &module.schemas);
+    push_array(output, "funcs", &module.funcs);
+    push_array(output, "pipeline", &pipeline_entries(module));
+    push_array(output, "owners", &module.owners);
+}
+
+/// Values for a module's `pipeline` key. Separate chains and parallel
+/// branches only survive as one `->` chain string per chain; a single
+/// sequential chain is written as its plain steps.
+pub fn pipeline_entries(module: &ModSection) -> Vec<String> {
+    let parallel = module.pipeline_chains.iter().flatten().any(|stage| stage.len() > 1);
+    if module.pipeline_chains.len() > 1 || parallel {
+        module.pipeline_chains.iter().map(|chain| render_chain(chain)).collect()
+    } else {
+        module.pipeline_chains.concat().concat()
+    }
+}
+
+/// `func.a -> { func.b, func.c } -> func.d`
+fn render_chain(stages: &[Vec<String>]) -> String {
+    let stages: Vec<String> = stages
+        .iter()
+        .map(|stage| match stage.as_slice() {
+            [step] => step.clone(),
+            steps => format!("{{ {} }}", steps.join(", ")),
+        })
+        .collect();
+    stages.join(" -> ")
+}
+
+fn render_status(output: &mut String, status: &StatusSection) {
+    output.push_str("[status]\n");
+    push_string(output, "updated_at", &status.updated_at);
+    for (name, module) in &status.modules {
+        output.push_str(&format!("\n[status.mod.{}]\n", name));
+        push_string(output, "state", &module.state);
+        if module.coverage > 0.0 {
+            output.push_str(&format!("coverage = {}\n", module.coverage));
+        }
+        push_string(output, "notes", &module.notes);
+        push_string(output, "updated_at", &module.updated_at);
+    }
+}
+
+fn push_string(output: &mut String, key: &str, value: &str) {
+    if !value.is_empty() {
+        output.push_str(&format!("{} = {}\n", key, quote(value)));
+    }
+}
+
+fn push_array(output: &mut String, key: &str, values: &[String]) {
+    if !values.is_empty() {
+        output.push_str(&format!("{} = {}\n", key, array(values)));
+    }
+}
+
+fn push_impl(
+    output: &mut String,
+    bind: &Option<String>,
+    lang: &Option<String>,
+    path: &Option<String>,
+) {
+    for (key, value) in [("impl.bind", bind), ("impl.lang", lang), ("impl.path", path)] {
+        if let Some(value) = value {
+            output.push_str(&format!("{} = {}\n", key, quote(value)));
+        }
+    }
+}
+
+fn array(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|v| quote(v)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_surv_str;
+
+    #[test]
+    fn round_trips_through_the_parser() {
+        let ir = r#"
+package = "users"
+import = ["common as c"]
+require = ["mod.auth"]
+
+[meta]
+name = "users"
+
+[schema.user]
+kind = "node"
+role = "entity"
+fields = { id = "string", name = "string" }
+impl.bind = "User"
+
+[schema.legacy_user]
+kind = "node"
+deprecated = true
+deprecated_note = "use schema.user"
+
+[func.create_user]
+intent = "Create a \"new\" user"
+input = ["schema.user"]
+output = ["schema.user"]
+
+[mod.users]
+purpose = "Users"
+schemas = ["schema.user"]
+funcs = ["func.create_user"]
+pipeline = "func.create_user -> { func.a, func.b }"
+
+[mod.jobs]
+purpose = "Jobs"
+pipeline = ["func.a -> func.b", "func.c"]
+
+[status]
+updated_at = "2026-01-01"
+
+[status.mod.users]
+state = "partial"
+coverage = 0.5
+updated_at = "2026-01-01"
+"#;
+        let original = parse_surv_str(ir).unwrap();
+        let rendered = render_surv_file(&original);
+        let reparsed = parse_surv_str(&rendered).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&original).unwrap(),
+            serde_json::to_value(&reparsed).unwrap()
+        );
+        assert!(round_trip_difference(&original).unwrap().is_none());
+    }
+
+    #[test]
+    fn reports_the_first_field_lost_in_the_round_trip() {
+        let mut file = parse_surv_str(
+            r#"
+[schema.user]
+kind = "node"
+fields = { id = "string" }
+
+[mod.users]
+purpose = "Users"
+"#,
+        )
+        .unwrap();
+        // A flat `pipeline` that disagrees with the chains cannot be written back
+        for section in &mut file.sections {
+            if let Section::Mod(module) = section {
+                module.pipeline = vec!["func.a".to_string()];
+            }
+        }
+
+        let difference = round_trip_difference(&file).unwrap().unwrap();
+        assert_eq!(difference.path, "mod.users.pipeline");
+        assert_eq!(difference.original, serde_json::json!(["func.a"]));
+        assert_eq!(difference.reparsed, serde_json::json!([]));
+    }
+}